@@ -0,0 +1,322 @@
+//! Evaluation of conditional requests.
+//!
+//! [RFC7232] defines a set of request headers which make a request conditional on the current
+//! state of the target resource. The `evaluate` function applies those preconditions in the
+//! order specified by [section 6] of that RFC, and reports how the server should respond.
+//!
+//! [RFC7232]: https://tools.ietf.org/html/rfc7232
+//! [section 6]: https://tools.ietf.org/html/rfc7232#section-6
+use http::header::{HeaderMap, RANGE};
+use http::Method;
+
+use crate::{
+    EntityTag, Error, HeaderMapExt, HttpDate, IfMatch, IfModifiedSince, IfNoneMatch, IfRange,
+    IfUnmodifiedSince,
+};
+
+/// The validators of the current representation of a resource.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    /// The entity tag of the representation, if it has one.
+    pub etag: Option<EntityTag>,
+    /// The last modification date of the representation, if it is known.
+    pub last_modified: Option<HttpDate>,
+}
+
+/// The outcome of evaluating the preconditions of a request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Precondition {
+    /// All preconditions passed, and the request should be processed normally, including any
+    /// `Range` header.
+    Proceed,
+    /// All preconditions passed, but the `If-Range` condition did not hold. The request should be
+    /// processed as if the `Range` header was absent, and the full representation returned.
+    IgnoreRange,
+    /// The server should respond with a 304 (Not Modified) status.
+    NotModified,
+    /// The server should respond with a 412 (Precondition Failed) status.
+    PreconditionFailed,
+}
+
+/// Evaluates the preconditions of a request.
+///
+/// `current` contains the validators of the current representation of the target resource, or
+/// `None` if the resource has no current representation.
+///
+/// The `If-Match`, `If-Unmodified-Since`, `If-None-Match`, `If-Modified-Since`, and `If-Range`
+/// headers are evaluated in that order. Date-based conditions are ignored if their header is not
+/// a valid HTTP date, as required by the RFC, and an `If-Range` header which fails to parse is
+/// treated as not matching. An error is returned if the `If-Match` or `If-None-Match` header is
+/// malformed.
+///
+/// The RFC permits servers to skip evaluation for requests whose response status would be
+/// anything other than 2xx or 412, so callers should only use this function once they know the
+/// request would otherwise succeed.
+pub fn evaluate(
+    headers: &HeaderMap,
+    method: &Method,
+    current: Option<&Validators>,
+) -> Result<Precondition, Error> {
+    let etag = current.and_then(|v| v.etag.as_ref());
+    let last_modified = current.and_then(|v| v.last_modified.as_ref());
+
+    // step 1 and 2
+    match headers.typed_get::<IfMatch>()? {
+        Some(if_match) => {
+            let pass = match if_match {
+                IfMatch::Any => current.is_some(),
+                IfMatch::Tags(_) => etag.is_some_and(|etag| if_match.matches(etag)),
+            };
+            if !pass {
+                return Ok(Precondition::PreconditionFailed);
+            }
+        }
+        None => {
+            if let (Some(date), Some(last_modified)) =
+                (date_header::<IfUnmodifiedSince>(headers), last_modified)
+            {
+                if *last_modified > date.0 {
+                    return Ok(Precondition::PreconditionFailed);
+                }
+            }
+        }
+    }
+
+    let safe = *method == Method::GET || *method == Method::HEAD;
+
+    // step 3 and 4
+    match headers.typed_get::<IfNoneMatch>()? {
+        Some(if_none_match) => {
+            let matched = match if_none_match {
+                IfNoneMatch::Any => current.is_some(),
+                IfNoneMatch::Tags(_) => etag.is_some_and(|etag| if_none_match.matches(etag)),
+            };
+            if matched {
+                return if safe {
+                    Ok(Precondition::NotModified)
+                } else {
+                    Ok(Precondition::PreconditionFailed)
+                };
+            }
+        }
+        None => {
+            if safe {
+                if let (Some(date), Some(last_modified)) =
+                    (date_header::<IfModifiedSince>(headers), last_modified)
+                {
+                    if *last_modified <= date.0 {
+                        return Ok(Precondition::NotModified);
+                    }
+                }
+            }
+        }
+    }
+
+    // step 5
+    if *method == Method::GET && headers.contains_key(RANGE) {
+        let matched = match headers.typed_get::<IfRange>() {
+            Ok(Some(IfRange::EntityTag(ref tag))) => etag.is_some_and(|etag| etag.strong_eq(tag)),
+            Ok(Some(IfRange::HttpDate(ref date))) => {
+                last_modified.is_some_and(|last_modified| last_modified == date)
+            }
+            Ok(None) => true,
+            Err(_) => false,
+        };
+        if !matched {
+            return Ok(Precondition::IgnoreRange);
+        }
+    }
+
+    Ok(Precondition::Proceed)
+}
+
+// Date headers which fail to parse must be ignored.
+fn date_header<H>(headers: &HeaderMap) -> Option<H>
+where
+    H: crate::Header,
+{
+    headers.typed_get::<H>().ok().and_then(|h| h)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::HeaderValue;
+
+    const EARLY: &str = "Sat, 29 Oct 1994 19:43:31 GMT";
+    const LATE: &str = "Sun, 30 Oct 1994 19:43:31 GMT";
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &(name, value) in pairs {
+            headers.append(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn validators(etag: &str, last_modified: &str) -> Validators {
+        Validators {
+            etag: Some(etag.parse().unwrap()),
+            last_modified: Some(last_modified.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn no_preconditions() {
+        let current = validators("\"a\"", EARLY);
+        let result = evaluate(&headers(&[]), &Method::GET, Some(&current)).unwrap();
+        assert_eq!(result, Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_match() {
+        let current = validators("\"a\"", EARLY);
+        let cases = [
+            ("\"a\"", Precondition::Proceed),
+            ("\"b\", \"a\"", Precondition::Proceed),
+            ("W/\"a\"", Precondition::PreconditionFailed),
+            ("\"b\"", Precondition::PreconditionFailed),
+            ("*", Precondition::Proceed),
+        ];
+        for &(value, expected) in &cases {
+            let headers = headers(&[("If-Match", value)]);
+            let result = evaluate(&headers, &Method::PUT, Some(&current)).unwrap();
+            assert_eq!(result, expected, "{}", value);
+        }
+
+        let headers = headers(&[("If-Match", "*")]);
+        let result = evaluate(&headers, &Method::PUT, None).unwrap();
+        assert_eq!(result, Precondition::PreconditionFailed);
+    }
+
+    #[test]
+    fn if_match_overrides_if_unmodified_since() {
+        let current = validators("\"a\"", LATE);
+        let headers = headers(&[("If-Match", "\"a\""), ("If-Unmodified-Since", EARLY)]);
+        let result = evaluate(&headers, &Method::PUT, Some(&current)).unwrap();
+        assert_eq!(result, Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_unmodified_since() {
+        let current = validators("\"a\"", EARLY);
+        let cases = [
+            (EARLY, Precondition::Proceed),
+            (LATE, Precondition::Proceed),
+            (
+                "Sat, 29 Oct 1994 19:43:30 GMT",
+                Precondition::PreconditionFailed,
+            ),
+            ("garbage", Precondition::Proceed),
+        ];
+        for &(value, expected) in &cases {
+            let headers = headers(&[("If-Unmodified-Since", value)]);
+            let result = evaluate(&headers, &Method::DELETE, Some(&current)).unwrap();
+            assert_eq!(result, expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn if_none_match() {
+        let current = validators("\"a\"", EARLY);
+        let cases = [
+            ("\"a\"", &Method::GET, Precondition::NotModified),
+            ("W/\"a\"", &Method::HEAD, Precondition::NotModified),
+            ("\"b\"", &Method::GET, Precondition::Proceed),
+            ("*", &Method::GET, Precondition::NotModified),
+            ("\"a\"", &Method::PUT, Precondition::PreconditionFailed),
+            ("*", &Method::PUT, Precondition::PreconditionFailed),
+        ];
+        for &(value, method, expected) in &cases {
+            let headers = headers(&[("If-None-Match", value)]);
+            let result = evaluate(&headers, method, Some(&current)).unwrap();
+            assert_eq!(result, expected, "{} {}", method, value);
+        }
+
+        let headers = headers(&[("If-None-Match", "*")]);
+        let result = evaluate(&headers, &Method::PUT, None).unwrap();
+        assert_eq!(result, Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_none_match_overrides_if_modified_since() {
+        let current = validators("\"a\"", EARLY);
+        let headers = headers(&[("If-None-Match", "\"b\""), ("If-Modified-Since", LATE)]);
+        let result = evaluate(&headers, &Method::GET, Some(&current)).unwrap();
+        assert_eq!(result, Precondition::Proceed);
+    }
+
+    #[test]
+    fn if_modified_since() {
+        let current = validators("\"a\"", EARLY);
+        let cases = [
+            (EARLY, &Method::GET, Precondition::NotModified),
+            (LATE, &Method::HEAD, Precondition::NotModified),
+            (
+                "Sat, 29 Oct 1994 19:43:30 GMT",
+                &Method::GET,
+                Precondition::Proceed,
+            ),
+            (LATE, &Method::POST, Precondition::Proceed),
+            ("garbage", &Method::GET, Precondition::Proceed),
+        ];
+        for &(value, method, expected) in &cases {
+            let headers = headers(&[("If-Modified-Since", value)]);
+            let result = evaluate(&headers, method, Some(&current)).unwrap();
+            assert_eq!(result, expected, "{} {}", method, value);
+        }
+    }
+
+    #[test]
+    fn if_range() {
+        let current = validators("\"a\"", EARLY);
+        let cases = [
+            ("\"a\"", Precondition::Proceed),
+            ("\"b\"", Precondition::IgnoreRange),
+            ("W/\"a\"", Precondition::IgnoreRange),
+            (EARLY, Precondition::Proceed),
+            (LATE, Precondition::IgnoreRange),
+            ("garbage", Precondition::IgnoreRange),
+        ];
+        for &(value, expected) in &cases {
+            let headers = headers(&[("Range", "bytes=0-10"), ("If-Range", value)]);
+            let result = evaluate(&headers, &Method::GET, Some(&current)).unwrap();
+            assert_eq!(result, expected, "{}", value);
+        }
+
+        let headers = headers(&[("If-Range", "\"b\"")]);
+        let result = evaluate(&headers, &Method::GET, Some(&current)).unwrap();
+        assert_eq!(result, Precondition::Proceed);
+    }
+
+    #[test]
+    fn precedence() {
+        let current = validators("\"a\"", EARLY);
+        let headers = headers(&[
+            ("If-Match", "\"b\""),
+            ("If-None-Match", "\"a\""),
+            ("If-Modified-Since", LATE),
+        ]);
+        let result = evaluate(&headers, &Method::GET, Some(&current)).unwrap();
+        assert_eq!(result, Precondition::PreconditionFailed);
+    }
+
+    #[test]
+    fn sub_second_precision() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let current = Validators {
+            etag: None,
+            last_modified: Some(HttpDate::from(UNIX_EPOCH + Duration::from_millis(1500))),
+        };
+        let headers = headers(&[("If-Modified-Since", "Thu, 01 Jan 1970 00:00:01 GMT")]);
+        let result = evaluate(&headers, &Method::GET, Some(&current)).unwrap();
+        assert_eq!(result, Precondition::NotModified);
+    }
+
+    #[test]
+    fn malformed() {
+        let headers = headers(&[("If-Match", "a")]);
+        assert!(evaluate(&headers, &Method::GET, None).is_err());
+    }
+}
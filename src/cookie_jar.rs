@@ -0,0 +1,541 @@
+//! A client-side cookie store.
+//!
+//! The `CookieJar` type implements the storage model of [RFC6265bis]: it ingests cookies from
+//! `Set-Cookie` response headers, and produces the `Cookie` header for subsequent requests.
+//!
+//! The jar does not consult the public suffix list. Instead, it treats single-label domains such
+//! as `com` as public suffixes, and refuses to set cookies for them from any other host.
+//!
+//! [RFC6265bis]: https://tools.ietf.org/html/draft-ietf-httpbis-rfc6265bis-12#section-5
+use http::header::{HeaderMap, SET_COOKIE};
+use http::Uri;
+use std::net::Ipv4Addr;
+use std::time::{Duration, SystemTime};
+
+use crate::{util, Cookie, HeaderMapExt, ResponseCookie, SameSite, SetCookie};
+
+// User agents must limit cookie lifetimes to 400 days.
+const MAX_LIFETIME: Duration = Duration::from_secs(400 * 24 * 60 * 60);
+
+#[derive(Debug, Clone)]
+struct StoredCookie {
+    name: String,
+    value: String,
+    domain: String,
+    host_only: bool,
+    path: String,
+    secure: bool,
+    expires: Option<SystemTime>,
+    creation: u64,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, host: &str, path: &str, secure: bool) -> bool {
+        let domain_matches = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(host, &self.domain)
+        };
+
+        domain_matches && path_match(path, &self.path) && (secure || !self.secure)
+    }
+}
+
+/// A store of cookies received from servers.
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    cookies: Vec<StoredCookie>,
+    next_creation: u64,
+}
+
+impl CookieJar {
+    /// Creates an empty cookie jar.
+    #[inline]
+    pub fn new() -> CookieJar {
+        CookieJar::default()
+    }
+
+    /// Returns the number of cookies in the jar, including any which have expired.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    /// Determines if the jar contains no cookies.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    /// Stores a cookie received in response to a request for `request_uri`.
+    ///
+    /// Returns `false` if the cookie was rejected. Cookies are rejected if their `Domain`
+    /// attribute does not match the request's host or is a public suffix, if they are `Secure`
+    /// or use the `__Secure-` or `__Host-` prefixes but were not received over a secure
+    /// connection, if they use `SameSite=None` without `Secure`, or if they would overwrite a
+    /// `Secure` cookie from an insecure connection.
+    ///
+    /// A cookie which has already expired is not stored, but removes any existing cookie with
+    /// the same name, domain, and path.
+    pub fn store(&mut self, cookie: &ResponseCookie, request_uri: &Uri, now: SystemTime) -> bool {
+        let host = match request_uri.host() {
+            Some(host) => host.to_ascii_lowercase(),
+            None => return false,
+        };
+        let secure_origin = is_secure(request_uri);
+
+        if cookie.secure() && !secure_origin {
+            return false;
+        }
+        if cookie.same_site() == Some(SameSite::None) && !cookie.secure() {
+            return false;
+        }
+
+        let (domain, host_only) = match cookie.domain() {
+            Some(domain) => {
+                if is_public_suffix(domain) {
+                    if domain != host {
+                        return false;
+                    }
+                    (host.clone(), true)
+                } else if !domain_match(&host, domain) {
+                    return false;
+                } else {
+                    (domain.to_string(), false)
+                }
+            }
+            None => (host.clone(), true),
+        };
+
+        let path = match cookie.path() {
+            Some(path) => path.to_string(),
+            None => default_path(request_uri.path()),
+        };
+
+        if starts_with_ignore_case(cookie.name(), "__Secure-")
+            && !(cookie.secure() && secure_origin)
+        {
+            return false;
+        }
+        if starts_with_ignore_case(cookie.name(), "__Host-")
+            && !(cookie.secure() && secure_origin && host_only && path == "/")
+        {
+            return false;
+        }
+
+        if !cookie.secure() && !secure_origin {
+            let shadows_secure = self.cookies.iter().any(|c| {
+                c.secure
+                    && c.name == cookie.name()
+                    && (domain_match(&domain, &c.domain) || domain_match(&c.domain, &domain))
+                    && path_match(&path, &c.path)
+            });
+            if shadows_secure {
+                return false;
+            }
+        }
+
+        let expires = match (cookie.max_age(), cookie.expires()) {
+            (Some(max_age), _) if max_age <= 0 => Some(SystemTime::UNIX_EPOCH),
+            (Some(max_age), _) => {
+                Some(now + Duration::min(Duration::from_secs(max_age as u64), MAX_LIFETIME))
+            }
+            (None, Some(expires)) => Some(SystemTime::min(
+                SystemTime::from(expires.clone()),
+                now + MAX_LIFETIME,
+            )),
+            (None, None) => None,
+        };
+
+        let existing = self
+            .cookies
+            .iter()
+            .position(|c| c.name == cookie.name() && c.domain == domain && c.path == path);
+        let creation = match existing {
+            Some(idx) => self.cookies.remove(idx).creation,
+            None => {
+                self.next_creation += 1;
+                self.next_creation
+            }
+        };
+
+        let stored = StoredCookie {
+            name: cookie.name().to_string(),
+            value: cookie.value().to_string(),
+            domain,
+            host_only,
+            path,
+            secure: cookie.secure(),
+            expires,
+            creation,
+        };
+        if stored.is_expired(now) {
+            return false;
+        }
+
+        self.cookies.push(stored);
+        true
+    }
+
+    /// Stores all cookies in a `Set-Cookie` header received in response to a request for
+    /// `request_uri`.
+    pub fn store_all(&mut self, set_cookie: &SetCookie, request_uri: &Uri, now: SystemTime) {
+        for cookie in set_cookie.iter() {
+            self.store(cookie, request_uri, now);
+        }
+    }
+
+    /// Stores the cookies in the `Set-Cookie` headers of a response to a request for
+    /// `request_uri`.
+    ///
+    /// Unlike parsing the `SetCookie` header as a whole, malformed cookies are skipped rather
+    /// than causing the remaining cookies to be discarded.
+    pub fn store_response(&mut self, headers: &HeaderMap, request_uri: &Uri, now: SystemTime) {
+        for value in headers.get_all(SET_COOKIE) {
+            let cookie = util::to_str(value)
+                .ok()
+                .and_then(|s| s.parse::<ResponseCookie>().ok());
+            if let Some(cookie) = cookie {
+                self.store(&cookie, request_uri, now);
+            }
+        }
+    }
+
+    /// Returns the cookies which should be sent with a request for `request_uri`.
+    ///
+    /// Cookies with longer paths are listed first, followed by those created earlier. `None` is
+    /// returned if no cookies apply.
+    pub fn cookies(&self, request_uri: &Uri, now: SystemTime) -> Option<Cookie> {
+        let host = request_uri.host()?.to_ascii_lowercase();
+        let path = match request_uri.path() {
+            "" => "/",
+            path => path,
+        };
+        let secure = is_secure(request_uri);
+
+        let mut matches = self
+            .cookies
+            .iter()
+            .filter(|c| !c.is_expired(now) && c.matches(&host, path, secure))
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            return None;
+        }
+        matches.sort_by(|a, b| {
+            b.path
+                .len()
+                .cmp(&a.path.len())
+                .then(a.creation.cmp(&b.creation))
+        });
+
+        let mut header = Cookie::new();
+        for cookie in matches {
+            header
+                .append(&cookie.name, &cookie.value)
+                .expect("stored cookies should be valid");
+        }
+        Some(header)
+    }
+
+    /// Inserts a `Cookie` header for a request for `request_uri`, replacing any existing header.
+    ///
+    /// The headers are left untouched if no cookies apply.
+    pub fn add_cookie_header(&self, headers: &mut HeaderMap, request_uri: &Uri, now: SystemTime) {
+        if let Some(cookie) = self.cookies(request_uri, now) {
+            headers.typed_insert(&cookie);
+        }
+    }
+
+    /// Removes all expired cookies from the jar.
+    pub fn remove_expired(&mut self, now: SystemTime) {
+        self.cookies.retain(|c| !c.is_expired(now));
+    }
+
+    /// Removes all cookies from the jar.
+    pub fn clear(&mut self) {
+        self.cookies.clear();
+    }
+}
+
+fn is_secure(uri: &Uri) -> bool {
+    matches!(uri.scheme_str(), Some("https") | Some("wss"))
+}
+
+fn is_ip_address(host: &str) -> bool {
+    host.starts_with('[') || host.parse::<Ipv4Addr>().is_ok()
+}
+
+fn is_public_suffix(domain: &str) -> bool {
+    !domain.contains('.')
+}
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.get(..prefix.len())
+        .is_some_and(|s| s.eq_ignore_ascii_case(prefix))
+}
+
+// https://tools.ietf.org/html/draft-ietf-httpbis-rfc6265bis-12#section-5.1.3
+fn domain_match(host: &str, domain: &str) -> bool {
+    if host == domain {
+        return true;
+    }
+
+    !is_ip_address(host)
+        && host.len() > domain.len()
+        && host.ends_with(domain)
+        && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+}
+
+// https://tools.ietf.org/html/draft-ietf-httpbis-rfc6265bis-12#section-5.1.4
+fn default_path(path: &str) -> String {
+    if !path.starts_with('/') {
+        return "/".to_string();
+    }
+
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(idx) => path[..idx].to_string(),
+    }
+}
+
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+    if request_path == cookie_path {
+        return true;
+    }
+
+    request_path.starts_with(cookie_path)
+        && (cookie_path.ends_with('/') || request_path.as_bytes()[cookie_path.len()] == b'/')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::{HeaderValue, COOKIE};
+    use std::time::UNIX_EPOCH;
+
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_000_000_000)
+    }
+
+    fn store(jar: &mut CookieJar, cookie: &str, uri: &str) -> bool {
+        jar.store(&cookie.parse().unwrap(), &uri.parse().unwrap(), now())
+    }
+
+    fn cookies(jar: &CookieJar, uri: &str) -> Option<String> {
+        jar.cookies(&uri.parse().unwrap(), now())
+            .map(|c| c.to_string())
+    }
+
+    #[test]
+    fn host_only() {
+        let mut jar = CookieJar::new();
+        assert!(store(&mut jar, "a=1", "http://example.com/"));
+        assert_eq!(
+            cookies(&jar, "http://example.com/"),
+            Some("a=1".to_string())
+        );
+        assert_eq!(
+            cookies(&jar, "http://EXAMPLE.com/foo"),
+            Some("a=1".to_string())
+        );
+        assert_eq!(cookies(&jar, "http://www.example.com/"), None);
+        assert_eq!(cookies(&jar, "http://example.org/"), None);
+    }
+
+    #[test]
+    fn domain() {
+        let mut jar = CookieJar::new();
+        assert!(store(
+            &mut jar,
+            "a=1; Domain=.example.com",
+            "http://www.example.com/"
+        ));
+        assert_eq!(
+            cookies(&jar, "http://example.com/"),
+            Some("a=1".to_string())
+        );
+        assert_eq!(
+            cookies(&jar, "http://foo.www.example.com/"),
+            Some("a=1".to_string())
+        );
+        assert_eq!(cookies(&jar, "http://badexample.com/"), None);
+
+        assert!(!store(
+            &mut jar,
+            "b=1; Domain=other.com",
+            "http://www.example.com/"
+        ));
+        assert!(!store(
+            &mut jar,
+            "b=1; Domain=www.example.com",
+            "http://example.com/"
+        ));
+        assert!(!store(&mut jar, "b=1; Domain=com", "http://example.com/"));
+        assert!(store(
+            &mut jar,
+            "b=1; Domain=localhost",
+            "http://localhost/"
+        ));
+        assert!(!store(&mut jar, "b=1; Domain=0.1", "http://127.0.0.1/"));
+    }
+
+    #[test]
+    fn path() {
+        let mut jar = CookieJar::new();
+        assert!(store(&mut jar, "a=1", "http://example.com/docs/index.html"));
+        assert!(store(
+            &mut jar,
+            "b=2; Path=/docs/web",
+            "http://example.com/"
+        ));
+        assert!(store(&mut jar, "c=3; Path=/", "http://example.com/"));
+
+        assert_eq!(
+            cookies(&jar, "http://example.com/docs/web/page"),
+            Some("b=2; a=1; c=3".to_string())
+        );
+        assert_eq!(
+            cookies(&jar, "http://example.com/docs"),
+            Some("a=1; c=3".to_string())
+        );
+        assert_eq!(
+            cookies(&jar, "http://example.com/docsweb"),
+            Some("c=3".to_string())
+        );
+        assert_eq!(
+            cookies(&jar, "http://example.com/docs/webby"),
+            Some("a=1; c=3".to_string())
+        );
+    }
+
+    #[test]
+    fn secure() {
+        let mut jar = CookieJar::new();
+        assert!(!store(&mut jar, "a=1; Secure", "http://example.com/"));
+        assert!(store(&mut jar, "a=1; Secure", "https://example.com/"));
+        assert_eq!(cookies(&jar, "http://example.com/"), None);
+        assert_eq!(
+            cookies(&jar, "https://example.com/"),
+            Some("a=1".to_string())
+        );
+
+        assert!(!store(&mut jar, "a=2", "http://example.com/"));
+        assert!(store(&mut jar, "a=2", "https://example.com/"));
+        assert_eq!(
+            cookies(&jar, "http://example.com/"),
+            Some("a=2".to_string())
+        );
+
+        assert!(!store(
+            &mut jar,
+            "b=1; SameSite=None",
+            "https://example.com/"
+        ));
+        assert!(store(
+            &mut jar,
+            "b=1; SameSite=None; Secure",
+            "https://example.com/"
+        ));
+    }
+
+    #[test]
+    fn prefixes() {
+        let mut jar = CookieJar::new();
+        assert!(!store(&mut jar, "__Secure-a=1", "https://example.com/"));
+        assert!(store(
+            &mut jar,
+            "__Secure-a=1; Secure",
+            "https://example.com/"
+        ));
+        assert!(!store(
+            &mut jar,
+            "__Host-a=1; Secure",
+            "https://example.com/docs/a"
+        ));
+        assert!(!store(
+            &mut jar,
+            "__Host-a=1; Secure; Path=/; Domain=example.com",
+            "https://example.com/"
+        ));
+        assert!(store(
+            &mut jar,
+            "__Host-a=1; Secure; Path=/",
+            "https://example.com/"
+        ));
+    }
+
+    #[test]
+    fn expiry() {
+        let mut jar = CookieJar::new();
+        assert!(store(&mut jar, "a=1; Max-Age=60", "http://example.com/"));
+        assert!(store(
+            &mut jar,
+            "b=1; Expires=Sun, 09 Sep 2001 02:46:40 GMT",
+            "http://example.com/"
+        ));
+        assert!(store(
+            &mut jar,
+            "c=1; Max-Age=60; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+            "http://example.com/"
+        ));
+        assert_eq!(
+            cookies(&jar, "http://example.com/"),
+            Some("a=1; b=1; c=1".to_string())
+        );
+
+        let later = now() + Duration::from_secs(61);
+        assert_eq!(
+            jar.cookies(&"http://example.com/".parse().unwrap(), later)
+                .unwrap()
+                .to_string(),
+            "b=1"
+        );
+        jar.remove_expired(later);
+        assert_eq!(jar.len(), 1);
+
+        assert!(!store(&mut jar, "b=2; Max-Age=0", "http://example.com/"));
+        assert!(jar.is_empty());
+    }
+
+    #[test]
+    fn replace() {
+        let mut jar = CookieJar::new();
+        assert!(store(&mut jar, "a=1", "http://example.com/"));
+        assert!(store(&mut jar, "b=1", "http://example.com/"));
+        assert!(store(&mut jar, "a=2", "http://example.com/"));
+        assert_eq!(
+            cookies(&jar, "http://example.com/"),
+            Some("a=2; b=1".to_string())
+        );
+    }
+
+    #[test]
+    fn headers() {
+        let uri = "https://example.com/".parse().unwrap();
+        let mut response = HeaderMap::new();
+        response.append(SET_COOKIE, HeaderValue::from_static("a=1; Secure"));
+        response.append(SET_COOKIE, HeaderValue::from_static("bad cookie"));
+        response.append(SET_COOKIE, HeaderValue::from_static("b=2; HttpOnly"));
+
+        let mut jar = CookieJar::new();
+        jar.store_response(&response, &uri, now());
+
+        let mut request = HeaderMap::new();
+        jar.add_cookie_header(&mut request, &uri, now());
+        assert_eq!(request.get(COOKIE).unwrap(), "a=1; b=2");
+
+        let mut jar = CookieJar::new();
+        let set_cookie = SetCookie::new(vec![
+            "c=3".parse().unwrap(),
+            "d=4; Path=/d".parse().unwrap(),
+        ])
+        .unwrap();
+        jar.store_all(&set_cookie, &uri, now());
+        jar.add_cookie_header(&mut request, &uri, now());
+        assert_eq!(request.get(COOKIE).unwrap(), "c=3");
+    }
+}
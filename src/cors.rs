@@ -0,0 +1,444 @@
+//! Cross-origin resource sharing.
+//!
+//! The [CORS protocol] allows servers to opt in to sharing responses with scripts running on other
+//! origins. A `CorsPolicy` describes what a server is willing to share, and evaluates requests
+//! against that policy to produce the headers which should be added to the response.
+//!
+//! [CORS protocol]: https://fetch.spec.whatwg.org/#http-cors-protocol
+use http::header::{
+    HeaderMap, HeaderName, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+};
+use http::Method;
+
+use crate::{
+    AccessControlAllowCredentials, AccessControlAllowHeaders, AccessControlAllowMethods,
+    AccessControlAllowOrigin, AccessControlExposeHeaders, AccessControlMaxAge,
+    AccessControlRequestHeaders, AccessControlRequestMethod, Error, HeaderMapExt, Origin, Vary,
+};
+
+/// The origins a policy shares responses with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowedOrigins {
+    /// Responses are shared with any origin other than `null`.
+    Any,
+    /// Responses are shared with the listed origins.
+    List(Vec<Origin>),
+}
+
+impl Default for AllowedOrigins {
+    #[inline]
+    fn default() -> AllowedOrigins {
+        AllowedOrigins::List(vec![])
+    }
+}
+
+impl AllowedOrigins {
+    fn allows(&self, origin: &Origin) -> bool {
+        match *self {
+            AllowedOrigins::Any => !origin.is_null(),
+            AllowedOrigins::List(ref origins) => origins.contains(origin),
+        }
+    }
+}
+
+/// The request headers a policy permits in cross-origin requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowedHeaders {
+    /// Any request header is permitted.
+    Any,
+    /// The listed request headers are permitted.
+    List(Vec<HeaderName>),
+}
+
+impl Default for AllowedHeaders {
+    #[inline]
+    fn default() -> AllowedHeaders {
+        AllowedHeaders::List(vec![])
+    }
+}
+
+/// A server's CORS policy.
+///
+/// The default policy does not share responses with any origin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsPolicy {
+    /// The origins responses are shared with.
+    pub allowed_origins: AllowedOrigins,
+    /// The methods permitted in cross-origin requests, in addition to the CORS-safelisted methods
+    /// `GET`, `HEAD`, and `POST`.
+    pub allowed_methods: Vec<Method>,
+    /// The request headers permitted in cross-origin requests.
+    pub allowed_headers: AllowedHeaders,
+    /// The response headers scripts are allowed to read, in addition to the CORS-safelisted
+    /// response headers.
+    pub exposed_headers: Vec<HeaderName>,
+    /// The number of seconds the result of a preflight request can be cached.
+    pub max_age: Option<u64>,
+    /// Whether responses are shared with requests that include credentials.
+    pub allow_credentials: bool,
+}
+
+/// The kind of request a policy was evaluated against.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RequestKind {
+    /// The request has no `Origin` header, and is not subject to the CORS protocol.
+    NotCors,
+    /// A CORS-preflight request.
+    Preflight,
+    /// A CORS request other than a preflight.
+    Actual,
+}
+
+/// The result of evaluating a request against a `CorsPolicy`.
+#[derive(Debug, Clone)]
+pub struct CorsResponse {
+    kind: RequestKind,
+    allowed: bool,
+    headers: HeaderMap,
+}
+
+impl CorsResponse {
+    /// Returns the kind of the request.
+    #[inline]
+    pub fn kind(&self) -> RequestKind {
+        self.kind
+    }
+
+    /// Determines if the request is a CORS-preflight request.
+    ///
+    /// Servers should respond to preflight requests directly, typically with a 204 (No Content)
+    /// status, rather than passing them on to the resource's `OPTIONS` handler.
+    #[inline]
+    pub fn is_preflight(&self) -> bool {
+        self.kind == RequestKind::Preflight
+    }
+
+    /// Determines if the policy permits the request.
+    ///
+    /// Requests which are not subject to the CORS protocol are always permitted.
+    #[inline]
+    pub fn is_allowed(&self) -> bool {
+        self.allowed
+    }
+
+    /// Returns the headers to add to the response.
+    ///
+    /// These include a `Vary` header whenever the response depends on the request's `Origin`,
+    /// even if the request itself was not permitted.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Consumes the result, returning the headers to add to the response.
+    #[inline]
+    pub fn into_headers(self) -> HeaderMap {
+        self.headers
+    }
+}
+
+impl CorsPolicy {
+    /// Evaluates a request against the policy.
+    ///
+    /// `method` and `request` are the method and headers of the incoming request. A request is
+    /// treated as a preflight if it is an `OPTIONS` request with both `Origin` and
+    /// `Access-Control-Request-Method` headers.
+    ///
+    /// An error is returned if any of the CORS request headers are malformed.
+    pub fn evaluate(&self, method: &Method, request: &HeaderMap) -> Result<CorsResponse, Error> {
+        let origin = request.typed_get::<Origin>()?;
+        let request_method = request.typed_get::<AccessControlRequestMethod>()?;
+
+        let mut vary = vec![];
+        if self.varies_by_origin() {
+            vary.push(ORIGIN);
+        }
+
+        let kind = match (&origin, &request_method) {
+            (None, _) => RequestKind::NotCors,
+            (Some(_), Some(_)) if *method == Method::OPTIONS => RequestKind::Preflight,
+            (Some(_), _) => RequestKind::Actual,
+        };
+        if *method == Method::OPTIONS && kind != RequestKind::Actual {
+            vary.push(ACCESS_CONTROL_REQUEST_METHOD);
+            vary.push(ACCESS_CONTROL_REQUEST_HEADERS);
+        }
+
+        let mut response = CorsResponse {
+            kind,
+            allowed: kind == RequestKind::NotCors,
+            headers: HeaderMap::new(),
+        };
+        if !vary.is_empty() {
+            response.headers.typed_insert(&Vary::Headers(vary));
+        }
+
+        let origin = match origin {
+            Some(ref origin) if self.allowed_origins.allows(origin) => origin,
+            _ => return Ok(response),
+        };
+
+        if let Some(request_method) = request_method.filter(|_| kind == RequestKind::Preflight) {
+            let request_headers = request
+                .typed_get::<AccessControlRequestHeaders>()?
+                .map(|h| h.0)
+                .unwrap_or_default();
+            if !self.allows_method(&request_method.0) || !self.allows_headers(&request_headers) {
+                return Ok(response);
+            }
+
+            self.insert_origin(&mut response.headers, origin);
+            if !self.allowed_methods.is_empty() {
+                response
+                    .headers
+                    .typed_insert(&AccessControlAllowMethods(self.allowed_methods.clone()));
+            }
+            let allow_headers = match self.allowed_headers {
+                AllowedHeaders::Any => request_headers,
+                AllowedHeaders::List(ref headers) => headers.clone(),
+            };
+            if !allow_headers.is_empty() {
+                response
+                    .headers
+                    .typed_insert(&AccessControlAllowHeaders(allow_headers));
+            }
+            if let Some(max_age) = self.max_age {
+                response.headers.typed_insert(&AccessControlMaxAge(max_age));
+            }
+        } else {
+            self.insert_origin(&mut response.headers, origin);
+            if !self.exposed_headers.is_empty() {
+                response
+                    .headers
+                    .typed_insert(&AccessControlExposeHeaders(self.exposed_headers.clone()));
+            }
+        }
+
+        response.allowed = true;
+        Ok(response)
+    }
+
+    // The wildcard can't be used with credentials, so the request's origin is echoed instead.
+    fn varies_by_origin(&self) -> bool {
+        match self.allowed_origins {
+            AllowedOrigins::Any => self.allow_credentials,
+            AllowedOrigins::List(_) => true,
+        }
+    }
+
+    fn allows_method(&self, method: &Method) -> bool {
+        *method == Method::GET
+            || *method == Method::HEAD
+            || *method == Method::POST
+            || self.allowed_methods.contains(method)
+    }
+
+    fn allows_headers(&self, headers: &[HeaderName]) -> bool {
+        match self.allowed_headers {
+            AllowedHeaders::Any => true,
+            AllowedHeaders::List(ref allowed) => headers.iter().all(|h| allowed.contains(h)),
+        }
+    }
+
+    fn insert_origin(&self, headers: &mut HeaderMap, origin: &Origin) {
+        let allow_origin = if self.varies_by_origin() {
+            AccessControlAllowOrigin::Origin(origin.clone())
+        } else {
+            AccessControlAllowOrigin::Any
+        };
+        headers.typed_insert(&allow_origin);
+
+        if self.allow_credentials {
+            headers.typed_insert(&AccessControlAllowCredentials);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::{HeaderValue, CONTENT_TYPE};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &(name, value) in pairs {
+            headers.append(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn policy() -> CorsPolicy {
+        CorsPolicy {
+            allowed_origins: AllowedOrigins::List(vec!["https://example.com".parse().unwrap()]),
+            allowed_methods: vec![Method::GET, Method::PUT],
+            allowed_headers: AllowedHeaders::List(vec![CONTENT_TYPE]),
+            exposed_headers: vec![HeaderName::from_static("x-request-id")],
+            max_age: Some(600),
+            allow_credentials: false,
+        }
+    }
+
+    fn value<'a>(response: &'a CorsResponse, name: &str) -> Option<&'a str> {
+        response.headers().get(name).map(|v| v.to_str().unwrap())
+    }
+
+    #[test]
+    fn not_cors() {
+        let response = policy().evaluate(&Method::GET, &headers(&[])).unwrap();
+        assert_eq!(response.kind(), RequestKind::NotCors);
+        assert!(response.is_allowed());
+        assert_eq!(value(&response, "vary"), Some("origin"));
+        assert_eq!(response.headers().len(), 1);
+    }
+
+    #[test]
+    fn actual() {
+        let request = headers(&[("Origin", "https://example.com")]);
+        let response = policy().evaluate(&Method::GET, &request).unwrap();
+        assert_eq!(response.kind(), RequestKind::Actual);
+        assert!(response.is_allowed());
+        assert_eq!(
+            value(&response, "access-control-allow-origin"),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            value(&response, "access-control-expose-headers"),
+            Some("x-request-id")
+        );
+        assert_eq!(value(&response, "vary"), Some("origin"));
+        assert_eq!(value(&response, "access-control-allow-credentials"), None);
+    }
+
+    #[test]
+    fn disallowed_origin() {
+        for origin in &["https://evil.example", "null"] {
+            let request = headers(&[("Origin", origin)]);
+            let response = policy().evaluate(&Method::GET, &request).unwrap();
+            assert!(!response.is_allowed(), "{}", origin);
+            assert_eq!(value(&response, "access-control-allow-origin"), None);
+            assert_eq!(value(&response, "vary"), Some("origin"));
+        }
+    }
+
+    #[test]
+    fn preflight() {
+        let request = headers(&[
+            ("Origin", "https://example.com"),
+            ("Access-Control-Request-Method", "PUT"),
+            ("Access-Control-Request-Headers", "content-type"),
+        ]);
+        let response = policy().evaluate(&Method::OPTIONS, &request).unwrap();
+        assert!(response.is_preflight());
+        assert!(response.is_allowed());
+        assert_eq!(
+            value(&response, "access-control-allow-origin"),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            value(&response, "access-control-allow-methods"),
+            Some("GET, PUT")
+        );
+        assert_eq!(
+            value(&response, "access-control-allow-headers"),
+            Some("content-type")
+        );
+        assert_eq!(value(&response, "access-control-max-age"), Some("600"));
+        assert_eq!(value(&response, "access-control-expose-headers"), None);
+        assert_eq!(
+            value(&response, "vary"),
+            Some("origin, access-control-request-method, access-control-request-headers")
+        );
+    }
+
+    #[test]
+    fn preflight_disallowed() {
+        let cases = [
+            ("DELETE", "content-type"),
+            ("PUT", "x-custom"),
+            ("PUT", "content-type, x-custom"),
+        ];
+        for &(method, request_headers) in &cases {
+            let request = headers(&[
+                ("Origin", "https://example.com"),
+                ("Access-Control-Request-Method", method),
+                ("Access-Control-Request-Headers", request_headers),
+            ]);
+            let response = policy().evaluate(&Method::OPTIONS, &request).unwrap();
+            assert!(response.is_preflight());
+            assert!(!response.is_allowed(), "{} {}", method, request_headers);
+            assert_eq!(value(&response, "access-control-allow-origin"), None);
+        }
+    }
+
+    #[test]
+    fn preflight_safelisted_method() {
+        let request = headers(&[
+            ("Origin", "https://example.com"),
+            ("Access-Control-Request-Method", "POST"),
+        ]);
+        let response = policy().evaluate(&Method::OPTIONS, &request).unwrap();
+        assert!(response.is_allowed());
+    }
+
+    #[test]
+    fn options_without_request_method() {
+        let request = headers(&[("Origin", "https://example.com")]);
+        let response = policy().evaluate(&Method::OPTIONS, &request).unwrap();
+        assert_eq!(response.kind(), RequestKind::Actual);
+        assert_eq!(value(&response, "vary"), Some("origin"));
+    }
+
+    #[test]
+    fn wildcard() {
+        let policy = CorsPolicy {
+            allowed_origins: AllowedOrigins::Any,
+            allowed_headers: AllowedHeaders::Any,
+            ..CorsPolicy::default()
+        };
+
+        let request = headers(&[("Origin", "https://example.com")]);
+        let response = policy.evaluate(&Method::GET, &request).unwrap();
+        assert_eq!(value(&response, "access-control-allow-origin"), Some("*"));
+        assert_eq!(value(&response, "vary"), None);
+
+        let request = headers(&[
+            ("Origin", "https://example.com"),
+            ("Access-Control-Request-Method", "GET"),
+            ("Access-Control-Request-Headers", "x-a, x-b"),
+        ]);
+        let response = policy.evaluate(&Method::OPTIONS, &request).unwrap();
+        assert!(response.is_allowed());
+        assert_eq!(
+            value(&response, "access-control-allow-headers"),
+            Some("x-a, x-b")
+        );
+        assert_eq!(value(&response, "access-control-allow-methods"), None);
+    }
+
+    #[test]
+    fn credentials() {
+        let policy = CorsPolicy {
+            allowed_origins: AllowedOrigins::Any,
+            allow_credentials: true,
+            ..CorsPolicy::default()
+        };
+
+        let request = headers(&[("Origin", "https://example.com")]);
+        let response = policy.evaluate(&Method::GET, &request).unwrap();
+        assert_eq!(
+            value(&response, "access-control-allow-origin"),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            value(&response, "access-control-allow-credentials"),
+            Some("true")
+        );
+        assert_eq!(value(&response, "vary"), Some("origin"));
+    }
+
+    #[test]
+    fn malformed() {
+        let request = headers(&[("Origin", "example.com")]);
+        assert!(policy().evaluate(&Method::GET, &request).is_err());
+    }
+}
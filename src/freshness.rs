@@ -0,0 +1,474 @@
+//! Calculation of the freshness of cached responses.
+//!
+//! [RFC7234] defines how a cache determines whether a stored response can be used to satisfy a
+//! request without contacting the origin server. The `evaluate` function implements the
+//! freshness model of [section 4.2] of that RFC, including heuristic freshness, along with the
+//! request directives which constrain it and the stale response extensions of [RFC5861].
+//!
+//! [RFC7234]: https://tools.ietf.org/html/rfc7234
+//! [section 4.2]: https://tools.ietf.org/html/rfc7234#section-4.2
+//! [RFC5861]: https://tools.ietf.org/html/rfc5861
+use http::header::HeaderMap;
+use http::StatusCode;
+use std::time::{Duration, SystemTime};
+
+use crate::{Age, CacheControl, Date, Error, Expires, HeaderMapExt, LastModified};
+
+/// A response held in a cache.
+#[derive(Debug, Clone)]
+pub struct StoredResponse<'a> {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: &'a HeaderMap,
+    /// The time at which the cache sent the request which produced this response.
+    pub request_time: SystemTime,
+    /// The time at which the cache received this response.
+    pub response_time: SystemTime,
+}
+
+/// How a cache may use a stored response.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The response is fresh, and can be used without contacting the origin server.
+    Fresh,
+    /// The response is stale, but the client or origin server permits it to be used without
+    /// validation.
+    ///
+    /// If the response's `stale-while-revalidate` directive applies, the cache should validate it
+    /// in the background.
+    Stale,
+    /// The response must be validated with the origin server before it can be used.
+    MustRevalidate,
+}
+
+/// The result of a freshness calculation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Freshness {
+    lifetime: Duration,
+    current_age: Duration,
+    heuristic: bool,
+    status: Status,
+}
+
+impl Freshness {
+    /// Returns the freshness lifetime of the response.
+    #[inline]
+    pub fn lifetime(&self) -> Duration {
+        self.lifetime
+    }
+
+    /// Returns the current age of the response.
+    #[inline]
+    pub fn current_age(&self) -> Duration {
+        self.current_age
+    }
+
+    /// Determines if the freshness lifetime was calculated heuristically.
+    #[inline]
+    pub fn is_heuristic(&self) -> bool {
+        self.heuristic
+    }
+
+    /// Returns how the cache may use the response.
+    #[inline]
+    pub fn status(&self) -> Status {
+        self.status
+    }
+}
+
+/// Evaluates the freshness of a stored response.
+///
+/// `request` contains the headers of the request the cache is attempting to satisfy, and `now`
+/// is the current time. `shared` indicates if the cache is a shared cache, such as a proxy,
+/// rather than a private cache such as a browser's.
+///
+/// Invalid `Date`, `Age`, and `Last-Modified` headers are ignored, and an invalid `Expires`
+/// header is treated as already expired, as required by the RFC. An error is returned if either
+/// `Cache-Control` header is malformed.
+pub fn evaluate(
+    response: &StoredResponse,
+    request: &HeaderMap,
+    now: SystemTime,
+    shared: bool,
+) -> Result<Freshness, Error> {
+    let response_cc = response.headers.typed_get::<CacheControl>()?;
+    let request_cc = request.typed_get::<CacheControl>()?;
+
+    let date = response
+        .headers
+        .typed_get::<Date>()
+        .ok()
+        .and_then(|d| d)
+        .map(|d| SystemTime::from(d.0));
+    let date = date.unwrap_or(response.response_time);
+
+    let (lifetime, heuristic) = lifetime(response, response_cc.as_ref(), date, shared);
+    let current_age = current_age(response, date, now);
+
+    let status = status(
+        response_cc.as_ref(),
+        request_cc.as_ref(),
+        lifetime,
+        current_age,
+        shared,
+    );
+
+    Ok(Freshness {
+        lifetime,
+        current_age,
+        heuristic,
+        status,
+    })
+}
+
+// https://tools.ietf.org/html/rfc7234#section-4.2.1
+fn lifetime(
+    response: &StoredResponse,
+    cc: Option<&CacheControl>,
+    date: SystemTime,
+    shared: bool,
+) -> (Duration, bool) {
+    if let Some(cc) = cc {
+        if shared {
+            if let Some(s_maxage) = cc.s_maxage() {
+                return (Duration::from_secs(s_maxage), false);
+            }
+        }
+
+        if let Some(max_age) = cc.max_age() {
+            return (Duration::from_secs(max_age), false);
+        }
+    }
+
+    match response.headers.typed_get::<Expires>() {
+        Ok(Some(expires)) => {
+            let lifetime = match expires.date() {
+                Some(expires) => elapsed(date, SystemTime::from(expires.clone())),
+                None => Duration::from_secs(0),
+            };
+            return (lifetime, false);
+        }
+        Ok(None) => {}
+        // multiple Expires headers are invalid, and so represent a time in the past
+        Err(_) => return (Duration::from_secs(0), false),
+    }
+
+    let public = cc.is_some_and(|cc| cc.public());
+    if !public && !is_heuristically_cacheable(response.status) {
+        return (Duration::from_secs(0), false);
+    }
+
+    // https://tools.ietf.org/html/rfc7234#section-4.2.2
+    let last_modified = response
+        .headers
+        .typed_get::<LastModified>()
+        .ok()
+        .and_then(|l| l);
+    match last_modified {
+        Some(last_modified) => {
+            let lifetime = elapsed(SystemTime::from(last_modified.0), date) / 10;
+            (lifetime, true)
+        }
+        None => (Duration::from_secs(0), false),
+    }
+}
+
+// https://tools.ietf.org/html/rfc7231#section-6.1
+fn is_heuristically_cacheable(status: StatusCode) -> bool {
+    matches!(
+        status.as_u16(),
+        200 | 203 | 204 | 206 | 300 | 301 | 404 | 405 | 410 | 414 | 501
+    )
+}
+
+// https://tools.ietf.org/html/rfc7234#section-4.2.3
+fn current_age(response: &StoredResponse, date: SystemTime, now: SystemTime) -> Duration {
+    let age_value = response.headers.typed_get::<Age>().ok().and_then(|a| a);
+    let age_value = Duration::from_secs(age_value.map_or(0, |a| a.0));
+
+    let apparent_age = elapsed(date, response.response_time);
+    let response_delay = elapsed(response.request_time, response.response_time);
+    let corrected_age_value = age_value + response_delay;
+    let corrected_initial_age = Duration::max(apparent_age, corrected_age_value);
+    let resident_time = elapsed(response.response_time, now);
+
+    corrected_initial_age + resident_time
+}
+
+fn status(
+    response_cc: Option<&CacheControl>,
+    request_cc: Option<&CacheControl>,
+    lifetime: Duration,
+    current_age: Duration,
+    shared: bool,
+) -> Status {
+    if response_cc.is_some_and(|cc| cc.no_cache() == Some(vec![]))
+        || request_cc.is_some_and(|cc| cc.no_cache().is_some())
+    {
+        return Status::MustRevalidate;
+    }
+
+    if let Some(max_age) = request_cc.and_then(|cc| cc.max_age()) {
+        if current_age > Duration::from_secs(max_age) {
+            return Status::MustRevalidate;
+        }
+    }
+
+    if lifetime > current_age {
+        let remaining = lifetime - current_age;
+        return match request_cc.and_then(|cc| cc.min_fresh()) {
+            Some(min_fresh) if remaining < Duration::from_secs(min_fresh) => Status::MustRevalidate,
+            _ => Status::Fresh,
+        };
+    }
+
+    // https://tools.ietf.org/html/rfc7234#section-4.2.4
+    if let Some(cc) = response_cc {
+        if cc.must_revalidate() || (shared && (cc.proxy_revalidate() || cc.s_maxage().is_some())) {
+            return Status::MustRevalidate;
+        }
+    }
+
+    let staleness = current_age - lifetime;
+
+    match request_cc.and_then(|cc| cc.max_stale()) {
+        Some(None) => return Status::Stale,
+        Some(Some(max_stale)) if staleness <= Duration::from_secs(max_stale) => {
+            return Status::Stale
+        }
+        _ => {}
+    }
+
+    match response_cc.and_then(|cc| cc.stale_while_revalidate()) {
+        Some(window) if staleness <= Duration::from_secs(window) => Status::Stale,
+        _ => Status::MustRevalidate,
+    }
+}
+
+// Returns the time elapsed from `earlier` to `later`, or zero if `later` precedes `earlier`.
+fn elapsed(earlier: SystemTime, later: SystemTime) -> Duration {
+    later
+        .duration_since(earlier)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::HeaderValue;
+    use std::time::UNIX_EPOCH;
+
+    fn time(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn date(secs: u64) -> String {
+        crate::HttpDate::from(time(secs)).to_string()
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &(name, ref value) in pairs {
+            headers.append(name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn check(
+        response: &[(&'static str, String)],
+        request: &[(&'static str, String)],
+        now: u64,
+        shared: bool,
+    ) -> Freshness {
+        let headers = headers(response);
+        let response = StoredResponse {
+            status: StatusCode::OK,
+            headers: &headers,
+            request_time: time(1000),
+            response_time: time(1000),
+        };
+        evaluate(&response, &self::headers(request), time(now), shared).unwrap()
+    }
+
+    #[test]
+    fn max_age() {
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60".to_string()),
+        ];
+
+        let freshness = check(&response, &[], 1030, false);
+        assert_eq!(freshness.lifetime(), Duration::from_secs(60));
+        assert_eq!(freshness.current_age(), Duration::from_secs(30));
+        assert!(!freshness.is_heuristic());
+        assert_eq!(freshness.status(), Status::Fresh);
+
+        let freshness = check(&response, &[], 1060, false);
+        assert_eq!(freshness.status(), Status::MustRevalidate);
+    }
+
+    #[test]
+    fn s_maxage() {
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60, s-maxage=10".to_string()),
+        ];
+
+        assert_eq!(check(&response, &[], 1030, false).status(), Status::Fresh);
+        let freshness = check(&response, &[], 1030, true);
+        assert_eq!(freshness.lifetime(), Duration::from_secs(10));
+        assert_eq!(freshness.status(), Status::MustRevalidate);
+    }
+
+    #[test]
+    fn expires() {
+        let response = [("Date", date(1000)), ("Expires", date(1100))];
+        let freshness = check(&response, &[], 1050, false);
+        assert_eq!(freshness.lifetime(), Duration::from_secs(100));
+        assert_eq!(freshness.status(), Status::Fresh);
+
+        let response = [("Date", date(1000)), ("Expires", "0".to_string())];
+        let freshness = check(&response, &[], 1000, false);
+        assert_eq!(freshness.lifetime(), Duration::from_secs(0));
+        assert_eq!(freshness.status(), Status::MustRevalidate);
+    }
+
+    #[test]
+    fn heuristic() {
+        let response = [("Date", date(1000)), ("Last-Modified", date(0))];
+        let freshness = check(&response, &[], 1050, false);
+        assert_eq!(freshness.lifetime(), Duration::from_secs(100));
+        assert!(freshness.is_heuristic());
+        assert_eq!(freshness.status(), Status::Fresh);
+
+        let headers = headers(&response);
+        let response = StoredResponse {
+            status: StatusCode::FOUND,
+            headers: &headers,
+            request_time: time(1000),
+            response_time: time(1000),
+        };
+        let freshness = evaluate(&response, &HeaderMap::new(), time(1050), false).unwrap();
+        assert_eq!(freshness.lifetime(), Duration::from_secs(0));
+        assert!(!freshness.is_heuristic());
+    }
+
+    #[test]
+    fn current_age() {
+        let headers = headers(&[("Date", date(990)), ("Age", "20".to_string())]);
+        let response = StoredResponse {
+            status: StatusCode::OK,
+            headers: &headers,
+            request_time: time(995),
+            response_time: time(1000),
+        };
+        let freshness = evaluate(&response, &HeaderMap::new(), time(1010), false).unwrap();
+        assert_eq!(freshness.current_age(), Duration::from_secs(35));
+    }
+
+    #[test]
+    fn no_cache() {
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60, no-cache".to_string()),
+        ];
+        assert_eq!(
+            check(&response, &[], 1000, false).status(),
+            Status::MustRevalidate
+        );
+
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60".to_string()),
+        ];
+        let request = [("Cache-Control", "no-cache".to_string())];
+        assert_eq!(
+            check(&response, &request, 1000, false).status(),
+            Status::MustRevalidate
+        );
+    }
+
+    #[test]
+    fn request_directives() {
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60".to_string()),
+        ];
+
+        let request = [("Cache-Control", "max-age=10".to_string())];
+        assert_eq!(
+            check(&response, &request, 1020, false).status(),
+            Status::MustRevalidate
+        );
+
+        let request = [("Cache-Control", "min-fresh=30".to_string())];
+        assert_eq!(
+            check(&response, &request, 1020, false).status(),
+            Status::Fresh
+        );
+        assert_eq!(
+            check(&response, &request, 1040, false).status(),
+            Status::MustRevalidate
+        );
+
+        let request = [("Cache-Control", "max-stale=10".to_string())];
+        assert_eq!(
+            check(&response, &request, 1070, false).status(),
+            Status::Stale
+        );
+        assert_eq!(
+            check(&response, &request, 1071, false).status(),
+            Status::MustRevalidate
+        );
+
+        let request = [("Cache-Control", "max-stale".to_string())];
+        assert_eq!(
+            check(&response, &request, 100_000, false).status(),
+            Status::Stale
+        );
+    }
+
+    #[test]
+    fn stale_while_revalidate() {
+        let response = [
+            ("Date", date(1000)),
+            (
+                "Cache-Control",
+                "max-age=60, stale-while-revalidate=30".to_string(),
+            ),
+        ];
+        assert_eq!(check(&response, &[], 1080, false).status(), Status::Stale);
+        assert_eq!(
+            check(&response, &[], 1100, false).status(),
+            Status::MustRevalidate
+        );
+    }
+
+    #[test]
+    fn must_revalidate() {
+        let request = [("Cache-Control", "max-stale".to_string())];
+
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60, must-revalidate".to_string()),
+        ];
+        assert_eq!(
+            check(&response, &request, 1080, false).status(),
+            Status::MustRevalidate
+        );
+
+        let response = [
+            ("Date", date(1000)),
+            ("Cache-Control", "max-age=60, proxy-revalidate".to_string()),
+        ];
+        assert_eq!(
+            check(&response, &request, 1080, false).status(),
+            Status::Stale
+        );
+        assert_eq!(
+            check(&response, &request, 1080, true).status(),
+            Status::MustRevalidate
+        );
+    }
+}
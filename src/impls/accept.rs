@@ -1,7 +1,8 @@
 use http::header::ACCEPT;
 use mime::Mime;
 
-use super::QualityItem;
+use super::quality;
+use super::{Quality, QualityItem};
 
 header! {
     /// `Accept` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.2)
@@ -31,9 +32,88 @@ header! {
     (Accept, ACCEPT) => (QualityItem<Mime>)*
 }
 
+impl Accept {
+    /// Returns the quality with which a media type is accepted.
+    ///
+    /// As described in [RFC7231], the quality is taken from the most specific media range which
+    /// matches the media type, where `type/subtype` is more specific than `type/*`, which is more
+    /// specific than `*/*`, and ranges with more parameters are more specific than those with
+    /// fewer. A quality of 0 is returned if no range matches.
+    ///
+    /// [RFC7231]: https://tools.ietf.org/html/rfc7231#section-5.3.2
+    pub fn quality(&self, mime: &Mime) -> Quality {
+        let mut best: Option<(Specificity, Quality)> = None;
+        for item in &self.0 {
+            let specificity = match matches(&item.item, mime) {
+                Some(specificity) => specificity,
+                None => continue,
+            };
+
+            match best {
+                Some((best_specificity, _)) if best_specificity >= specificity => {}
+                _ => best = Some((specificity, item.quality)),
+            }
+        }
+
+        best.map_or(Quality::from_u16(0), |(_, quality)| quality)
+    }
+
+    /// Selects the most acceptable of a list of available media types.
+    ///
+    /// Media types with a quality of 0 are never selected. If multiple media types are equally
+    /// acceptable, the one appearing first in `available` is selected.
+    pub fn negotiate<'a>(&self, available: &'a [Mime]) -> Option<&'a Mime> {
+        quality::negotiate(available, |mime| self.quality(mime))
+    }
+}
+
+// (wildcard level, parameter count)
+type Specificity = (u8, usize);
+
+fn matches(range: &Mime, mime: &Mime) -> Option<Specificity> {
+    let level = if range.type_() == mime::STAR {
+        0
+    } else if !range
+        .type_()
+        .as_str()
+        .eq_ignore_ascii_case(mime.type_().as_str())
+    {
+        return None;
+    } else if range.subtype() == mime::STAR {
+        1
+    } else if !range
+        .subtype()
+        .as_str()
+        .eq_ignore_ascii_case(mime.subtype().as_str())
+    {
+        return None;
+    } else {
+        2
+    };
+
+    let mut count = 0;
+    for (name, value) in range.params() {
+        let matched = mime.params().any(|(n, v)| {
+            n.as_str().eq_ignore_ascii_case(name.as_str())
+                && if name == mime::CHARSET {
+                    v.as_str().eq_ignore_ascii_case(value.as_str())
+                } else {
+                    v.as_str() == value.as_str()
+                }
+        });
+        if !matched {
+            return None;
+        }
+        count += 1;
+    }
+
+    Some((level, count))
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{util, Quality, QualityItem};
+    use crate::{util, ErrorKind, HeaderMapExt, Quality, QualityItem};
+    use http::HeaderMap;
 
     use super::*;
 
@@ -60,4 +140,87 @@ mod test {
             &["text/plain; q=0.5, text/html, text/x-dvi; q=0.8, text/x-c"],
         );
     }
+
+    #[test]
+    fn invalid_media_type() {
+        let mut map = HeaderMap::new();
+        map.append(ACCEPT, "text/html".parse().unwrap());
+        map.append(ACCEPT, "text/plain, text".parse().unwrap());
+
+        let err = map.typed_get::<Accept>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidMediaType);
+        assert_eq!(err.index(), Some(1));
+        assert_eq!(err.offset(), Some(12));
+    }
+
+    fn mimes(s: &[&str]) -> Vec<Mime> {
+        s.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn rfc_precedence() {
+        let accept = Accept(vec![
+            QualityItem::new("text/*".parse().unwrap(), Quality::from_u16(300)),
+            QualityItem::new("text/html".parse().unwrap(), Quality::from_u16(700)),
+            QualityItem::new(
+                "text/html;level=1".parse().unwrap(),
+                Quality::from_u16(1000),
+            ),
+            QualityItem::new("text/html;level=2".parse().unwrap(), Quality::from_u16(400)),
+            QualityItem::new("*/*".parse().unwrap(), Quality::from_u16(500)),
+        ]);
+
+        let cases = [
+            ("text/html;level=1", 1000),
+            ("text/html", 700),
+            ("text/plain", 300),
+            ("image/jpeg", 500),
+            ("text/html;level=2", 400),
+            ("text/html;level=3", 700),
+        ];
+        for &(mime, quality) in &cases {
+            assert_eq!(
+                accept.quality(&mime.parse().unwrap()),
+                Quality::from_u16(quality),
+                "{}",
+                mime
+            );
+        }
+    }
+
+    #[test]
+    fn negotiate() {
+        let accept = Accept(vec![
+            QualityItem::new("text/plain".parse().unwrap(), Quality::from_u16(500)),
+            QualityItem::new("text/html".parse().unwrap(), Quality::from_u16(1000)),
+            QualityItem::new("application/*".parse().unwrap(), Quality::from_u16(1000)),
+            QualityItem::new("application/xml".parse().unwrap(), Quality::from_u16(0)),
+        ]);
+
+        let available = mimes(&["text/plain", "application/json", "text/html"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+
+        let available = mimes(&["application/xml", "text/plain"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+
+        let available = mimes(&["application/xml", "image/png"]);
+        assert_eq!(accept.negotiate(&available), None);
+    }
+
+    #[test]
+    fn negotiate_parameters() {
+        let accept = Accept(vec![
+            QualityItem::new(
+                "text/plain; charset=utf-8".parse().unwrap(),
+                Quality::from_u16(1000),
+            ),
+            QualityItem::new("text/plain".parse().unwrap(), Quality::from_u16(100)),
+        ]);
+
+        let available = mimes(&[
+            "text/plain; charset=iso-8859-1",
+            "text/plain; charset=UTF-8",
+        ]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+    }
 }
@@ -0,0 +1,116 @@
+use http::header::ACCEPT_CHARSET;
+
+use super::quality;
+use super::{Charset, Quality, QualityItem};
+
+header! {
+    /// `Accept-Charset` header, defined in
+    /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.3)
+    ///
+    /// The `Accept-Charset` header field can be sent by a user agent to
+    /// indicate what charsets are acceptable in textual response content.
+    /// This field allows user agents capable of understanding more
+    /// comprehensive or special-purpose charsets to signal that capability
+    /// to a server which is capable of representing information in those
+    /// charsets.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Accept-Charset = 1#( ( charset / "*" ) [ weight ] )
+    /// ```
+    ///
+    /// # Example values
+    /// * `iso-8859-5, unicode-1-1;q=0.8`
+    (AcceptCharset, ACCEPT_CHARSET) => (QualityItem<Charset>)+
+}
+
+impl AcceptCharset {
+    /// Returns the quality with which a charset is accepted.
+    ///
+    /// A charset listed explicitly takes its listed quality, and otherwise takes the quality of
+    /// the `*` wildcard if present. If neither is present, ISO-8859-1 is accepted with a quality
+    /// of 1 as described in [RFC2616], and all other charsets are not acceptable.
+    ///
+    /// [RFC2616]: https://tools.ietf.org/html/rfc2616#section-14.2
+    pub fn quality(&self, charset: &Charset) -> Quality {
+        if let Some(item) = self.0.iter().find(|item| item.item == *charset) {
+            return item.quality;
+        }
+
+        if let Some(item) = self.0.iter().find(|item| item.item == Charset::STAR) {
+            return item.quality;
+        }
+
+        if *charset == Charset::ISO_8859_1 {
+            Quality::from_u16(1000)
+        } else {
+            Quality::from_u16(0)
+        }
+    }
+
+    /// Determines if a charset is acceptable.
+    #[inline]
+    pub fn is_acceptable(&self, charset: &Charset) -> bool {
+        self.quality(charset) != Quality::from_u16(0)
+    }
+
+    /// Selects the most acceptable of a list of available charsets.
+    ///
+    /// Charsets with a quality of 0 are never selected. If multiple charsets are equally
+    /// acceptable, the one appearing first in `available` is selected.
+    pub fn negotiate<'a>(&self, available: &'a [Charset]) -> Option<&'a Charset> {
+        quality::negotiate(available, |charset| self.quality(charset))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    fn qitem(charset: &str, quality: u16) -> QualityItem<Charset> {
+        QualityItem::new(charset.parse().unwrap(), Quality::from_u16(quality))
+    }
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AcceptCharset::new(vec![qitem("iso-8859-5", 1000), qitem("unicode-1-1", 800)])
+                .unwrap(),
+            &["iso-8859-5, unicode-1-1; q=0.8"],
+        );
+    }
+
+    #[test]
+    fn aliases() {
+        util::test_decode(
+            &["UTF8, Latin1;q=0.5, ASCII;q=0.1"],
+            &AcceptCharset::new(vec![
+                QualityItem::new(Charset::UTF_8, Quality::from_u16(1000)),
+                QualityItem::new(Charset::ISO_8859_1, Quality::from_u16(500)),
+                QualityItem::new(Charset::US_ASCII, Quality::from_u16(100)),
+            ])
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn negotiate() {
+        let available = [Charset::US_ASCII, Charset::ISO_8859_1, Charset::UTF_8];
+
+        let accept = AcceptCharset::new(vec![qitem("utf-8", 800)]).unwrap();
+        assert_eq!(accept.negotiate(&available), Some(&Charset::ISO_8859_1));
+        assert!(!accept.is_acceptable(&Charset::US_ASCII));
+
+        let accept = AcceptCharset::new(vec![qitem("utf-8", 1000), qitem("*", 500)]).unwrap();
+        assert_eq!(accept.negotiate(&available), Some(&Charset::UTF_8));
+        assert_eq!(accept.quality(&Charset::ISO_8859_1), Quality::from_u16(500));
+
+        let accept = AcceptCharset::new(vec![qitem("utf-8", 1000), qitem("*", 0)]).unwrap();
+        assert_eq!(accept.negotiate(&available[..2]), None);
+
+        let accept = AcceptCharset::new(vec![qitem("latin1", 0), qitem("us-ascii", 500)]).unwrap();
+        assert_eq!(accept.negotiate(&available[1..]), None);
+    }
+}
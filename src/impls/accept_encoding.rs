@@ -1,6 +1,7 @@
 use http::header::ACCEPT_ENCODING;
 
-use super::{ContentCoding, QualityItem};
+use super::quality;
+use super::{ContentCoding, Quality, QualityItem};
 
 header! {
     /// `Accept-Encoding` header, defined in
@@ -27,3 +28,108 @@ header! {
     /// * `gzip;q=1.0, identity; q=0.5, *;q=0`
     (AcceptEncoding, ACCEPT_ENCODING) => (QualityItem<ContentCoding>)*
 }
+
+impl AcceptEncoding {
+    /// Returns the quality with which a content coding is accepted.
+    ///
+    /// As described in [RFC7231], a coding listed explicitly takes its listed quality, and
+    /// otherwise takes the quality of the `*` wildcard if present. The `identity` coding is
+    /// acceptable even if neither is present, but since it was not explicitly requested it is
+    /// assigned the lowest nonzero quality in that case.
+    ///
+    /// [RFC7231]: https://tools.ietf.org/html/rfc7231#section-5.3.4
+    pub fn quality(&self, coding: &ContentCoding) -> Quality {
+        if let Some(item) = self.0.iter().find(|item| item.item == *coding) {
+            return item.quality;
+        }
+
+        if let Some(item) = self.0.iter().find(|item| item.item == ContentCoding::STAR) {
+            return item.quality;
+        }
+
+        if *coding == ContentCoding::IDENTITY {
+            Quality::from_u16(1)
+        } else {
+            Quality::from_u16(0)
+        }
+    }
+
+    /// Determines if a content coding is acceptable.
+    #[inline]
+    pub fn is_acceptable(&self, coding: &ContentCoding) -> bool {
+        self.quality(coding) != Quality::from_u16(0)
+    }
+
+    /// Selects the most acceptable of a list of available content codings.
+    ///
+    /// Codings with a quality of 0 are never selected. If multiple codings are equally
+    /// acceptable, the one appearing first in `available` is selected. `available` should
+    /// include `ContentCoding::IDENTITY` if an unencoded representation can be served.
+    pub fn negotiate<'a>(&self, available: &'a [ContentCoding]) -> Option<&'a ContentCoding> {
+        quality::negotiate(available, |coding| self.quality(coding))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    fn qitem(coding: ContentCoding, quality: u16) -> QualityItem<ContentCoding> {
+        QualityItem::new(coding, Quality::from_u16(quality))
+    }
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AcceptEncoding(vec![
+                qitem(ContentCoding::GZIP, 1000),
+                qitem(ContentCoding::IDENTITY, 500),
+                qitem(ContentCoding::STAR, 0),
+            ]),
+            &["gzip, identity; q=0.5, *; q=0"],
+        );
+    }
+
+    #[test]
+    fn negotiate() {
+        let available = [
+            ContentCoding::BROTLI,
+            ContentCoding::GZIP,
+            ContentCoding::IDENTITY,
+        ];
+
+        let accept = AcceptEncoding(vec![
+            qitem(ContentCoding::GZIP, 1000),
+            qitem(ContentCoding::BROTLI, 500),
+        ]);
+        assert_eq!(accept.negotiate(&available), Some(&ContentCoding::GZIP));
+
+        let accept = AcceptEncoding(vec![qitem(ContentCoding::DEFLATE, 1000)]);
+        assert_eq!(accept.negotiate(&available), Some(&ContentCoding::IDENTITY));
+
+        let accept = AcceptEncoding(vec![]);
+        assert_eq!(accept.negotiate(&available), Some(&ContentCoding::IDENTITY));
+
+        let accept = AcceptEncoding(vec![qitem(ContentCoding::STAR, 1000)]);
+        assert_eq!(accept.negotiate(&available), Some(&ContentCoding::BROTLI));
+
+        let accept = AcceptEncoding(vec![
+            qitem(ContentCoding::GZIP, 1000),
+            qitem(ContentCoding::IDENTITY, 500),
+            qitem(ContentCoding::STAR, 0),
+        ]);
+        assert_eq!(accept.negotiate(&available), Some(&ContentCoding::GZIP));
+        assert!(!accept.is_acceptable(&ContentCoding::BROTLI));
+
+        let accept = AcceptEncoding(vec![
+            qitem(ContentCoding::BROTLI, 1000),
+            qitem(ContentCoding::STAR, 0),
+        ]);
+        assert!(!accept.is_acceptable(&ContentCoding::IDENTITY));
+        assert_eq!(accept.negotiate(&[ContentCoding::IDENTITY]), None);
+
+        let accept = AcceptEncoding(vec![qitem(ContentCoding::IDENTITY, 0)]);
+        assert_eq!(accept.negotiate(&[ContentCoding::IDENTITY]), None);
+    }
+}
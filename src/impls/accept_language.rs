@@ -0,0 +1,188 @@
+use http::header::ACCEPT_LANGUAGE;
+use std::cmp::Reverse;
+
+use super::{LanguageRange, LanguageTag, Quality, QualityItem};
+
+header! {
+    /// `Accept-Language` header, defined in
+    /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.5)
+    ///
+    /// The `Accept-Language` header field can be used by user agents to
+    /// indicate the set of natural languages that are preferred in the
+    /// response.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Accept-Language = 1#( language-range [ weight ] )
+    /// language-range  = <language-range, see [RFC4647], Section 2.1>
+    /// ```
+    ///
+    /// # Example values
+    /// * `da, en-gb;q=0.8, en;q=0.7`
+    /// * `en-us;q=1.0, en;q=0.5, fr`
+    (AcceptLanguage, ACCEPT_LANGUAGE) => (QualityItem<LanguageRange>)+
+}
+
+impl AcceptLanguage {
+    /// Returns the quality with which a language tag is accepted.
+    ///
+    /// The quality is taken from the longest language range which matches the tag, with the
+    /// wildcard range matching only tags not matched by any other range. A quality of 0 is
+    /// returned if no range matches.
+    pub fn quality(&self, tag: &LanguageTag) -> Quality {
+        let mut best: Option<(usize, Quality)> = None;
+        for item in &self.0 {
+            if !item.item.matches(tag) {
+                continue;
+            }
+
+            let specificity = if item.item.is_star() {
+                0
+            } else {
+                item.item.as_str().len()
+            };
+            match best {
+                Some((best_specificity, _)) if best_specificity >= specificity => {}
+                _ => best = Some((specificity, item.quality)),
+            }
+        }
+
+        best.map_or(Quality::from_u16(0), |(_, quality)| quality)
+    }
+
+    /// Returns the available language tags selected by the basic filtering scheme of [RFC4647].
+    ///
+    /// Tags are ordered by decreasing quality, with ties broken by their order in `available`.
+    /// Tags with a quality of 0 are excluded.
+    ///
+    /// [RFC4647]: https://tools.ietf.org/html/rfc4647#section-3.3.1
+    pub fn filter<'a>(&self, available: &'a [LanguageTag]) -> Vec<&'a LanguageTag> {
+        let mut tags = available
+            .iter()
+            .map(|tag| (tag, self.quality(tag)))
+            .filter(|&(_, quality)| quality != Quality::from_u16(0))
+            .collect::<Vec<_>>();
+        // sorting is stable, so ties retain their original order
+        tags.sort_by_key(|&(_, quality)| Reverse(quality));
+        tags.into_iter().map(|(tag, _)| tag).collect()
+    }
+
+    /// Selects the single best available language tag using the lookup scheme of [RFC4647].
+    ///
+    /// Language ranges are considered in order of decreasing quality. Each range is
+    /// progressively truncated until it equals one of the available tags. Ranges with a quality
+    /// of 0 and the wildcard range are ignored. `None` is returned if no tag is found, in which
+    /// case the caller should fall back to a default.
+    ///
+    /// [RFC4647]: https://tools.ietf.org/html/rfc4647#section-3.4
+    pub fn lookup<'a>(&self, available: &'a [LanguageTag]) -> Option<&'a LanguageTag> {
+        let mut ranges = self
+            .0
+            .iter()
+            .filter(|item| !item.item.is_star() && item.quality != Quality::from_u16(0))
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|item| Reverse(item.quality));
+
+        for item in ranges {
+            let mut range = item.item.as_str();
+            loop {
+                if let Some(tag) = available
+                    .iter()
+                    .find(|tag| tag.as_str().eq_ignore_ascii_case(range))
+                {
+                    return Some(tag);
+                }
+
+                range = match range.rfind('-') {
+                    Some(idx) => &range[..idx],
+                    None => break,
+                };
+                // singletons can't appear at the end of a tag
+                if range.len() >= 2 && range.as_bytes()[range.len() - 2] == b'-' {
+                    range = &range[..range.len() - 2];
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    fn tags(s: &[&str]) -> Vec<LanguageTag> {
+        s.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    fn qitem(range: &str, quality: u16) -> QualityItem<LanguageRange> {
+        QualityItem::new(range.parse().unwrap(), Quality::from_u16(quality))
+    }
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AcceptLanguage::new(vec![
+                qitem("da", 1000),
+                qitem("en-gb", 800),
+                qitem("en", 700),
+            ])
+            .unwrap(),
+            &["da, en-gb; q=0.8, en; q=0.7"],
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let accept = AcceptLanguage::new(vec![
+            qitem("de-de", 1000),
+            qitem("en", 500),
+            qitem("en-gb", 0),
+        ])
+        .unwrap();
+        let available = tags(&["en-US", "de", "de-DE-1996", "en-GB", "de-Deva", "en"]);
+
+        assert_eq!(
+            accept.filter(&available),
+            vec![&available[2], &available[0], &available[5]],
+        );
+
+        let accept = AcceptLanguage::new(vec![qitem("*", 500), qitem("de", 1000)]).unwrap();
+        assert_eq!(
+            accept.filter(&available),
+            vec![
+                &available[1],
+                &available[2],
+                &available[4],
+                &available[0],
+                &available[3],
+                &available[5],
+            ],
+        );
+    }
+
+    #[test]
+    fn lookup() {
+        let accept = AcceptLanguage::new(vec![
+            qitem("zh-Hant-CN-x-private1-private2", 1000),
+            qitem("fr", 500),
+        ])
+        .unwrap();
+
+        let available = tags(&["fr", "zh-Hant", "zh"]);
+        assert_eq!(accept.lookup(&available), Some(&available[1]));
+
+        let available = tags(&["fr", "zh-Hans"]);
+        assert_eq!(accept.lookup(&available), Some(&available[0]));
+
+        let available = tags(&["de", "zh-Hans"]);
+        assert_eq!(accept.lookup(&available), None);
+
+        let accept = AcceptLanguage::new(vec![qitem("de-CH-a-ext", 1000)]).unwrap();
+        let available = tags(&["de-CH"]);
+        assert_eq!(accept.lookup(&available), Some(&available[0]));
+    }
+}
@@ -0,0 +1,35 @@
+use http::header::ACCEPT_RANGES;
+
+use super::RangeUnit;
+
+header! {
+    /// `Accept-Ranges` header, defined in
+    /// [RFC7233](https://tools.ietf.org/html/rfc7233#section-2.3)
+    ///
+    /// The `Accept-Ranges` header field allows a server to indicate that it
+    /// supports range requests for the target resource.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Accept-Ranges     = acceptable-ranges
+    /// acceptable-ranges = 1#range-unit / "none"
+    /// ```
+    ///
+    /// # Example values
+    /// * `bytes`
+    /// * `none`
+    (AcceptRanges, ACCEPT_RANGES) => (RangeUnit)+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&AcceptRanges::from(RangeUnit::BYTES), &["bytes"]);
+        util::test_round_trip(&AcceptRanges::from(RangeUnit::NONE), &["none"]);
+    }
+}
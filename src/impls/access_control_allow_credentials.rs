@@ -0,0 +1,65 @@
+use http::header::{self, HeaderName, HeaderValue, ACCESS_CONTROL_ALLOW_CREDENTIALS};
+
+use crate::{Error, Header, ToValues};
+
+/// `Access-Control-Allow-Credentials` header, defined in the
+/// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-allow-credentials)
+///
+/// Indicates whether the response can be shared when request's
+/// credentials mode is "include".
+///
+/// The only valid value is `true`. A response which does not allow credentials omits the header.
+///
+/// # ABNF
+///
+/// ```text
+/// Access-Control-Allow-Credentials = %s"true" ; case-sensitive
+/// ```
+///
+/// # Example values
+///
+/// * `true`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessControlAllowCredentials;
+
+impl Header for AccessControlAllowCredentials {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &ACCESS_CONTROL_ALLOW_CREDENTIALS
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<AccessControlAllowCredentials>, Error> {
+        match values.next() {
+            Some(value) if value == "true" => Ok(Some(AccessControlAllowCredentials)),
+            Some(_) => Err(Error::invalid_value().with_index(0)),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        values.append(HeaderValue::from_static("true"));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{util, HeaderMapExt};
+    use http::HeaderMap;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&AccessControlAllowCredentials, &["true"]);
+
+        let mut map = HeaderMap::new();
+        map.insert(
+            ACCESS_CONTROL_ALLOW_CREDENTIALS,
+            HeaderValue::from_static("TRUE"),
+        );
+        assert!(map.typed_get::<AccessControlAllowCredentials>().is_err());
+    }
+}
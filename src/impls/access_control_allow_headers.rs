@@ -0,0 +1,38 @@
+use http::header::{HeaderName, ACCESS_CONTROL_ALLOW_HEADERS};
+
+header! {
+    /// `Access-Control-Allow-Headers` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-allow-headers)
+    ///
+    /// Indicates which headers are supported by the response's URL for the
+    /// purposes of the CORS protocol.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Access-Control-Allow-Headers = #field-name
+    /// ```
+    ///
+    /// # Example values
+    /// * `content-type, x-requested-with`
+    /// * `*`
+    (AccessControlAllowHeaders, ACCESS_CONTROL_ALLOW_HEADERS) => (HeaderName)*
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+    use http::header::CONTENT_TYPE;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AccessControlAllowHeaders(vec![
+                CONTENT_TYPE,
+                HeaderName::from_static("x-requested-with"),
+            ]),
+            &["content-type, x-requested-with"],
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use http::header::ACCESS_CONTROL_ALLOW_METHODS;
+use http::Method;
+
+header! {
+    /// `Access-Control-Allow-Methods` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-allow-methods)
+    ///
+    /// Indicates which methods are supported by the response's URL for the
+    /// purposes of the CORS protocol.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Access-Control-Allow-Methods = #method
+    /// ```
+    ///
+    /// # Example values
+    /// * `GET, POST, PUT`
+    /// * `*`
+    (AccessControlAllowMethods, ACCESS_CONTROL_ALLOW_METHODS) => (Method)*
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AccessControlAllowMethods(vec![Method::GET, Method::POST, Method::PUT]),
+            &["GET, POST, PUT"],
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use http::header::{self, HeaderName, HeaderValue, ACCESS_CONTROL_ALLOW_ORIGIN};
+
+use super::Origin;
+use crate::{util, Error, Header, ToValues};
+
+/// `Access-Control-Allow-Origin` header, defined in the
+/// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-allow-origin)
+///
+/// Indicates whether the response can be shared, via returning the
+/// literal value of the `Origin` request header (which can be `null`) or
+/// `*` in a response.
+///
+/// # ABNF
+///
+/// ```text
+/// Access-Control-Allow-Origin = origin-or-null / wildcard
+/// ```
+///
+/// # Example values
+///
+/// * `*`
+/// * `https://example.com`
+/// * `null`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessControlAllowOrigin {
+    /// The response can be shared with any origin, for requests without credentials.
+    Any,
+    /// The response can be shared with the specified origin.
+    Origin(Origin),
+}
+
+impl Header for AccessControlAllowOrigin {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &ACCESS_CONTROL_ALLOW_ORIGIN
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<AccessControlAllowOrigin>, Error> {
+        let value = match values.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        if value == "*" {
+            return Ok(Some(AccessControlAllowOrigin::Any));
+        }
+
+        let value = util::to_str(value).map_err(|e| e.with_index(0))?;
+        value
+            .parse()
+            .map(|origin| Some(AccessControlAllowOrigin::Origin(origin)))
+            .map_err(|e: Error| e.with_index(0))
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        match *self {
+            AccessControlAllowOrigin::Any => values.append(HeaderValue::from_static("*")),
+            AccessControlAllowOrigin::Origin(ref origin) => {
+                util::encode_single_value(origin, values)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&AccessControlAllowOrigin::Any, &["*"]);
+        util::test_round_trip(
+            &AccessControlAllowOrigin::Origin("https://example.com".parse().unwrap()),
+            &["https://example.com"],
+        );
+        util::test_round_trip(&AccessControlAllowOrigin::Origin(Origin::null()), &["null"]);
+    }
+}
@@ -0,0 +1,34 @@
+use http::header::{HeaderName, ACCESS_CONTROL_EXPOSE_HEADERS};
+
+header! {
+    /// `Access-Control-Expose-Headers` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-expose-headers)
+    ///
+    /// Indicates which headers can be exposed as part of the response by
+    /// listing their names.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Access-Control-Expose-Headers = #field-name
+    /// ```
+    ///
+    /// # Example values
+    /// * `etag, x-request-id`
+    (AccessControlExposeHeaders, ACCESS_CONTROL_EXPOSE_HEADERS) => (HeaderName)*
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+    use http::header::ETAG;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AccessControlExposeHeaders(vec![ETAG, HeaderName::from_static("x-request-id")]),
+            &["etag, x-request-id"],
+        );
+    }
+}
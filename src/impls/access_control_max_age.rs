@@ -0,0 +1,31 @@
+use http::header::ACCESS_CONTROL_MAX_AGE;
+
+header! {
+    /// `Access-Control-Max-Age` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-max-age)
+    ///
+    /// Indicates the number of seconds the information provided by the
+    /// `Access-Control-Allow-Methods` and `Access-Control-Allow-Headers`
+    /// headers can be cached.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Access-Control-Max-Age = delta-seconds
+    /// ```
+    ///
+    /// # Example values
+    /// * `600`
+    (AccessControlMaxAge, ACCESS_CONTROL_MAX_AGE) => [u64]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&AccessControlMaxAge(600), &["600"]);
+    }
+}
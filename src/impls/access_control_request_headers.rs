@@ -0,0 +1,37 @@
+use http::header::{HeaderName, ACCESS_CONTROL_REQUEST_HEADERS};
+
+header! {
+    /// `Access-Control-Request-Headers` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-request-headers)
+    ///
+    /// Indicates which headers a future CORS request to the same resource
+    /// might use.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Access-Control-Request-Headers = #field-name
+    /// ```
+    ///
+    /// # Example values
+    /// * `content-type, x-requested-with`
+    (AccessControlRequestHeaders, ACCESS_CONTROL_REQUEST_HEADERS) => (HeaderName)*
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+    use http::header::CONTENT_TYPE;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &AccessControlRequestHeaders(vec![
+                CONTENT_TYPE,
+                HeaderName::from_static("x-requested-with"),
+            ]),
+            &["content-type, x-requested-with"],
+        );
+    }
+}
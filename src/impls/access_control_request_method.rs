@@ -0,0 +1,31 @@
+use http::header::ACCESS_CONTROL_REQUEST_METHOD;
+use http::Method;
+
+header! {
+    /// `Access-Control-Request-Method` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#http-access-control-request-method)
+    ///
+    /// Indicates which method a future CORS request to the same resource
+    /// might use.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Access-Control-Request-Method = method
+    /// ```
+    ///
+    /// # Example values
+    /// * `PUT`
+    (AccessControlRequestMethod, ACCESS_CONTROL_REQUEST_METHOD) => [Method]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&AccessControlRequestMethod(Method::PUT), &["PUT"]);
+    }
+}
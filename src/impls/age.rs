@@ -0,0 +1,82 @@
+use http::header::{self, HeaderName, HeaderValue, AGE};
+use std::ops::{Deref, DerefMut};
+
+use crate::{util, Error, Header, ToValues};
+
+/// `Age` header, defined in [RFC7234](http://tools.ietf.org/html/rfc7234#section-5.1)
+///
+/// The `Age` header field conveys the sender's estimate of the amount
+/// of time since the response was generated or successfully validated at
+/// the origin server.  Age values are calculated as specified in
+/// Section 4.2.3.
+///
+/// Values too large to represent are clamped to 2^31 seconds.
+///
+/// # ABNF
+///
+/// ```text
+/// Age = delta-seconds
+/// ```
+///
+/// # Example values
+///
+/// * `60`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Age(pub u64);
+
+impl Deref for Age {
+    type Target = u64;
+
+    #[inline]
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+impl DerefMut for Age {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut u64 {
+        &mut self.0
+    }
+}
+
+impl Header for Age {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &AGE
+    }
+
+    #[inline]
+    fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<Age>, Error> {
+        let value = match values.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let value = util::to_str(value).map_err(|e| e.with_index(0))?;
+        let start = util::leading_ws(value);
+        util::parse_delta_seconds(value.trim())
+            .map(|age| Some(Age(age)))
+            .map_err(|e| e.offset_by(start).with_index(0))
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(&self.0, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&Age(60), &["60"]);
+    }
+
+    #[test]
+    fn overflow() {
+        util::test_decode(&["99999999999999999999"], &Age(2_147_483_648));
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind};
+
+/// An authentication parameter, as defined in [RFC7235].
+///
+/// Parameter names are case-insensitive. The value is stored unescaped, and is serialized either
+/// as a token or as a quoted-string.
+///
+/// [RFC7235]: https://tools.ietf.org/html/rfc7235#section-2.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthParam {
+    name: String,
+    value: String,
+    quoted: bool,
+}
+
+impl AuthParam {
+    /// Creates a new auth-param.
+    ///
+    /// The value will be serialized as a token if possible, and as a quoted-string otherwise.
+    #[inline]
+    pub fn new(name: &str, value: &str) -> Result<AuthParam, Error> {
+        let quoted = !util::is_token(value);
+        AuthParam::with_quoting(name, value, quoted)
+    }
+
+    /// Creates a new auth-param whose value is always serialized as a quoted-string.
+    ///
+    /// Some authentication schemes, such as Digest, require certain parameters to be quoted.
+    #[inline]
+    pub fn quoted(name: &str, value: &str) -> Result<AuthParam, Error> {
+        AuthParam::with_quoting(name, value, true)
+    }
+
+    fn with_quoting(name: &str, value: &str, quoted: bool) -> Result<AuthParam, Error> {
+        if !util::is_token(name) {
+            return Err(util::invalid_token(name));
+        }
+
+        if !util::is_quotable(value) {
+            return Err(Error::new(ErrorKind::InvalidQuotedString));
+        }
+
+        Ok(AuthParam {
+            name: name.to_string(),
+            value: value.to_string(),
+            quoted,
+        })
+    }
+
+    /// Returns the name of the parameter.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the unescaped value of the parameter.
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns true if the value is serialized as a quoted-string.
+    #[inline]
+    pub fn is_quoted(&self) -> bool {
+        self.quoted
+    }
+
+    /// Parses a single auth-param at the cursor's position.
+    pub(crate) fn parse(c: &mut Cursor) -> Result<AuthParam, Error> {
+        let name = c.token()?;
+        c.ows();
+        c.expect(b'=')?;
+        c.ows();
+
+        let (value, quoted) = if c.peek() == Some(b'"') {
+            (c.quoted_string()?, true)
+        } else {
+            (c.token()?.to_string(), false)
+        };
+
+        Ok(AuthParam {
+            name: name.to_string(),
+            value,
+            quoted,
+        })
+    }
+
+    /// Determines if an auth-param, rather than the start of some other construct, is at the
+    /// cursor's position.
+    pub(crate) fn is_next(c: &Cursor) -> bool {
+        let mut c = Cursor::new(c.rest());
+        if c.token().is_err() {
+            return false;
+        }
+        c.ows();
+        if !c.eat(b'=') {
+            return false;
+        }
+        // a token68 value can end with `=`, so make sure a value follows
+        c.ows();
+        match c.peek() {
+            Some(b'"') => true,
+            Some(b) => b != b'=' && util::is_tchar(b),
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for AuthParam {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.name)?;
+        fmt.write_str("=")?;
+        if self.quoted {
+            util::write_quoted_string(fmt, &self.value)
+        } else {
+            fmt.write_str(&self.value)
+        }
+    }
+}
+
+/// Parses a comma-separated list of auth-params which makes up the remainder of the cursor.
+pub(crate) fn parse_list(c: &mut Cursor) -> Result<Vec<AuthParam>, Error> {
+    let mut params = vec![];
+
+    loop {
+        c.skip_empty_elements();
+        if c.is_empty() {
+            break;
+        }
+
+        let start = c.pos();
+        let param = AuthParam::parse(c)?;
+        if find(&params, param.name()).is_some() {
+            return Err(Error::new(ErrorKind::DuplicateParameter).with_offset(start));
+        }
+        params.push(param);
+
+        c.ows();
+        if !c.is_empty() {
+            c.expect(b',')?;
+        }
+    }
+
+    Ok(params)
+}
+
+/// Ensures that no parameter name appears more than once.
+pub(crate) fn check_duplicates(params: &[AuthParam]) -> Result<(), Error> {
+    for (i, param) in params.iter().enumerate() {
+        if find(&params[..i], param.name()).is_some() {
+            return Err(Error::new(ErrorKind::DuplicateParameter));
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks up a parameter by case-insensitive name.
+pub(crate) fn find<'a>(params: &'a [AuthParam], name: &str) -> Option<&'a AuthParam> {
+    params.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// Writes a comma-separated list of auth-params.
+pub(crate) fn write_list(fmt: &mut fmt::Formatter, params: &[AuthParam]) -> fmt::Result {
+    let mut it = params.iter();
+    if let Some(param) = it.next() {
+        fmt::Display::fmt(param, fmt)?;
+        for param in it {
+            write!(fmt, ", {}", param)?;
+        }
+    }
+    Ok(())
+}
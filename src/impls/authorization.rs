@@ -26,7 +26,9 @@ header! {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{util, Token68};
+    use crate::{util, AuthParam, AuthScheme, ErrorKind, HeaderMapExt, Token68};
+    use http::HeaderMap;
+    use std::error::Error;
 
     #[test]
     fn rfc1() {
@@ -45,4 +47,81 @@ mod test {
             &["Bearer fpKL54jvWmEGVoRdCNjG"],
         );
     }
+
+    #[test]
+    fn invalid_token68() {
+        let mut map = HeaderMap::new();
+        map.insert(AUTHORIZATION, "Bearer  a@b".parse().unwrap());
+
+        let err = map.typed_get::<Authorization>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidToken68);
+        assert_eq!(err.header_name(), Some(&AUTHORIZATION));
+        assert_eq!(err.index(), Some(0));
+        assert_eq!(err.offset(), Some(8));
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn auth_params() {
+        let credentials = Credentials::from_auth_params(
+            AuthScheme::new("Digest").unwrap(),
+            vec![
+                AuthParam::quoted("username", "Mufasa").unwrap(),
+                AuthParam::quoted("realm", "http-auth@example.org").unwrap(),
+                AuthParam::new("qop", "auth").unwrap(),
+                AuthParam::new("nc", "00000001").unwrap(),
+                AuthParam::new("opaque", "a \"b\" c").unwrap(),
+            ],
+        )
+        .unwrap();
+        util::test_round_trip(
+            &Authorization(credentials),
+            &[
+                r#"digest username="Mufasa", realm="http-auth@example.org", qop=auth, nc=00000001, opaque="a \"b\" c""#,
+            ],
+        );
+    }
+
+    #[test]
+    fn auth_params_decode() {
+        let mut map = HeaderMap::new();
+        map.insert(
+            AUTHORIZATION,
+            r#"Digest  USERNAME = "Mufasa",, realm=x ,"#.parse().unwrap(),
+        );
+
+        let header = map.typed_get::<Authorization>().unwrap().unwrap();
+        assert_eq!(header.auth_param("username"), Some("Mufasa"));
+        assert_eq!(header.auth_param("Realm"), Some("x"));
+        assert_eq!(header.auth_param("nonce"), None);
+        assert_eq!(header.token68(), None);
+        assert_eq!(header.auth_params().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn duplicate_auth_params() {
+        let mut map = HeaderMap::new();
+        map.insert(AUTHORIZATION, "Digest realm=a, Realm=b".parse().unwrap());
+
+        let err = map.typed_get::<Authorization>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DuplicateParameter);
+        assert_eq!(err.offset(), Some(16));
+
+        assert!(Credentials::from_auth_params(
+            AuthScheme::BASIC,
+            vec![
+                AuthParam::new("a", "b").unwrap(),
+                AuthParam::new("A", "c").unwrap(),
+            ],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn token68_with_padding() {
+        util::test_round_trip(
+            &Authorization(Credentials::bearer(Token68::new("abc==").unwrap())),
+            &["Bearer abc=="],
+        );
+    }
 }
@@ -0,0 +1,654 @@
+use http::header::{self, HeaderName, HeaderValue, CACHE_CONTROL};
+use std::fmt;
+use std::ops::Deref;
+
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind, Header, ToValues};
+
+/// A cache directive, as defined in [RFC7234].
+///
+/// Directive names are case-insensitive. Arguments to the standard directives are accepted in
+/// both token and quoted-string form, and are serialized in the form recommended by the RFC.
+///
+/// [RFC7234]: https://tools.ietf.org/html/rfc7234#section-5.2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+    /// `max-age`, in seconds.
+    MaxAge(u64),
+    /// `max-stale`, with an optional limit in seconds.
+    MaxStale(Option<u64>),
+    /// `min-fresh`, in seconds.
+    MinFresh(u64),
+    /// `no-cache`, qualified by a possibly empty list of field names.
+    NoCache(Vec<HeaderName>),
+    /// `no-store`.
+    NoStore,
+    /// `no-transform`.
+    NoTransform,
+    /// `only-if-cached`.
+    OnlyIfCached,
+    /// `must-revalidate`.
+    MustRevalidate,
+    /// `public`.
+    Public,
+    /// `private`, qualified by a possibly empty list of field names.
+    Private(Vec<HeaderName>),
+    /// `proxy-revalidate`.
+    ProxyRevalidate,
+    /// `s-maxage`, in seconds.
+    SMaxAge(u64),
+    /// `immutable`, defined in [RFC8246](https://tools.ietf.org/html/rfc8246).
+    Immutable,
+    /// `stale-while-revalidate`, in seconds, defined in
+    /// [RFC5861](https://tools.ietf.org/html/rfc5861#section-3).
+    StaleWhileRevalidate(u64),
+    /// `stale-if-error`, in seconds, defined in
+    /// [RFC5861](https://tools.ietf.org/html/rfc5861#section-4).
+    StaleIfError(u64),
+    /// An unrecognized directive.
+    Extension(CacheExtension),
+}
+
+impl CacheDirective {
+    fn parse(c: &mut Cursor) -> Result<CacheDirective, Error> {
+        let start = c.pos();
+        let name = c.token()?;
+        let arg = if c.eat(b'=') {
+            if c.peek() == Some(b'"') {
+                Some((c.quoted_string()?, true))
+            } else {
+                Some((c.token()?.to_string(), false))
+            }
+        } else {
+            None
+        };
+
+        let directive = match &*name.to_ascii_lowercase() {
+            "max-age" => delta_seconds(&arg).map(CacheDirective::MaxAge),
+            "max-stale" => match arg {
+                Some(_) => delta_seconds(&arg).map(|s| CacheDirective::MaxStale(Some(s))),
+                None => Ok(CacheDirective::MaxStale(None)),
+            },
+            "min-fresh" => delta_seconds(&arg).map(CacheDirective::MinFresh),
+            "no-cache" => field_names(&arg).map(CacheDirective::NoCache),
+            "no-store" => no_argument(&arg, CacheDirective::NoStore),
+            "no-transform" => no_argument(&arg, CacheDirective::NoTransform),
+            "only-if-cached" => no_argument(&arg, CacheDirective::OnlyIfCached),
+            "must-revalidate" => no_argument(&arg, CacheDirective::MustRevalidate),
+            "public" => no_argument(&arg, CacheDirective::Public),
+            "private" => field_names(&arg).map(CacheDirective::Private),
+            "proxy-revalidate" => no_argument(&arg, CacheDirective::ProxyRevalidate),
+            "s-maxage" => delta_seconds(&arg).map(CacheDirective::SMaxAge),
+            "immutable" => no_argument(&arg, CacheDirective::Immutable),
+            "stale-while-revalidate" => {
+                delta_seconds(&arg).map(CacheDirective::StaleWhileRevalidate)
+            }
+            "stale-if-error" => delta_seconds(&arg).map(CacheDirective::StaleIfError),
+            _ => Ok(CacheDirective::Extension(CacheExtension {
+                name: name.to_string(),
+                value: arg,
+            })),
+        };
+
+        directive.map_err(|e| e.with_offset(start))
+    }
+}
+
+fn delta_seconds(arg: &Option<(String, bool)>) -> Result<u64, Error> {
+    let arg = match *arg {
+        Some((ref arg, _)) => arg,
+        None => return Err(Error::new(ErrorKind::InvalidInteger)),
+    };
+
+    util::parse_delta_seconds(arg)
+}
+
+fn field_names(arg: &Option<(String, bool)>) -> Result<Vec<HeaderName>, Error> {
+    let arg = match *arg {
+        Some((ref arg, _)) => arg,
+        None => return Ok(vec![]),
+    };
+
+    let mut names = vec![];
+    let mut c = Cursor::new(arg);
+    loop {
+        c.skip_empty_elements();
+        if c.is_empty() {
+            break;
+        }
+
+        let name = c.token()?;
+        names.push(HeaderName::from_bytes(name.as_bytes()).map_err(util::convert_error)?);
+
+        c.ows();
+        if !c.is_empty() && c.peek() != Some(b',') {
+            return Err(c.error(ErrorKind::InvalidToken));
+        }
+    }
+
+    Ok(names)
+}
+
+fn no_argument(
+    arg: &Option<(String, bool)>,
+    directive: CacheDirective,
+) -> Result<CacheDirective, Error> {
+    match *arg {
+        Some(_) => Err(Error::invalid_value()),
+        None => Ok(directive),
+    }
+}
+
+fn write_field_names(fmt: &mut fmt::Formatter, name: &str, fields: &[HeaderName]) -> fmt::Result {
+    fmt.write_str(name)?;
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    fmt.write_str("=\"")?;
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            fmt.write_str(", ")?;
+        }
+        fmt.write_str(field.as_str())?;
+    }
+    fmt.write_str("\"")
+}
+
+impl fmt::Display for CacheDirective {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CacheDirective::MaxAge(seconds) => write!(fmt, "max-age={}", seconds),
+            CacheDirective::MaxStale(Some(seconds)) => write!(fmt, "max-stale={}", seconds),
+            CacheDirective::MaxStale(None) => fmt.write_str("max-stale"),
+            CacheDirective::MinFresh(seconds) => write!(fmt, "min-fresh={}", seconds),
+            CacheDirective::NoCache(ref fields) => write_field_names(fmt, "no-cache", fields),
+            CacheDirective::NoStore => fmt.write_str("no-store"),
+            CacheDirective::NoTransform => fmt.write_str("no-transform"),
+            CacheDirective::OnlyIfCached => fmt.write_str("only-if-cached"),
+            CacheDirective::MustRevalidate => fmt.write_str("must-revalidate"),
+            CacheDirective::Public => fmt.write_str("public"),
+            CacheDirective::Private(ref fields) => write_field_names(fmt, "private", fields),
+            CacheDirective::ProxyRevalidate => fmt.write_str("proxy-revalidate"),
+            CacheDirective::SMaxAge(seconds) => write!(fmt, "s-maxage={}", seconds),
+            CacheDirective::Immutable => fmt.write_str("immutable"),
+            CacheDirective::StaleWhileRevalidate(seconds) => {
+                write!(fmt, "stale-while-revalidate={}", seconds)
+            }
+            CacheDirective::StaleIfError(seconds) => write!(fmt, "stale-if-error={}", seconds),
+            CacheDirective::Extension(ref extension) => fmt::Display::fmt(extension, fmt),
+        }
+    }
+}
+
+/// An unrecognized cache directive.
+///
+/// The directive's name and argument are preserved as received, including whether the argument
+/// was a token or a quoted-string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheExtension {
+    name: String,
+    value: Option<(String, bool)>,
+}
+
+impl CacheExtension {
+    /// Creates a new cache extension directive.
+    ///
+    /// A value will be serialized as a token if possible, and as a quoted-string otherwise.
+    pub fn new(name: &str, value: Option<&str>) -> Result<CacheExtension, Error> {
+        if !util::is_token(name) {
+            return Err(util::invalid_token(name));
+        }
+
+        let value = match value {
+            Some(value) => {
+                if !util::is_quotable(value) {
+                    return Err(Error::new(ErrorKind::InvalidQuotedString));
+                }
+                Some((value.to_string(), !util::is_token(value)))
+            }
+            None => None,
+        };
+
+        Ok(CacheExtension {
+            name: name.to_string(),
+            value,
+        })
+    }
+
+    /// Returns the name of the directive.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the unescaped argument of the directive, if present.
+    #[inline]
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_ref().map(|v| &*v.0)
+    }
+
+    /// Returns true if the argument is serialized as a quoted-string.
+    #[inline]
+    pub fn is_quoted(&self) -> bool {
+        self.value.as_ref().is_some_and(|v| v.1)
+    }
+}
+
+impl fmt::Display for CacheExtension {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.name)?;
+        match self.value {
+            Some((ref value, true)) => {
+                fmt.write_str("=")?;
+                util::write_quoted_string(fmt, value)
+            }
+            Some((ref value, false)) => write!(fmt, "={}", value),
+            None => Ok(()),
+        }
+    }
+}
+
+/// `Cache-Control` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.2)
+///
+/// The `Cache-Control` header field is used to specify directives for
+/// caches along the request/response chain.  Such cache directives are
+/// unidirectional in that the presence of a directive in a request does
+/// not imply that the same directive is to be given in the response.
+///
+/// The accessor methods resolve duplicated directives deterministically. Numeric directives
+/// take the smallest value present, and the field names of qualified `no-cache` and `private`
+/// directives are merged, with an unqualified directive applying to the entire message.
+///
+/// # ABNF
+///
+/// ```text
+/// Cache-Control   = 1#cache-directive
+///
+/// cache-directive = token [ "=" ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+///
+/// * `no-cache`
+/// * `private, community="UCI"`
+/// * `max-age=30`
+/// * `max-age=31536000, immutable`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheControl(Vec<CacheDirective>);
+
+impl CacheControl {
+    /// Creates a new `Cache-Control` header.
+    ///
+    /// An error is returned if no directives are provided.
+    #[inline]
+    pub fn new(directives: Vec<CacheDirective>) -> Result<CacheControl, Error> {
+        if directives.is_empty() {
+            Err(Error::too_few_values())
+        } else {
+            Ok(CacheControl(directives))
+        }
+    }
+
+    fn min_seconds<F>(&self, f: F) -> Option<u64>
+    where
+        F: Fn(&CacheDirective) -> Option<u64>,
+    {
+        self.0.iter().filter_map(f).min()
+    }
+
+    fn has(&self, directive: &CacheDirective) -> bool {
+        self.0.contains(directive)
+    }
+
+    fn field_names<F>(&self, f: F) -> Option<Vec<&HeaderName>>
+    where
+        F: Fn(&CacheDirective) -> Option<&[HeaderName]>,
+    {
+        let mut names: Option<Vec<&HeaderName>> = None;
+        for fields in self.0.iter().filter_map(f) {
+            if fields.is_empty() {
+                return Some(vec![]);
+            }
+
+            let names = names.get_or_insert_with(Vec::new);
+            for field in fields {
+                if !names.contains(&field) {
+                    names.push(field);
+                }
+            }
+        }
+        names
+    }
+
+    /// Returns the value of the `max-age` directive, in seconds.
+    pub fn max_age(&self) -> Option<u64> {
+        self.min_seconds(|d| match *d {
+            CacheDirective::MaxAge(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Returns the value of the `max-stale` directive.
+    ///
+    /// `Some(None)` indicates that the client will accept a stale response of any age.
+    pub fn max_stale(&self) -> Option<Option<u64>> {
+        let mut max_stale = None;
+        for directive in &self.0 {
+            if let CacheDirective::MaxStale(s) = *directive {
+                max_stale = match (max_stale, s) {
+                    (Some(Some(a)), Some(b)) => Some(Some(u64::min(a, b))),
+                    (Some(Some(a)), None) => Some(Some(a)),
+                    (_, s) => Some(s),
+                };
+            }
+        }
+        max_stale
+    }
+
+    /// Returns the value of the `min-fresh` directive, in seconds.
+    pub fn min_fresh(&self) -> Option<u64> {
+        self.min_seconds(|d| match *d {
+            CacheDirective::MinFresh(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Returns the field names of the `no-cache` directive.
+    ///
+    /// `None` is returned if the directive is absent, and an empty list is returned if it applies
+    /// to the entire response.
+    pub fn no_cache(&self) -> Option<Vec<&HeaderName>> {
+        self.field_names(|d| match *d {
+            CacheDirective::NoCache(ref fields) => Some(fields),
+            _ => None,
+        })
+    }
+
+    /// Determines if the `no-store` directive is present.
+    pub fn no_store(&self) -> bool {
+        self.has(&CacheDirective::NoStore)
+    }
+
+    /// Determines if the `no-transform` directive is present.
+    pub fn no_transform(&self) -> bool {
+        self.has(&CacheDirective::NoTransform)
+    }
+
+    /// Determines if the `only-if-cached` directive is present.
+    pub fn only_if_cached(&self) -> bool {
+        self.has(&CacheDirective::OnlyIfCached)
+    }
+
+    /// Determines if the `must-revalidate` directive is present.
+    pub fn must_revalidate(&self) -> bool {
+        self.has(&CacheDirective::MustRevalidate)
+    }
+
+    /// Determines if the `public` directive is present.
+    pub fn public(&self) -> bool {
+        self.has(&CacheDirective::Public)
+    }
+
+    /// Returns the field names of the `private` directive.
+    ///
+    /// `None` is returned if the directive is absent, and an empty list is returned if it applies
+    /// to the entire response.
+    pub fn private(&self) -> Option<Vec<&HeaderName>> {
+        self.field_names(|d| match *d {
+            CacheDirective::Private(ref fields) => Some(fields),
+            _ => None,
+        })
+    }
+
+    /// Determines if the `proxy-revalidate` directive is present.
+    pub fn proxy_revalidate(&self) -> bool {
+        self.has(&CacheDirective::ProxyRevalidate)
+    }
+
+    /// Returns the value of the `s-maxage` directive, in seconds.
+    pub fn s_maxage(&self) -> Option<u64> {
+        self.min_seconds(|d| match *d {
+            CacheDirective::SMaxAge(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Determines if the `immutable` directive is present.
+    pub fn immutable(&self) -> bool {
+        self.has(&CacheDirective::Immutable)
+    }
+
+    /// Returns the value of the `stale-while-revalidate` directive, in seconds.
+    pub fn stale_while_revalidate(&self) -> Option<u64> {
+        self.min_seconds(|d| match *d {
+            CacheDirective::StaleWhileRevalidate(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Returns the value of the `stale-if-error` directive, in seconds.
+    pub fn stale_if_error(&self) -> Option<u64> {
+        self.min_seconds(|d| match *d {
+            CacheDirective::StaleIfError(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Returns the first extension directive with the specified name, ignoring case.
+    pub fn extension(&self, name: &str) -> Option<&CacheExtension> {
+        self.0.iter().find_map(|d| match *d {
+            CacheDirective::Extension(ref e) if e.name.eq_ignore_ascii_case(name) => Some(e),
+            _ => None,
+        })
+    }
+}
+
+impl Deref for CacheControl {
+    type Target = Vec<CacheDirective>;
+
+    #[inline]
+    fn deref(&self) -> &Vec<CacheDirective> {
+        &self.0
+    }
+}
+
+impl From<CacheDirective> for CacheControl {
+    #[inline]
+    fn from(directive: CacheDirective) -> CacheControl {
+        CacheControl(vec![directive])
+    }
+}
+
+impl Header for CacheControl {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &CACHE_CONTROL
+    }
+
+    // Quoted field name lists can contain commas, so we can't use util::parse_comma_delimited.
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<CacheControl>, Error> {
+        let mut directives = vec![];
+        let mut empty = true;
+        for (index, value) in values.enumerate() {
+            empty = false;
+
+            let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+            let mut c = Cursor::new(value);
+            loop {
+                c.skip_empty_elements();
+                if c.is_empty() {
+                    break;
+                }
+
+                directives.push(CacheDirective::parse(&mut c).map_err(|e| e.with_index(index))?);
+
+                c.ows();
+                if !c.is_empty() && c.peek() != Some(b',') {
+                    return Err(c.error(ErrorKind::InvalidValue).with_index(index));
+                }
+            }
+        }
+
+        if empty {
+            Ok(None)
+        } else if directives.is_empty() {
+            Err(Error::too_few_values())
+        } else {
+            Ok(Some(CacheControl(directives)))
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_comma_delimited(&self.0, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HeaderMapExt;
+    use http::header::{SET_COOKIE, VARY};
+    use http::HeaderMap;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &CacheControl::from(CacheDirective::NoCache(vec![])),
+            &["no-cache"],
+        );
+        util::test_round_trip(
+            &CacheControl::new(vec![
+                CacheDirective::Private(vec![]),
+                CacheDirective::Extension(CacheExtension::new("community", Some("UCI")).unwrap()),
+            ])
+            .unwrap(),
+            &["private, community=UCI"],
+        );
+        util::test_round_trip(
+            &CacheControl::from(CacheDirective::MaxAge(30)),
+            &["max-age=30"],
+        );
+    }
+
+    #[test]
+    fn all_directives() {
+        let header = CacheControl::new(vec![
+            CacheDirective::MaxAge(1),
+            CacheDirective::MaxStale(None),
+            CacheDirective::MaxStale(Some(2)),
+            CacheDirective::MinFresh(3),
+            CacheDirective::NoCache(vec![SET_COOKIE, VARY]),
+            CacheDirective::NoStore,
+            CacheDirective::NoTransform,
+            CacheDirective::OnlyIfCached,
+            CacheDirective::MustRevalidate,
+            CacheDirective::Public,
+            CacheDirective::Private(vec![SET_COOKIE]),
+            CacheDirective::ProxyRevalidate,
+            CacheDirective::SMaxAge(4),
+            CacheDirective::Immutable,
+            CacheDirective::StaleWhileRevalidate(5),
+            CacheDirective::StaleIfError(6),
+        ])
+        .unwrap();
+        util::test_round_trip(
+            &header,
+            &[
+                "max-age=1, max-stale, max-stale=2, min-fresh=3, no-cache=\"set-cookie, vary\", \
+               no-store, no-transform, only-if-cached, must-revalidate, public, \
+               private=\"set-cookie\", proxy-revalidate, s-maxage=4, immutable, \
+               stale-while-revalidate=5, stale-if-error=6",
+            ],
+        );
+    }
+
+    #[test]
+    fn lenient_forms() {
+        util::test_decode(
+            &[
+                "MAX-AGE=\"60\", Private=Set-Cookie",
+                "No-Cache=\",Set-Cookie ,, Vary\"",
+            ],
+            &CacheControl::new(vec![
+                CacheDirective::MaxAge(60),
+                CacheDirective::Private(vec![SET_COOKIE]),
+                CacheDirective::NoCache(vec![SET_COOKIE, VARY]),
+            ])
+            .unwrap(),
+        );
+        util::test_decode(
+            &["max-age=99999999999999999999999"],
+            &CacheControl::from(CacheDirective::MaxAge(2_147_483_648)),
+        );
+        util::test_decode(
+            &["s-maxage=4294967296"],
+            &CacheControl::from(CacheDirective::SMaxAge(2_147_483_648)),
+        );
+    }
+
+    #[test]
+    fn extensions() {
+        util::test_round_trip(
+            &CacheControl::new(vec![
+                CacheDirective::Extension(CacheExtension::new("Foo", None).unwrap()),
+                CacheDirective::Extension(CacheExtension::new("bar", Some("a, b")).unwrap()),
+            ])
+            .unwrap(),
+            &["Foo, bar=\"a, b\""],
+        );
+
+        util::test_round_trip(
+            &CacheControl::from(CacheDirective::Extension(CacheExtension {
+                name: "ext".to_string(),
+                value: Some(("abc".to_string(), true)),
+            })),
+            &["ext=\"abc\""],
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        let header = CacheControl::new(vec![
+            CacheDirective::MaxAge(60),
+            CacheDirective::MaxAge(30),
+            CacheDirective::MaxStale(None),
+            CacheDirective::MaxStale(Some(10)),
+            CacheDirective::NoCache(vec![SET_COOKIE]),
+            CacheDirective::NoCache(vec![VARY, SET_COOKIE]),
+            CacheDirective::Private(vec![SET_COOKIE]),
+            CacheDirective::Private(vec![]),
+        ])
+        .unwrap();
+        assert_eq!(header.max_age(), Some(30));
+        assert_eq!(header.max_stale(), Some(Some(10)));
+        assert_eq!(header.no_cache(), Some(vec![&SET_COOKIE, &VARY]));
+        assert_eq!(header.private(), Some(vec![]));
+        assert_eq!(header.s_maxage(), None);
+        assert!(!header.no_store());
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "max-age",
+            "max-age=",
+            "max-age=-1",
+            "max-age=1.5",
+            "no-store=1",
+            "no-cache=\"a b\"",
+            "no-cache=\"unterminated",
+            "max-age=1 public",
+            "=1",
+        ];
+
+        for value in &cases {
+            let mut map = HeaderMap::new();
+            map.insert(CACHE_CONTROL, HeaderValue::from_static(value));
+            assert!(map.typed_get::<CacheControl>().is_err(), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use http::header::{self, HeaderValue};
+use std::fmt;
+use std::str::FromStr;
+
+use super::auth_param;
+use super::{AuthParam, AuthScheme, Token68};
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Info {
+    None,
+    Token68(Token68),
+    AuthParams(Vec<AuthParam>),
+}
+
+/// An authentication challenge, as described in [RFC7235].
+///
+/// [RFC7235]: https://tools.ietf.org/html/rfc7235#section-2.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    scheme: AuthScheme,
+    info: Info,
+}
+
+impl Challenge {
+    /// Creates a challenge from just an auth-scheme.
+    #[inline]
+    pub fn from_auth_scheme(scheme: AuthScheme) -> Challenge {
+        Challenge {
+            scheme,
+            info: Info::None,
+        }
+    }
+
+    /// Creates a challenge from an auth-scheme and token68 data.
+    #[inline]
+    pub fn from_token68(scheme: AuthScheme, token: Token68) -> Challenge {
+        Challenge {
+            scheme,
+            info: Info::Token68(token),
+        }
+    }
+
+    /// Creates a challenge from an auth-scheme and a list of auth-params.
+    ///
+    /// An error is returned if a parameter name appears more than once. Parameter names are
+    /// compared case-insensitively.
+    #[inline]
+    pub fn from_auth_params(
+        scheme: AuthScheme,
+        params: Vec<AuthParam>,
+    ) -> Result<Challenge, Error> {
+        auth_param::check_duplicates(&params)?;
+
+        if params.is_empty() {
+            return Ok(Challenge::from_auth_scheme(scheme));
+        }
+
+        Ok(Challenge {
+            scheme,
+            info: Info::AuthParams(params),
+        })
+    }
+
+    /// Creates a Basic authentication challenge as described in [RFC7617].
+    ///
+    /// [RFC7617]: https://tools.ietf.org/html/rfc7617#section-2
+    #[inline]
+    pub fn basic(realm: &str) -> Result<Challenge, Error> {
+        let realm = AuthParam::quoted("realm", realm)?;
+        Challenge::from_auth_params(AuthScheme::BASIC, vec![realm])
+    }
+
+    /// Creates a Bearer authentication challenge as described in [RFC6750].
+    ///
+    /// [RFC6750]: https://tools.ietf.org/html/rfc6750#section-3
+    #[inline]
+    pub fn bearer(params: Vec<AuthParam>) -> Result<Challenge, Error> {
+        Challenge::from_auth_params(AuthScheme::BEARER, params)
+    }
+
+    /// Returns the auth-scheme associated with the challenge.
+    #[inline]
+    pub fn scheme(&self) -> &AuthScheme {
+        &self.scheme
+    }
+
+    /// Returns the token68 value associated with the challenge if present.
+    #[inline]
+    pub fn token68(&self) -> Option<&Token68> {
+        match self.info {
+            Info::Token68(ref token) => Some(token),
+            _ => None,
+        }
+    }
+
+    /// Returns the auth-params associated with the challenge if present.
+    #[inline]
+    pub fn auth_params(&self) -> Option<&[AuthParam]> {
+        match self.info {
+            Info::AuthParams(ref params) => Some(params),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the auth-param with the specified name if present.
+    ///
+    /// Parameter names are compared case-insensitively.
+    #[inline]
+    pub fn auth_param(&self, name: &str) -> Option<&str> {
+        self.auth_params()
+            .and_then(|params| auth_param::find(params, name))
+            .map(AuthParam::value)
+    }
+
+    /// Returns the value of the `realm` auth-param if present.
+    #[inline]
+    pub fn realm(&self) -> Option<&str> {
+        self.auth_param("realm")
+    }
+
+    /// Parses a challenge at the cursor's position.
+    ///
+    /// Since challenges and their auth-params are both comma separated, parsing stops at the
+    /// first list element which is not an auth-param.
+    fn parse(c: &mut Cursor) -> Result<Challenge, Error> {
+        let scheme = c.token()?.parse::<AuthScheme>()?;
+
+        if !c.eat(b' ') {
+            return Ok(Challenge::from_auth_scheme(scheme));
+        }
+        c.ows();
+
+        if AuthParam::is_next(c) {
+            let mut params = vec![];
+            loop {
+                let start = c.pos();
+                let param = AuthParam::parse(c)?;
+                if auth_param::find(&params, param.name()).is_some() {
+                    return Err(Error::new(ErrorKind::DuplicateParameter).with_offset(start));
+                }
+                params.push(param);
+
+                c.ows();
+                let end = c.pos();
+                c.skip_empty_elements();
+                if end == c.pos() || !AuthParam::is_next(c) {
+                    // leave the delimiter for the next challenge
+                    c.set_pos(end);
+                    break;
+                }
+            }
+
+            return Ok(Challenge {
+                scheme,
+                info: Info::AuthParams(params),
+            });
+        }
+
+        let start = c.pos();
+        let token = c.take_while(|b| b != b',' && b != b' ' && b != b'\t');
+        if token.is_empty() {
+            return Ok(Challenge::from_auth_scheme(scheme));
+        }
+        let token = token
+            .parse::<Token68>()
+            .map_err(|e| util::convert_error(e).with_offset(start))?;
+
+        Ok(Challenge::from_token68(scheme, token))
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.info {
+            Info::None => fmt.write_str(self.scheme.as_str()),
+            Info::Token68(ref token) => write!(fmt, "{} {}", self.scheme, token),
+            Info::AuthParams(ref params) => {
+                write!(fmt, "{} ", self.scheme)?;
+                auth_param::write_list(fmt, params)
+            }
+        }
+    }
+}
+
+impl FromStr for Challenge {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Challenge, Error> {
+        let mut c = Cursor::new(s.trim());
+        let challenge = Challenge::parse(&mut c)?;
+        c.skip_empty_elements();
+        if c.is_empty() {
+            Ok(challenge)
+        } else {
+            Err(c.error(ErrorKind::InvalidValue))
+        }
+    }
+}
+
+/// Parses the challenges in a `WWW-Authenticate` or `Proxy-Authenticate` header.
+pub(crate) fn parse_challenges(
+    values: &mut header::ValueIter<HeaderValue>,
+) -> Result<Option<Vec<Challenge>>, Error> {
+    let mut out = vec![];
+    let mut empty = true;
+    for (index, value) in values.enumerate() {
+        empty = false;
+
+        let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+        let mut c = Cursor::new(value);
+        loop {
+            c.skip_empty_elements();
+            if c.is_empty() {
+                break;
+            }
+
+            let challenge = Challenge::parse(&mut c).map_err(|e| e.with_index(index))?;
+            out.push(challenge);
+
+            c.ows();
+            if !c.is_empty() && c.peek() != Some(b',') {
+                return Err(c.error(ErrorKind::InvalidValue).with_index(index));
+            }
+        }
+    }
+
+    if empty {
+        Ok(None)
+    } else if out.is_empty() {
+        Err(Error::too_few_values())
+    } else {
+        Ok(Some(out))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::{HeaderMap, WWW_AUTHENTICATE};
+
+    fn parse(values: &[&'static str]) -> Result<Option<Vec<Challenge>>, Error> {
+        let mut map = HeaderMap::new();
+        for &value in values {
+            map.append(WWW_AUTHENTICATE, HeaderValue::from_static(value));
+        }
+        parse_challenges(&mut map.get_all(WWW_AUTHENTICATE).iter())
+    }
+
+    #[test]
+    fn multiple_challenges() {
+        let challenges = vec![
+            Challenge::basic("a").unwrap(),
+            Challenge::bearer(vec![
+                AuthParam::quoted("realm", "b").unwrap(),
+                AuthParam::quoted("error", "invalid_token").unwrap(),
+            ])
+            .unwrap(),
+        ];
+        assert_eq!(
+            parse(&[r#"Basic realm="a", Bearer realm="b", error="invalid_token""#]).unwrap(),
+            Some(challenges.clone()),
+        );
+        assert_eq!(
+            parse(&[
+                r#"Basic realm="a",,"#,
+                r#", Bearer realm="b" ,error="invalid_token""#,
+            ])
+            .unwrap(),
+            Some(challenges.clone()),
+        );
+        assert_eq!(challenges[1].auth_param("ERROR"), Some("invalid_token"));
+    }
+
+    #[test]
+    fn mixed_forms() {
+        assert_eq!(
+            parse(&["Negotiate, Newauth abc==, Bearer, Basic realm=x"]).unwrap(),
+            Some(vec![
+                Challenge::from_auth_scheme(AuthScheme::new("Negotiate").unwrap()),
+                Challenge::from_token68(
+                    AuthScheme::new("Newauth").unwrap(),
+                    Token68::new("abc==").unwrap(),
+                ),
+                Challenge::from_auth_scheme(AuthScheme::BEARER),
+                Challenge::from_auth_params(
+                    AuthScheme::BASIC,
+                    vec![AuthParam::new("realm", "x").unwrap()],
+                )
+                .unwrap(),
+            ]),
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse(&[]).unwrap(), None);
+
+        let err = parse(&["Basic realm=a b"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+        assert_eq!(err.offset(), Some(14));
+
+        let err = parse(&[", ,"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TooFewValues);
+
+        let err = parse(&["Basic realm=a, realm=b"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DuplicateParameter);
+    }
+}
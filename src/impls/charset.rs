@@ -0,0 +1,42 @@
+token! {
+    /// A character encoding, used in the `Accept-Charset` header and `charset` media type
+    /// parameter.
+    ///
+    /// Names and aliases are taken from the [IANA character set registry].
+    ///
+    /// [IANA character set registry]: https://www.iana.org/assignments/character-sets/character-sets.xhtml
+    Charset => {
+        /// The UTF-8 encoding, as specified in [RFC3629].
+        ///
+        /// [RFC3629]: https://tools.ietf.org/html/rfc3629
+        UTF_8 => "utf-8" => ["utf8", "csutf8"],
+        /// The ISO-8859-1 (Latin-1) encoding.
+        ISO_8859_1 => "iso-8859-1" => [
+            "iso_8859-1:1987",
+            "iso-ir-100",
+            "iso_8859-1",
+            "iso8859-1",
+            "latin1",
+            "l1",
+            "ibm819",
+            "cp819",
+            "csisolatin1"
+        ],
+        /// The US-ASCII encoding.
+        US_ASCII => "us-ascii" => [
+            "iso-ir-6",
+            "ansi_x3.4-1968",
+            "ansi_x3.4-1986",
+            "iso_646.irv:1991",
+            "iso646-us",
+            "us",
+            "ibm367",
+            "cp367",
+            "csascii",
+            "ascii"
+        ],
+        /// A wildcard, used in the `Accept-Charset` header to indicate that all charsets are
+        /// acceptable.
+        STAR => "*" => [],
+    }
+}
@@ -0,0 +1,47 @@
+use http::header::CONTENT_LANGUAGE;
+
+use super::LanguageTag;
+
+header! {
+    /// `Content-Language` header, defined in
+    /// [RFC7231](https://tools.ietf.org/html/rfc7231#section-3.1.3.2)
+    ///
+    /// The `Content-Language` header field describes the natural language(s)
+    /// of the intended audience for the representation.  Note that this
+    /// might not be equivalent to all the languages used within the
+    /// representation.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Content-Language = 1#language-tag
+    /// ```
+    ///
+    /// # Example values
+    /// * `da`
+    /// * `mi, en`
+    (ContentLanguage, CONTENT_LANGUAGE) => (LanguageTag)+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &ContentLanguage::new(vec!["mi".parse().unwrap(), "en".parse().unwrap()]).unwrap(),
+            &["mi, en"],
+        );
+    }
+
+    #[test]
+    fn canonical_case() {
+        util::test_decode(
+            &["EN-us, zh-hant"],
+            &ContentLanguage::new(vec!["en-US".parse().unwrap(), "zh-Hant".parse().unwrap()])
+                .unwrap(),
+        );
+    }
+}
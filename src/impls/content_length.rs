@@ -1,7 +1,7 @@
 use http::header::{self, HeaderName, HeaderValue, CONTENT_LENGTH};
 use std::ops::{Deref, DerefMut};
 
-use crate::{util, Error, Header, ToValues};
+use crate::{util, Error, ErrorKind, Header, ToValues};
 
 /// `Content-Length` header, defined in
 /// [RFC7230](http://tools.ietf.org/html/rfc7230#section-3.3.2)
@@ -58,21 +58,32 @@ impl Header for ContentLength {
     ) -> Result<Option<ContentLength>, Error> {
         let mut length = None;
 
-        for value in values {
-            let value = value.to_str().map_err(|_| Error::invalid_value())?;
+        for (index, value) in values.enumerate() {
+            let value = util::to_str(value).map_err(|e| e.with_index(index))?;
             if value.trim().is_empty() {
-                return Err(Error::invalid_value());
+                return Err(Error::new(ErrorKind::InvalidInteger).with_index(index));
             }
 
+            let mut start = 0;
             for elem in value.split(',') {
+                let offset = start + util::leading_ws(elem);
+                start += elem.len() + 1;
+
                 let elem = elem.trim();
                 if elem.is_empty() {
                     continue;
                 }
 
-                let elem = elem.parse().map_err(|_| Error::invalid_value())?;
+                let elem = elem.parse().map_err(|e| {
+                    Error::new(ErrorKind::InvalidInteger)
+                        .with_index(index)
+                        .with_offset(offset)
+                        .with_source(e)
+                })?;
                 match length {
-                    Some(length) if length != elem => return Err(Error::invalid_value()),
+                    Some(length) if length != elem => {
+                        return Err(Error::invalid_value().with_index(index).with_offset(offset))
+                    }
                     Some(_) => {}
                     None => length = Some(elem),
                 }
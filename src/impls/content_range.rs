@@ -0,0 +1,259 @@
+use http::header::{self, HeaderName, HeaderValue, CONTENT_RANGE};
+use std::fmt;
+use std::str::FromStr;
+
+use super::RangeUnit;
+use crate::{util, Error, Header, ToValues};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Inner {
+    Bytes {
+        range: Option<(u64, u64)>,
+        complete_length: Option<u64>,
+    },
+    Other(RangeUnit, String),
+}
+
+/// `Content-Range` header, defined in
+/// [RFC7233](https://tools.ietf.org/html/rfc7233#section-4.2)
+///
+/// The `Content-Range` header field is sent in a single part 206
+/// (Partial Content) response to indicate the partial range of the
+/// selected representation enclosed as the message payload, sent in each
+/// part of a multipart 206 response to indicate the range enclosed within
+/// each body part, and sent in 416 (Range Not Satisfiable) responses to
+/// provide information about the selected representation.
+///
+/// # ABNF
+///
+/// ```text
+/// Content-Range       = byte-content-range
+///                     / other-content-range
+///
+/// byte-content-range  = bytes-unit SP
+///                       ( byte-range-resp / unsatisfied-range )
+///
+/// byte-range-resp     = byte-range "/" ( complete-length / "*" )
+/// byte-range          = first-byte-pos "-" last-byte-pos
+/// unsatisfied-range   = "*/" complete-length
+///
+/// complete-length     = 1*DIGIT
+///
+/// other-content-range = other-range-unit SP other-range-resp
+/// other-range-resp    = *CHAR
+/// ```
+///
+/// # Example values
+///
+/// * `bytes 42-1233/1234`
+/// * `bytes 42-1233/*`
+/// * `bytes */1234`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentRange(Inner);
+
+impl ContentRange {
+    /// Creates a `Content-Range` header for a satisfied byte range.
+    ///
+    /// `first` and `last` are inclusive. An error is returned if `last` precedes `first`, or if
+    /// the range extends beyond the complete length of the representation when it is known.
+    pub fn bytes(
+        first: u64,
+        last: u64,
+        complete_length: Option<u64>,
+    ) -> Result<ContentRange, Error> {
+        if last < first {
+            return Err(Error::invalid_value());
+        }
+
+        if let Some(complete_length) = complete_length {
+            if last >= complete_length {
+                return Err(Error::invalid_value());
+            }
+        }
+
+        Ok(ContentRange(Inner::Bytes {
+            range: Some((first, last)),
+            complete_length,
+        }))
+    }
+
+    /// Creates a `Content-Range` header for a 416 (Range Not Satisfiable) response.
+    #[inline]
+    pub fn unsatisfied_bytes(complete_length: u64) -> ContentRange {
+        ContentRange(Inner::Bytes {
+            range: None,
+            complete_length: Some(complete_length),
+        })
+    }
+
+    /// Creates a `Content-Range` header from a unit other than `bytes` and an opaque response.
+    pub fn other(unit: RangeUnit, resp: &str) -> Result<ContentRange, Error> {
+        if unit == RangeUnit::BYTES {
+            return format!("{} {}", unit, resp).parse();
+        }
+
+        if let Some(offset) = resp.bytes().position(|b| !(b' '..=b'~').contains(&b)) {
+            return Err(Error::invalid_value().with_offset(offset));
+        }
+
+        Ok(ContentRange(Inner::Other(unit, resp.to_string())))
+    }
+
+    /// Returns the inclusive byte range, if this header uses the `bytes` unit and is satisfied.
+    #[inline]
+    pub fn bytes_range(&self) -> Option<(u64, u64)> {
+        match self.0 {
+            Inner::Bytes { range, .. } => range,
+            Inner::Other(..) => None,
+        }
+    }
+
+    /// Returns the complete length of the representation, if this header uses the `bytes` unit
+    /// and the length is known.
+    #[inline]
+    pub fn bytes_complete_length(&self) -> Option<u64> {
+        match self.0 {
+            Inner::Bytes {
+                complete_length, ..
+            } => complete_length,
+            Inner::Other(..) => None,
+        }
+    }
+
+    /// Returns the unit and response, if this header uses a unit other than `bytes`.
+    #[inline]
+    pub fn other_range(&self) -> Option<(&RangeUnit, &str)> {
+        match self.0 {
+            Inner::Bytes { .. } => None,
+            Inner::Other(ref unit, ref resp) => Some((unit, resp)),
+        }
+    }
+}
+
+impl fmt::Display for ContentRange {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Inner::Bytes {
+                range,
+                complete_length,
+            } => {
+                fmt.write_str("bytes ")?;
+                match range {
+                    Some((first, last)) => write!(fmt, "{}-{}", first, last)?,
+                    None => fmt.write_str("*")?,
+                }
+                match complete_length {
+                    Some(complete_length) => write!(fmt, "/{}", complete_length),
+                    None => fmt.write_str("/*"),
+                }
+            }
+            Inner::Other(ref unit, ref resp) => write!(fmt, "{} {}", unit, resp),
+        }
+    }
+}
+
+impl FromStr for ContentRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ContentRange, Error> {
+        let idx = match s.find(' ') {
+            Some(idx) => idx,
+            None => return Err(Error::invalid_value()),
+        };
+        let unit = s[..idx].parse::<RangeUnit>()?;
+        let resp = &s[idx + 1..];
+        let base = idx + 1;
+
+        if unit != RangeUnit::BYTES {
+            return ContentRange::other(unit, resp).map_err(|e| e.offset_by(base));
+        }
+
+        let slash = match resp.find('/') {
+            Some(slash) => slash,
+            None => return Err(Error::invalid_value().with_offset(base)),
+        };
+        let (range, complete_length) = (&resp[..slash], &resp[slash + 1..]);
+
+        let complete_length = if complete_length == "*" {
+            None
+        } else {
+            let complete_length =
+                util::parse_digits(complete_length).map_err(|e| e.offset_by(base + slash + 1))?;
+            Some(complete_length)
+        };
+
+        if range == "*" {
+            return match complete_length {
+                Some(complete_length) => Ok(ContentRange::unsatisfied_bytes(complete_length)),
+                None => Err(Error::invalid_value().with_offset(base)),
+            };
+        }
+
+        let dash = match range.find('-') {
+            Some(dash) => dash,
+            None => return Err(Error::invalid_value().with_offset(base)),
+        };
+        let first = util::parse_digits(&range[..dash]).map_err(|e| e.offset_by(base))?;
+        let last =
+            util::parse_digits(&range[dash + 1..]).map_err(|e| e.offset_by(base + dash + 1))?;
+
+        ContentRange::bytes(first, last, complete_length).map_err(|e| e.with_offset(base))
+    }
+}
+
+impl Header for ContentRange {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &CONTENT_RANGE
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<ContentRange>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &ContentRange::bytes(42, 1233, Some(1234)).unwrap(),
+            &["bytes 42-1233/1234"],
+        );
+        util::test_round_trip(
+            &ContentRange::bytes(42, 1233, None).unwrap(),
+            &["bytes 42-1233/*"],
+        );
+        util::test_round_trip(&ContentRange::unsatisfied_bytes(1234), &["bytes */1234"]);
+        util::test_round_trip(
+            &ContentRange::other(RangeUnit::new("items").unwrap(), "0-4/10").unwrap(),
+            &["items 0-4/10"],
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "bytes 42-1233",
+            "bytes */*",
+            "bytes 10-5/100",
+            "bytes 0-100/100",
+            "bytes 0-+5/100",
+            "bytes=0-5/100",
+        ];
+
+        for value in &cases {
+            assert!(value.parse::<ContentRange>().is_err(), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,654 @@
+use http::header::CONTENT_SECURITY_POLICY;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{CspDirectiveName, CspKeyword};
+use crate::Error;
+
+header! {
+    /// `Content-Security-Policy` header, defined in
+    /// [CSP3](https://www.w3.org/TR/CSP3/#csp-header)
+    ///
+    /// The `Content-Security-Policy` HTTP response header field is the
+    /// preferred mechanism for delivering a policy from a server to a client.
+    ///
+    /// Each element of the header is a separate policy, and a resource must satisfy all of them.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Content-Security-Policy = 1#serialized-policy
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `default-src 'self'; img-src *`
+    /// * `script-src 'nonce-r4nd0m' 'strict-dynamic'; object-src 'none', frame-ancestors 'none'`
+    (ContentSecurityPolicy, CONTENT_SECURITY_POLICY) => (CspPolicy)+
+}
+
+impl ContentSecurityPolicy {
+    /// Adds another policy, which is enforced alongside the existing policies.
+    ///
+    /// Adding a policy can only further restrict what the resource is permitted to do. Use
+    /// `CspPolicy::merge` to combine the directives of two policies into one.
+    #[inline]
+    pub fn push(&mut self, policy: CspPolicy) {
+        self.0.push(policy);
+    }
+}
+
+/// A single content security policy, consisting of an ordered list of directives.
+///
+/// Directive names are case-insensitive. When parsing, directives with invalid names are ignored,
+/// as are directives whose name was already seen earlier in the policy.
+///
+/// # ABNF
+///
+/// ```text
+/// serialized-policy    = serialized-directive *( OWS ";" [ OWS serialized-directive ] )
+/// serialized-directive = directive-name [ RWS directive-value ]
+/// directive-name       = 1*( ALPHA / DIGIT / "-" )
+/// directive-value      = *( %x09 / %x20-%x2B / %x2D-%x3A / %x3C-%x7E )
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CspPolicy(Vec<CspDirective>);
+
+impl CspPolicy {
+    /// Creates an empty policy.
+    #[inline]
+    pub fn new() -> CspPolicy {
+        CspPolicy::default()
+    }
+
+    /// Returns the directives of the policy, in order.
+    #[inline]
+    pub fn directives(&self) -> &[CspDirective] {
+        &self.0
+    }
+
+    /// Returns the directive with the specified name, if present.
+    pub fn get(&self, name: &CspDirectiveName) -> Option<&CspDirective> {
+        self.0.iter().find(|d| d.name == *name)
+    }
+
+    /// Adds a directive to the policy.
+    ///
+    /// If the policy already has a directive with the same name, it is replaced in its current
+    /// position.
+    pub fn insert(&mut self, directive: CspDirective) {
+        match self.0.iter_mut().find(|d| d.name == directive.name) {
+            Some(existing) => *existing = directive,
+            None => self.0.push(directive),
+        }
+    }
+
+    /// Adds a directive to the policy, returning the modified policy.
+    #[inline]
+    pub fn with(mut self, directive: CspDirective) -> CspPolicy {
+        self.insert(directive);
+        self
+    }
+
+    /// Removes the directive with the specified name, returning it.
+    pub fn remove(&mut self, name: &CspDirectiveName) -> Option<CspDirective> {
+        let idx = self.0.iter().position(|d| d.name == *name)?;
+        Some(self.0.remove(idx))
+    }
+
+    /// Merges the directives of another policy into this one.
+    ///
+    /// Directives which are only present in `other` are appended to this policy. For directives
+    /// present in both, the sources of `other` which this policy lacks are appended to the
+    /// existing directive, and a `'none'` source is dropped once the directive has any other
+    /// sources.
+    ///
+    /// This is intended for assembling a policy from parts. Note that a merged directive permits
+    /// everything either original directive did, and a directive added by `other` no longer falls
+    /// back to this policy's `default-src`.
+    pub fn merge(&mut self, other: &CspPolicy) {
+        for directive in &other.0 {
+            match self.0.iter_mut().find(|d| d.name == directive.name) {
+                Some(existing) => {
+                    for source in &directive.sources {
+                        existing.add_source(source.clone());
+                    }
+                }
+                None => self.0.push(directive.clone()),
+            }
+        }
+    }
+}
+
+impl fmt::Display for CspPolicy {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, directive) in self.0.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str("; ")?;
+            }
+            fmt::Display::fmt(directive, fmt)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CspPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CspPolicy, Error> {
+        let mut policy = CspPolicy::new();
+        for directive in s.split(';') {
+            let mut parts = directive.split(is_ascii_ws).filter(|p| !p.is_empty());
+            let name = match parts.next() {
+                Some(name) if is_directive_name(name) => name,
+                _ => continue,
+            };
+            let name = CspDirectiveName::new(name)?;
+            if policy.get(&name).is_some() {
+                continue;
+            }
+
+            let source_list = takes_source_list(&name);
+            let sources = parts.map(|p| CspSource::parse(p, source_list)).collect();
+            policy.0.push(CspDirective { name, sources });
+        }
+
+        Ok(policy)
+    }
+}
+
+/// A directive of a content security policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CspDirective {
+    name: CspDirectiveName,
+    sources: Vec<CspSource>,
+}
+
+impl CspDirective {
+    /// Creates a new directive.
+    #[inline]
+    pub fn new(name: CspDirectiveName, sources: Vec<CspSource>) -> CspDirective {
+        CspDirective { name, sources }
+    }
+
+    /// Returns the name of the directive.
+    #[inline]
+    pub fn name(&self) -> &CspDirectiveName {
+        &self.name
+    }
+
+    /// Returns the values of the directive.
+    #[inline]
+    pub fn sources(&self) -> &[CspSource] {
+        &self.sources
+    }
+
+    /// Adds a value to the directive, if it is not already present.
+    ///
+    /// A `'none'` source is removed if the directive has any other sources.
+    pub fn add_source(&mut self, source: CspSource) {
+        if !self.sources.contains(&source) {
+            self.sources.push(source);
+        }
+        if self.sources.len() > 1 {
+            self.sources
+                .retain(|s| s.as_keyword() != Some(&CspKeyword::NONE));
+        }
+    }
+}
+
+impl fmt::Display for CspDirective {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.name, fmt)?;
+        for source in &self.sources {
+            write!(fmt, " {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+/// A hash algorithm used in a hash source expression.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CspHashAlgorithm {
+    /// SHA-256.
+    Sha256,
+    /// SHA-384.
+    Sha384,
+    /// SHA-512.
+    Sha512,
+}
+
+impl CspHashAlgorithm {
+    fn as_str(self) -> &'static str {
+        match self {
+            CspHashAlgorithm::Sha256 => "sha256",
+            CspHashAlgorithm::Sha384 => "sha384",
+            CspHashAlgorithm::Sha512 => "sha512",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SourceInner {
+    Keyword(CspKeyword),
+    Nonce(String),
+    Hash(CspHashAlgorithm, String),
+    Scheme(String),
+    Host(String),
+    Other(String),
+}
+
+/// A value of a content security policy directive.
+///
+/// The values of directives which take a source list, like `script-src` and `frame-ancestors`,
+/// are parsed into keyword, nonce, hash, scheme, and host source expressions. All other values,
+/// including those of other directives like `sandbox` and `report-uri`, are kept as-is.
+///
+/// # ABNF
+///
+/// ```text
+/// source-expression = scheme-source / host-source / keyword-source
+///                     / nonce-source / hash-source
+/// scheme-source     = scheme-part ":"
+/// host-source       = [ scheme-part "://" ] host-part [ ":" port-part ] [ path-part ]
+/// keyword-source    = "'self'" / "'unsafe-inline'" / "'unsafe-eval'"
+///                     / "'strict-dynamic'" / "'unsafe-hashes'"
+///                     / "'report-sample'" / "'unsafe-allow-redirects'"
+///                     / "'wasm-unsafe-eval'"
+/// nonce-source      = "'nonce-" base64-value "'"
+/// hash-source       = "'" hash-algorithm "-" base64-value "'"
+/// hash-algorithm    = "sha256" / "sha384" / "sha512"
+/// base64-value      = 1*( ALPHA / DIGIT / "+" / "/" / "-" / "_" )*2( "=" )
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CspSource(SourceInner);
+
+impl CspSource {
+    /// Creates a keyword source expression.
+    #[inline]
+    pub fn keyword(keyword: CspKeyword) -> CspSource {
+        CspSource(SourceInner::Keyword(keyword))
+    }
+
+    /// Creates a nonce source expression from a base64-encoded nonce.
+    pub fn nonce(nonce: &str) -> Result<CspSource, Error> {
+        if is_base64_value(nonce) {
+            Ok(CspSource(SourceInner::Nonce(nonce.to_string())))
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    /// Creates a hash source expression from a base64-encoded digest.
+    pub fn hash(algorithm: CspHashAlgorithm, digest: &str) -> Result<CspSource, Error> {
+        if is_base64_value(digest) {
+            Ok(CspSource(SourceInner::Hash(algorithm, digest.to_string())))
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    /// Creates a scheme source expression, like `https`.
+    ///
+    /// The scheme should not include the trailing `:`.
+    pub fn scheme(scheme: &str) -> Result<CspSource, Error> {
+        if is_scheme(scheme) {
+            Ok(CspSource(SourceInner::Scheme(scheme.to_ascii_lowercase())))
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    /// Creates a host source expression, like `https://*.example.com:443/path`.
+    pub fn host(host: &str) -> Result<CspSource, Error> {
+        if is_host_source(host) {
+            Ok(CspSource(SourceInner::Host(host.to_string())))
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    /// Creates a value which is not a source expression, like the `allow-scripts` flag of the
+    /// `sandbox` directive.
+    pub fn other(value: &str) -> Result<CspSource, Error> {
+        if !value.is_empty() && value.bytes().all(is_directive_value_char) {
+            Ok(CspSource(SourceInner::Other(value.to_string())))
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    fn parse(s: &str, source_list: bool) -> CspSource {
+        if source_list {
+            if let Some(inner) = s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                if let Some(source) = parse_quoted_source(inner) {
+                    return source;
+                }
+            } else if let Some(scheme) = s.strip_suffix(':') {
+                if let Ok(source) = CspSource::scheme(scheme) {
+                    return source;
+                }
+            } else if let Ok(source) = CspSource::host(s) {
+                return source;
+            }
+        }
+
+        CspSource(SourceInner::Other(s.to_string()))
+    }
+
+    /// Returns the keyword, if this is a keyword source expression.
+    pub fn as_keyword(&self) -> Option<&CspKeyword> {
+        match self.0 {
+            SourceInner::Keyword(ref keyword) => Some(keyword),
+            _ => None,
+        }
+    }
+
+    /// Returns the nonce, if this is a nonce source expression.
+    pub fn as_nonce(&self) -> Option<&str> {
+        match self.0 {
+            SourceInner::Nonce(ref nonce) => Some(nonce),
+            _ => None,
+        }
+    }
+
+    /// Returns the algorithm and digest, if this is a hash source expression.
+    pub fn as_hash(&self) -> Option<(CspHashAlgorithm, &str)> {
+        match self.0 {
+            SourceInner::Hash(algorithm, ref digest) => Some((algorithm, digest)),
+            _ => None,
+        }
+    }
+
+    /// Returns the scheme, if this is a scheme source expression.
+    pub fn as_scheme(&self) -> Option<&str> {
+        match self.0 {
+            SourceInner::Scheme(ref scheme) => Some(scheme),
+            _ => None,
+        }
+    }
+
+    /// Returns the host source, if this is a host source expression.
+    pub fn as_host(&self) -> Option<&str> {
+        match self.0 {
+            SourceInner::Host(ref host) => Some(host),
+            _ => None,
+        }
+    }
+
+    /// Returns the value, if this is not a source expression.
+    pub fn as_other(&self) -> Option<&str> {
+        match self.0 {
+            SourceInner::Other(ref value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<CspKeyword> for CspSource {
+    #[inline]
+    fn from(keyword: CspKeyword) -> CspSource {
+        CspSource::keyword(keyword)
+    }
+}
+
+impl fmt::Display for CspSource {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            SourceInner::Keyword(ref keyword) => write!(fmt, "'{}'", keyword),
+            SourceInner::Nonce(ref nonce) => write!(fmt, "'nonce-{}'", nonce),
+            SourceInner::Hash(algorithm, ref digest) => {
+                write!(fmt, "'{}-{}'", algorithm.as_str(), digest)
+            }
+            SourceInner::Scheme(ref scheme) => write!(fmt, "{}:", scheme),
+            SourceInner::Host(ref host) => fmt.write_str(host),
+            SourceInner::Other(ref value) => fmt.write_str(value),
+        }
+    }
+}
+
+fn parse_quoted_source(s: &str) -> Option<CspSource> {
+    let (prefix, value) = match s.find('-') {
+        Some(idx) => (&s[..idx], &s[idx + 1..]),
+        None => (s, ""),
+    };
+    let algorithm = if prefix.eq_ignore_ascii_case("nonce") {
+        return CspSource::nonce(value).ok();
+    } else if prefix.eq_ignore_ascii_case("sha256") {
+        CspHashAlgorithm::Sha256
+    } else if prefix.eq_ignore_ascii_case("sha384") {
+        CspHashAlgorithm::Sha384
+    } else if prefix.eq_ignore_ascii_case("sha512") {
+        CspHashAlgorithm::Sha512
+    } else {
+        return CspKeyword::new(s).ok().map(CspSource::keyword);
+    };
+
+    CspSource::hash(algorithm, value).ok()
+}
+
+fn takes_source_list(name: &CspDirectiveName) -> bool {
+    let name = name.as_str();
+    name.ends_with("-src")
+        || name.starts_with("script-src-")
+        || name.starts_with("style-src-")
+        || name == "base-uri"
+        || name == "form-action"
+        || name == "frame-ancestors"
+}
+
+fn is_ascii_ws(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\n' || c == '\x0c' || c == '\r'
+}
+
+fn is_directive_name(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+fn is_directive_value_char(b: u8) -> bool {
+    (b'!'..=b'~').contains(&b) && b != b';' && b != b','
+}
+
+fn is_base64_value(s: &str) -> bool {
+    let value = s.trim_end_matches('=');
+    !value.is_empty()
+        && s.len() - value.len() <= 2
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"+/-_".contains(&b))
+}
+
+fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+}
+
+fn is_host_source(s: &str) -> bool {
+    let rest = match s.find("://") {
+        Some(idx) if is_scheme(&s[..idx]) => &s[idx + 3..],
+        Some(_) => return false,
+        None => s,
+    };
+
+    let end = rest.find(['/', ':']).unwrap_or(rest.len());
+    let (host, mut rest) = rest.split_at(end);
+    if host != "*" {
+        let host = host.strip_prefix("*.").unwrap_or(host);
+        let host = host.strip_suffix('.').unwrap_or(host);
+        let valid = host.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        });
+        if !valid {
+            return false;
+        }
+    }
+
+    if let Some(port) = rest.strip_prefix(':') {
+        let end = port.find('/').unwrap_or(port.len());
+        let (port, path) = port.split_at(end);
+        if port != "*" && (port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit())) {
+            return false;
+        }
+        rest = path;
+    }
+
+    rest.is_empty() || (rest.starts_with('/') && rest.bytes().all(is_directive_value_char))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    fn source(s: &str) -> CspSource {
+        CspSource::parse(s, true)
+    }
+
+    #[test]
+    fn rfc() {
+        let policy = CspPolicy::new()
+            .with(CspDirective::new(
+                CspDirectiveName::DEFAULT_SRC,
+                vec![CspKeyword::SELF.into()],
+            ))
+            .with(CspDirective::new(
+                CspDirectiveName::SCRIPT_SRC,
+                vec![
+                    CspSource::nonce("r4nd0m").unwrap(),
+                    CspKeyword::STRICT_DYNAMIC.into(),
+                    CspSource::hash(CspHashAlgorithm::Sha256, "abc+/=").unwrap(),
+                    CspSource::scheme("https").unwrap(),
+                    CspSource::host("*.example.com:443").unwrap(),
+                ],
+            ))
+            .with(CspDirective::new(
+                CspDirectiveName::UPGRADE_INSECURE_REQUESTS,
+                vec![],
+            ));
+        util::test_round_trip(
+            &ContentSecurityPolicy::from(policy),
+            &[
+                "default-src 'self'; script-src 'nonce-r4nd0m' 'strict-dynamic' \
+               'sha256-abc+/=' https: *.example.com:443; upgrade-insecure-requests",
+            ],
+        );
+
+        let header = ContentSecurityPolicy::new(vec![
+            "default-src 'self'".parse().unwrap(),
+            "frame-ancestors 'none'".parse().unwrap(),
+        ])
+        .unwrap();
+        util::test_round_trip(&header, &["default-src 'self', frame-ancestors 'none'"]);
+        util::test_decode(&["default-src 'self'", "frame-ancestors 'none'"], &header);
+    }
+
+    #[test]
+    fn parse() {
+        let policy = "  Script-Src 'SELF'\thttps://cdn.example.com/js/ 'NONCE-abc' data: ;; \
+                      sandbox allow-scripts; report-uri /csp; script-src *; bad_name x"
+            .parse::<CspPolicy>()
+            .unwrap();
+        assert_eq!(policy.directives().len(), 3);
+
+        let script = policy.get(&CspDirectiveName::SCRIPT_SRC).unwrap();
+        assert_eq!(script.sources()[0].as_keyword(), Some(&CspKeyword::SELF));
+        assert_eq!(
+            script.sources()[1].as_host(),
+            Some("https://cdn.example.com/js/")
+        );
+        assert_eq!(script.sources()[2].as_nonce(), Some("abc"));
+        assert_eq!(script.sources()[3].as_scheme(), Some("data"));
+
+        let sandbox = policy.get(&CspDirectiveName::SANDBOX).unwrap();
+        assert_eq!(sandbox.sources()[0].as_other(), Some("allow-scripts"));
+        let report_uri = policy.get(&CspDirectiveName::REPORT_URI).unwrap();
+        assert_eq!(report_uri.sources()[0].as_other(), Some("/csp"));
+
+        assert_eq!(
+            policy.to_string(),
+            "script-src 'self' https://cdn.example.com/js/ 'nonce-abc' data:; \
+             sandbox allow-scripts; report-uri /csp"
+        );
+    }
+
+    #[test]
+    fn sources() {
+        let cases = [
+            "*",
+            "example.com",
+            "*.example.com",
+            "example.com.",
+            "https://example.com",
+            "wss://example.com:*",
+            "example.com:8080/path",
+        ];
+        for value in &cases {
+            assert_eq!(source(value).as_host(), Some(*value), "{}", value);
+        }
+
+        let cases = [
+            "'nonce-'",
+            "'nonce-a=b'",
+            "'sha256-abc==='",
+            "'a=b'",
+            "exa_mple.com",
+            "*.*.example.com",
+            "example..com",
+            "example.com:8o",
+            "1http://example.com",
+        ];
+        for value in &cases {
+            assert_eq!(source(value).as_other(), Some(*value), "{}", value);
+        }
+
+        assert_eq!(
+            source("'SHA384-abc='").as_hash(),
+            Some((CspHashAlgorithm::Sha384, "abc="))
+        );
+        assert_eq!(
+            source("'unknown'").as_keyword().unwrap().as_str(),
+            "unknown"
+        );
+        assert_eq!(source("HTTPS:").to_string(), "https:");
+
+        assert!(CspSource::host("example.com; script-src *").is_err());
+        assert!(CspSource::other("a b").is_err());
+        assert!(CspSource::nonce("").is_err());
+    }
+
+    #[test]
+    fn merge() {
+        let mut policy = "default-src 'self'; script-src 'none'; img-src 'self'"
+            .parse::<CspPolicy>()
+            .unwrap();
+        let other = "script-src https://cdn.example.com; img-src 'self' data:; object-src 'none'"
+            .parse::<CspPolicy>()
+            .unwrap();
+        policy.merge(&other);
+        assert_eq!(
+            policy.to_string(),
+            "default-src 'self'; script-src https://cdn.example.com; \
+             img-src 'self' data:; object-src 'none'"
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let mut policy = "default-src 'self'; img-src *"
+            .parse::<CspPolicy>()
+            .unwrap();
+        policy.insert(CspDirective::new(
+            CspDirectiveName::DEFAULT_SRC,
+            vec![CspKeyword::NONE.into()],
+        ));
+        assert_eq!(policy.to_string(), "default-src 'none'; img-src *");
+
+        let removed = policy.remove(&CspDirectiveName::IMG_SRC).unwrap();
+        assert_eq!(removed.to_string(), "img-src *");
+        assert_eq!(policy.to_string(), "default-src 'none'");
+    }
+}
@@ -0,0 +1,49 @@
+use http::header::CONTENT_SECURITY_POLICY_REPORT_ONLY;
+
+use super::CspPolicy;
+
+header! {
+    /// `Content-Security-Policy-Report-Only` header, defined in
+    /// [CSP3](https://www.w3.org/TR/CSP3/#cspro-header)
+    ///
+    /// The `Content-Security-Policy-Report-Only` HTTP response header field
+    /// allows web developers to experiment with policies by monitoring (but
+    /// not enforcing) their effects.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Content-Security-Policy-Report-Only = 1#serialized-policy
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `default-src 'self'; report-to csp-endpoint`
+    (ContentSecurityPolicyReportOnly, CONTENT_SECURITY_POLICY_REPORT_ONLY) => (CspPolicy)+
+}
+
+impl ContentSecurityPolicyReportOnly {
+    /// Adds another policy, which is monitored alongside the existing policies.
+    #[inline]
+    pub fn push(&mut self, policy: CspPolicy) {
+        self.0.push(policy);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &ContentSecurityPolicyReportOnly::from(
+                "default-src 'self'; report-to csp-endpoint"
+                    .parse::<CspPolicy>()
+                    .unwrap(),
+            ),
+            &["default-src 'self'; report-to csp-endpoint"],
+        );
+    }
+}
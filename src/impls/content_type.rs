@@ -2,6 +2,8 @@ use http::header::CONTENT_TYPE;
 
 use mime::Mime;
 
+use super::Charset;
+
 header! {
     /// `Content-Type` header, defined in
     /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-3.1.1.5)
@@ -26,3 +28,40 @@ header! {
     /// * `application/json`
     (ContentType, CONTENT_TYPE) => [Mime]
 }
+
+impl ContentType {
+    /// Returns the value of the media type's `charset` parameter, if present.
+    ///
+    /// `None` is also returned if the parameter's value is not a valid charset token.
+    pub fn charset(&self) -> Option<Charset> {
+        self.0
+            .get_param(mime::CHARSET)
+            .and_then(|charset| charset.as_str().parse().ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &ContentType("text/html; charset=utf-8".parse().unwrap()),
+            &["text/html; charset=utf-8"],
+        );
+    }
+
+    #[test]
+    fn charset() {
+        let header = ContentType("text/html; charset=UTF-8".parse().unwrap());
+        assert_eq!(header.charset(), Some(Charset::UTF_8));
+
+        let header = ContentType("text/plain; charset=latin1".parse().unwrap());
+        assert_eq!(header.charset(), Some(Charset::ISO_8859_1));
+
+        let header = ContentType(mime::APPLICATION_JSON);
+        assert_eq!(header.charset(), None);
+    }
+}
@@ -0,0 +1,225 @@
+use http::header::{self, HeaderName, HeaderValue, COOKIE};
+use std::fmt;
+use std::mem;
+
+use super::set_cookie;
+use crate::{util, Error, Header, ToValues};
+
+/// `Cookie` header, defined in [RFC6265bis](https://tools.ietf.org/html/draft-ietf-httpbis-rfc6265bis-12#section-4.2)
+///
+/// The `Cookie` header field contains cookies the user agent received in
+/// previous `Set-Cookie` headers.  The origin server is free to ignore
+/// the `Cookie` header field or use its contents for an application-
+/// defined purpose.
+///
+/// The header is an ordered multimap from cookie names to values. Cookie names are
+/// case-sensitive, and any double quotes surrounding a value are considered part of it.
+///
+/// Parsing is lenient: whitespace around names, values, and separators is ignored, and pairs
+/// without a name or an `=` are skipped. Multiple `Cookie` header fields, as permitted by HTTP/2,
+/// are merged in order.
+///
+/// # ABNF
+///
+/// ```text
+/// cookie-header = "Cookie:" SP cookie-string
+/// cookie-string = cookie-pair *( ";" SP cookie-pair )
+/// ```
+///
+/// # Example values
+///
+/// * `SID=31d4d96e407aad42`
+/// * `SID=31d4d96e407aad42; lang=en-US`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookie(Vec<(String, String)>);
+
+impl Cookie {
+    /// Creates an empty `Cookie` header.
+    #[inline]
+    pub fn new() -> Cookie {
+        Cookie::default()
+    }
+
+    /// Returns the value of the first cookie with the specified name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.iter().find(|(n, _)| n == name).map(|(_, v)| &**v)
+    }
+
+    /// Returns an iterator over the values of all cookies with the specified name.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, v)| &**v)
+    }
+
+    /// Returns an iterator over the names and values of the cookies, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(n, v)| (&**n, &**v))
+    }
+
+    /// Returns the number of cookies.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Determines if there are no cookies.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the value of a cookie, replacing any existing cookies with the same name.
+    ///
+    /// The cookie takes the position of the first existing cookie with the name, or is added to
+    /// the end if there is none. An error is returned if the name is not a token or the value
+    /// contains invalid characters.
+    pub fn insert(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        validate(name, value)?;
+
+        let mut found = false;
+        self.0
+            .retain(|(n, _)| n != name || !mem::replace(&mut found, true));
+
+        match self.0.iter_mut().find(|(n, _)| n == name) {
+            Some(cookie) => cookie.1 = value.to_string(),
+            None => self.0.push((name.to_string(), value.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Adds a cookie to the end of the header, retaining any existing cookies with the same
+    /// name.
+    ///
+    /// An error is returned if the name is not a token or the value contains invalid characters.
+    pub fn append(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        validate(name, value)?;
+        self.0.push((name.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    /// Removes all cookies with the specified name.
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|(n, _)| n != name);
+    }
+}
+
+fn validate(name: &str, value: &str) -> Result<(), Error> {
+    set_cookie::validate_name(name)?;
+    set_cookie::validate_value(value).map_err(|e| e.offset_by(name.len() + 1))
+}
+
+impl fmt::Display for Cookie {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str("; ")?;
+            }
+            write!(fmt, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Header for Cookie {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &COOKIE
+    }
+
+    #[inline]
+    fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<Cookie>, Error> {
+        let mut cookies = vec![];
+        let mut empty = true;
+        for (index, value) in values.enumerate() {
+            empty = false;
+
+            let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+            for pair in value.split(';') {
+                let eq = match pair.find('=') {
+                    Some(eq) => eq,
+                    None => continue,
+                };
+                let name = pair[..eq].trim();
+                if name.is_empty() {
+                    continue;
+                }
+                let value = pair[eq + 1..].trim();
+                cookies.push((name.to_string(), value.to_string()));
+            }
+        }
+
+        if empty {
+            Ok(None)
+        } else {
+            Ok(Some(Cookie(cookies)))
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cookie(pairs: &[(&str, &str)]) -> Cookie {
+        let mut cookie = Cookie::new();
+        for &(name, value) in pairs {
+            cookie.append(name, value).unwrap();
+        }
+        cookie
+    }
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &cookie(&[("SID", "31d4d96e407aad42")]),
+            &["SID=31d4d96e407aad42"],
+        );
+        util::test_round_trip(
+            &cookie(&[("SID", "31d4d96e407aad42"), ("lang", "en-US")]),
+            &["SID=31d4d96e407aad42; lang=en-US"],
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        util::test_decode(
+            &[" a = 1 ;b=2;;  c=\"x y\"; d; =e", "a=3; f="],
+            &Cookie(vec![
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string()),
+                ("c".to_string(), "\"x y\"".to_string()),
+                ("a".to_string(), "3".to_string()),
+                ("f".to_string(), "".to_string()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn multimap() {
+        let mut cookie = cookie(&[("a", "1"), ("b", "2"), ("a", "3")]);
+        assert_eq!(cookie.get("a"), Some("1"));
+        assert_eq!(cookie.get("A"), None);
+        assert_eq!(cookie.get_all("a").collect::<Vec<_>>(), vec!["1", "3"]);
+        assert_eq!(cookie.len(), 3);
+
+        cookie.insert("c", "4").unwrap();
+        cookie.insert("a", "5").unwrap();
+        assert_eq!(
+            cookie.iter().collect::<Vec<_>>(),
+            vec![("a", "5"), ("b", "2"), ("c", "4")]
+        );
+
+        cookie.remove("b");
+        assert_eq!(cookie.to_string(), "a=5; c=4");
+
+        assert!(cookie.insert("a b", "1").is_err());
+        assert!(cookie.append("a", "1;2").is_err());
+    }
+}
@@ -1,15 +1,16 @@
-use base64;
 use std::fmt;
 use std::str::FromStr;
 
+use super::auth_param;
+use super::{AuthParam, AuthScheme, Token68};
+use crate::util::{self, Cursor};
 use crate::Error;
-use super::{AuthScheme, Token68};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Info {
     None,
     Token68(Token68),
-    // AuthParams(Vec<(String, String)>),
+    AuthParams(Vec<AuthParam>),
 }
 
 /// Authentication credentials, as described in [RFC7235].
@@ -40,6 +41,27 @@ impl Credentials {
         }
     }
 
+    /// Creates credentials from an auth-scheme and a list of auth-params.
+    ///
+    /// An error is returned if a parameter name appears more than once. Parameter names are
+    /// compared case-insensitively.
+    #[inline]
+    pub fn from_auth_params(
+        scheme: AuthScheme,
+        params: Vec<AuthParam>,
+    ) -> Result<Credentials, Error> {
+        auth_param::check_duplicates(&params)?;
+
+        if params.is_empty() {
+            return Ok(Credentials::from_auth_scheme(scheme));
+        }
+
+        Ok(Credentials {
+            scheme,
+            info: Info::AuthParams(params),
+        })
+    }
+
     /// Creates Bearer authentication credentials as described in [RFC6750].
     ///
     /// [RFC6750]: https://tools.ietf.org/html/rfc6750
@@ -78,11 +100,30 @@ impl Credentials {
     #[inline]
     pub fn token68(&self) -> Option<&Token68> {
         match self.info {
-            Info::None => None,
             Info::Token68(ref token) => Some(token),
+            _ => None,
+        }
+    }
+
+    /// Returns the auth-params associated with the credentials if present.
+    #[inline]
+    pub fn auth_params(&self) -> Option<&[AuthParam]> {
+        match self.info {
+            Info::AuthParams(ref params) => Some(params),
+            _ => None,
         }
     }
 
+    /// Returns the value of the auth-param with the specified name if present.
+    ///
+    /// Parameter names are compared case-insensitively.
+    #[inline]
+    pub fn auth_param(&self, name: &str) -> Option<&str> {
+        self.auth_params()
+            .and_then(|params| auth_param::find(params, name))
+            .map(AuthParam::value)
+    }
+
     /// Returns the bearer token if this contains Bearer credentials.
     #[inline]
     pub fn as_bearer(&self) -> Option<&Token68> {
@@ -99,6 +140,10 @@ impl fmt::Display for Credentials {
         match self.info {
             Info::None => fmt.write_str(self.scheme.as_str()),
             Info::Token68(ref token) => write!(fmt, "{} {}", self.scheme, token),
+            Info::AuthParams(ref params) => {
+                write!(fmt, "{} ", self.scheme)?;
+                auth_param::write_list(fmt, params)
+            }
         }
     }
 }
@@ -108,24 +153,29 @@ impl FromStr for Credentials {
 
     fn from_str(s: &str) -> Result<Credentials, Error> {
         let mut it = s.splitn(2, ' ');
-        let auth_scheme = it
-            .next()
-            .unwrap()
-            .parse::<AuthScheme>()
-            .map_err(|_| Error::invalid_value())?;
+        let auth_scheme = it.next().unwrap().parse::<AuthScheme>()?;
 
         let info = match it.next() {
             Some(info) => info,
             None => return Ok(Credentials::from_auth_scheme(auth_scheme)),
         };
 
-        let info = info.trim_start_matches(' ');
-
-        match info.parse::<Token68>() {
-            Ok(token) => Ok(Credentials::from_token68(auth_scheme, token)),
-            // FIXME parse out auth-params
-            Err(_) => return Err(Error::invalid_value()),
+        let offset = s.len() - info.len();
+        let trimmed = info.trim_start_matches(' ');
+        let offset = offset + info.len() - trimmed.len();
+
+        match trimmed.parse::<Token68>() {
+            Ok(token) => return Ok(Credentials::from_token68(auth_scheme, token)),
+            // auth-params must contain an `=` before any token68-style padding
+            Err(e) if !trimmed.trim_end_matches('=').contains('=') => {
+                return Err(util::convert_error(e).with_offset(offset));
+            }
+            Err(_) => {}
         }
+
+        let params =
+            auth_param::parse_list(&mut Cursor::new(trimmed)).map_err(|e| e.offset_by(offset))?;
+        Credentials::from_auth_params(auth_scheme, params)
     }
 }
 
@@ -0,0 +1,81 @@
+use http::header::{self, HeaderName, HeaderValue};
+
+use crate::structured::{self, BareItem, Item};
+use crate::{util, Error, Header, ToValues};
+
+static CROSS_ORIGIN_EMBEDDER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-embedder-policy");
+
+token! {
+    /// `Cross-Origin-Embedder-Policy` header, defined in the
+    /// [HTML Standard](https://html.spec.whatwg.org/multipage/browsers.html#the-coep-headers)
+    ///
+    /// The `Cross-Origin-Embedder-Policy` header controls whether a document may load
+    /// cross-origin resources which have not explicitly granted it permission to do so.
+    ///
+    /// The header is a structured field item. Its parameters, such as `report-to`, are ignored.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Cross-Origin-Embedder-Policy = sf-token *( ";" parameter )
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `require-corp`
+    /// * `credentialless`
+    CrossOriginEmbedderPolicy => {
+        /// Cross-origin resources can be loaded without restriction.
+        UNSAFE_NONE => "unsafe-none" => [],
+        /// Cross-origin resources must opt in with CORS or `Cross-Origin-Resource-Policy`.
+        REQUIRE_CORP => "require-corp" => [],
+        /// Cross-origin `no-cors` requests are sent without credentials.
+        CREDENTIALLESS => "credentialless" => [],
+    }
+}
+
+impl Header for CrossOriginEmbedderPolicy {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &CROSS_ORIGIN_EMBEDDER_POLICY
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<CrossOriginEmbedderPolicy>, Error> {
+        let item = match structured::parse_field::<Item>(values)? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        match item.bare_item {
+            BareItem::Token(ref token) => CrossOriginEmbedderPolicy::new(token).map(Some),
+            _ => Err(Error::invalid_value().with_index(0)),
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&CrossOriginEmbedderPolicy::REQUIRE_CORP, &["require-corp"]);
+        util::test_round_trip(
+            &CrossOriginEmbedderPolicy::CREDENTIALLESS,
+            &["credentialless"],
+        );
+        util::test_decode(
+            &["require-corp;report-to=\"coep\""],
+            &CrossOriginEmbedderPolicy::REQUIRE_CORP,
+        );
+    }
+}
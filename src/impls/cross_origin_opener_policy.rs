@@ -0,0 +1,109 @@
+use http::header::{self, HeaderName, HeaderValue};
+
+use crate::structured::{self, BareItem, Item};
+use crate::{util, Error, Header, ToValues};
+
+static CROSS_ORIGIN_OPENER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-opener-policy");
+
+token! {
+    /// `Cross-Origin-Opener-Policy` header, defined in the
+    /// [HTML Standard](https://html.spec.whatwg.org/multipage/browsers.html#the-coop-headers)
+    ///
+    /// The `Cross-Origin-Opener-Policy` header controls whether a top-level document shares a
+    /// browsing context group with cross-origin documents that open it or that it opens.
+    ///
+    /// The header is a structured field item. Its parameters, such as `report-to`, are ignored.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Cross-Origin-Opener-Policy = sf-token *( ";" parameter )
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `same-origin`
+    /// * `same-origin-allow-popups`
+    CrossOriginOpenerPolicy => {
+        /// The document is not isolated from cross-origin documents.
+        UNSAFE_NONE => "unsafe-none" => [],
+        /// The document only shares a browsing context group with same-origin documents.
+        SAME_ORIGIN => "same-origin" => [],
+        /// Like `same-origin`, but popups opened by the document keep a reference to it.
+        SAME_ORIGIN_ALLOW_POPUPS => "same-origin-allow-popups" => [],
+        /// The document is always opened in a new browsing context group.
+        NOOPENER_ALLOW_POPUPS => "noopener-allow-popups" => [],
+    }
+}
+
+impl Header for CrossOriginOpenerPolicy {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &CROSS_ORIGIN_OPENER_POLICY
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<CrossOriginOpenerPolicy>, Error> {
+        let item = match structured::parse_field::<Item>(values)? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        match item.bare_item {
+            BareItem::Token(ref token) => CrossOriginOpenerPolicy::new(token).map(Some),
+            _ => Err(Error::invalid_value().with_index(0)),
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HeaderMapExt;
+    use http::HeaderMap;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&CrossOriginOpenerPolicy::SAME_ORIGIN, &["same-origin"]);
+        util::test_round_trip(
+            &CrossOriginOpenerPolicy::SAME_ORIGIN_ALLOW_POPUPS,
+            &["same-origin-allow-popups"],
+        );
+        util::test_round_trip(&CrossOriginOpenerPolicy::UNSAFE_NONE, &["unsafe-none"]);
+        util::test_decode(
+            &["same-origin; report-to=\"coop\""],
+            &CrossOriginOpenerPolicy::SAME_ORIGIN,
+        );
+        util::test_round_trip(
+            &CrossOriginOpenerPolicy::new("restrict-properties").unwrap(),
+            &["restrict-properties"],
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "\"same-origin\"",
+            "same-origin;",
+            "same-origin, unsafe-none",
+            "?1",
+        ];
+        for value in &cases {
+            let mut map = HeaderMap::new();
+            map.insert(&CROSS_ORIGIN_OPENER_POLICY, HeaderValue::from_static(value));
+            assert!(
+                map.typed_get::<CrossOriginOpenerPolicy>().is_err(),
+                "{}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use http::header::{self, HeaderName, HeaderValue};
+
+use crate::{util, Error, Header, ToValues};
+
+static CROSS_ORIGIN_RESOURCE_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-resource-policy");
+
+token! {
+    /// `Cross-Origin-Resource-Policy` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#cross-origin-resource-policy-header)
+    ///
+    /// The `Cross-Origin-Resource-Policy` response header can be used to
+    /// require checking a request's current URL's origin against a request's
+    /// origin when request's mode is "no-cors".
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Cross-Origin-Resource-Policy = %s"same-origin" / %s"same-site" / %s"cross-origin"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `same-origin`
+    /// * `cross-origin`
+    CrossOriginResourcePolicy => {
+        /// Only requests from the same origin can read the resource.
+        SAME_ORIGIN => "same-origin" => [],
+        /// Only requests from the same site can read the resource.
+        SAME_SITE => "same-site" => [],
+        /// Requests from any origin can read the resource.
+        CROSS_ORIGIN => "cross-origin" => [],
+    }
+}
+
+impl Header for CrossOriginResourcePolicy {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &CROSS_ORIGIN_RESOURCE_POLICY
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<CrossOriginResourcePolicy>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&CrossOriginResourcePolicy::SAME_ORIGIN, &["same-origin"]);
+        util::test_round_trip(&CrossOriginResourcePolicy::SAME_SITE, &["same-site"]);
+        util::test_round_trip(&CrossOriginResourcePolicy::CROSS_ORIGIN, &["cross-origin"]);
+    }
+}
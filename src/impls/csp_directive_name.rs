@@ -0,0 +1,61 @@
+token! {
+    /// The name of a `Content-Security-Policy` directive.
+    CspDirectiveName => {
+        /// The `default-src` directive, the fallback for the other fetch directives.
+        DEFAULT_SRC => "default-src" => [],
+        /// The `child-src` directive.
+        CHILD_SRC => "child-src" => [],
+        /// The `connect-src` directive.
+        CONNECT_SRC => "connect-src" => [],
+        /// The `font-src` directive.
+        FONT_SRC => "font-src" => [],
+        /// The `frame-src` directive.
+        FRAME_SRC => "frame-src" => [],
+        /// The `img-src` directive.
+        IMG_SRC => "img-src" => [],
+        /// The `manifest-src` directive.
+        MANIFEST_SRC => "manifest-src" => [],
+        /// The `media-src` directive.
+        MEDIA_SRC => "media-src" => [],
+        /// The `object-src` directive.
+        OBJECT_SRC => "object-src" => [],
+        /// The `script-src` directive.
+        SCRIPT_SRC => "script-src" => [],
+        /// The `script-src-elem` directive.
+        SCRIPT_SRC_ELEM => "script-src-elem" => [],
+        /// The `script-src-attr` directive.
+        SCRIPT_SRC_ATTR => "script-src-attr" => [],
+        /// The `style-src` directive.
+        STYLE_SRC => "style-src" => [],
+        /// The `style-src-elem` directive.
+        STYLE_SRC_ELEM => "style-src-elem" => [],
+        /// The `style-src-attr` directive.
+        STYLE_SRC_ATTR => "style-src-attr" => [],
+        /// The `worker-src` directive.
+        WORKER_SRC => "worker-src" => [],
+        /// The `base-uri` directive.
+        BASE_URI => "base-uri" => [],
+        /// The `sandbox` directive.
+        SANDBOX => "sandbox" => [],
+        /// The `form-action` directive.
+        FORM_ACTION => "form-action" => [],
+        /// The `frame-ancestors` directive.
+        FRAME_ANCESTORS => "frame-ancestors" => [],
+        /// The `report-uri` directive.
+        REPORT_URI => "report-uri" => [],
+        /// The `report-to` directive.
+        REPORT_TO => "report-to" => [],
+        /// The `upgrade-insecure-requests` directive, defined in [Upgrade Insecure Requests].
+        ///
+        /// [Upgrade Insecure Requests]: https://www.w3.org/TR/upgrade-insecure-requests/
+        UPGRADE_INSECURE_REQUESTS => "upgrade-insecure-requests" => [],
+        /// The `require-trusted-types-for` directive, defined in [Trusted Types].
+        ///
+        /// [Trusted Types]: https://w3c.github.io/trusted-types/dist/spec/
+        REQUIRE_TRUSTED_TYPES_FOR => "require-trusted-types-for" => [],
+        /// The `trusted-types` directive, defined in [Trusted Types].
+        ///
+        /// [Trusted Types]: https://w3c.github.io/trusted-types/dist/spec/
+        TRUSTED_TYPES => "trusted-types" => [],
+    }
+}
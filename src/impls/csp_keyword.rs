@@ -0,0 +1,26 @@
+token! {
+    /// A keyword source expression in a `Content-Security-Policy` directive.
+    ///
+    /// Keywords are serialized surrounded by single quotes, which are not included in the string
+    /// representation of this type.
+    CspKeyword => {
+        /// The `'self'` keyword, matching the origin of the protected resource.
+        SELF => "self" => [],
+        /// The `'none'` keyword, matching nothing.
+        NONE => "none" => [],
+        /// The `'unsafe-inline'` keyword.
+        UNSAFE_INLINE => "unsafe-inline" => [],
+        /// The `'unsafe-eval'` keyword.
+        UNSAFE_EVAL => "unsafe-eval" => [],
+        /// The `'strict-dynamic'` keyword.
+        STRICT_DYNAMIC => "strict-dynamic" => [],
+        /// The `'unsafe-hashes'` keyword.
+        UNSAFE_HASHES => "unsafe-hashes" => [],
+        /// The `'report-sample'` keyword.
+        REPORT_SAMPLE => "report-sample" => [],
+        /// The `'unsafe-allow-redirects'` keyword.
+        UNSAFE_ALLOW_REDIRECTS => "unsafe-allow-redirects" => [],
+        /// The `'wasm-unsafe-eval'` keyword.
+        WASM_UNSAFE_EVAL => "wasm-unsafe-eval" => [],
+    }
+}
@@ -0,0 +1,232 @@
+use http::header::{self, HeaderValue};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind, ToValues};
+
+/// An entity tag, as defined in [RFC7232].
+///
+/// An entity tag is an opaque validator for a representation, and may be either strong or weak.
+///
+/// [RFC7232]: https://tools.ietf.org/html/rfc7232#section-2.3
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Creates a strong entity tag.
+    ///
+    /// The tag should not include the surrounding double quotes. An error is returned if it
+    /// contains characters which are not permitted in an entity tag.
+    #[inline]
+    pub fn strong(tag: &str) -> Result<EntityTag, Error> {
+        EntityTag::new(false, tag)
+    }
+
+    /// Creates a weak entity tag.
+    ///
+    /// The tag should not include the surrounding double quotes or `W/` prefix. An error is
+    /// returned if it contains characters which are not permitted in an entity tag.
+    #[inline]
+    pub fn weak(tag: &str) -> Result<EntityTag, Error> {
+        EntityTag::new(true, tag)
+    }
+
+    fn new(weak: bool, tag: &str) -> Result<EntityTag, Error> {
+        if let Some(offset) = tag.bytes().position(|b| !is_etagc(b)) {
+            return Err(Error::new(ErrorKind::InvalidEntityTag).with_offset(offset));
+        }
+
+        Ok(EntityTag {
+            weak,
+            tag: tag.to_string(),
+        })
+    }
+
+    /// Returns the opaque tag, without the surrounding double quotes.
+    #[inline]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Determines if this is a weak entity tag.
+    #[inline]
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Compares two entity tags using the strong comparison function.
+    ///
+    /// Two entity tags are strongly equivalent if both are strong and their opaque tags match.
+    #[inline]
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compares two entity tags using the weak comparison function.
+    ///
+    /// Two entity tags are weakly equivalent if their opaque tags match, regardless of either's
+    /// weakness.
+    #[inline]
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+
+    fn parse(c: &mut Cursor) -> Result<EntityTag, Error> {
+        let weak = c.rest().starts_with("W/");
+        if weak {
+            c.set_pos(c.pos() + 2);
+        }
+
+        if !c.eat(b'"') {
+            return Err(c.error(ErrorKind::InvalidEntityTag));
+        }
+        let tag = c.take_while(is_etagc);
+        if !c.eat(b'"') {
+            return Err(c.error(ErrorKind::InvalidEntityTag));
+        }
+
+        Ok(EntityTag {
+            weak,
+            tag: tag.to_string(),
+        })
+    }
+}
+
+fn is_etagc(b: u8) -> bool {
+    b == 0x21 || (0x23..=0x7e).contains(&b)
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.weak {
+            fmt.write_str("W/")?;
+        }
+        write!(fmt, "\"{}\"", self.tag)
+    }
+}
+
+impl FromStr for EntityTag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<EntityTag, Error> {
+        let mut c = Cursor::new(s);
+        let tag = EntityTag::parse(&mut c)?;
+        if c.is_empty() {
+            Ok(tag)
+        } else {
+            Err(c.error(ErrorKind::InvalidEntityTag))
+        }
+    }
+}
+
+/// The value of an `If-Match` or `If-None-Match` header.
+pub(crate) enum TagList {
+    Any,
+    Tags(Vec<EntityTag>),
+}
+
+// Entity tags can contain commas, so we can't use util::parse_comma_delimited.
+pub(crate) fn parse_tag_list(
+    values: &mut header::ValueIter<HeaderValue>,
+) -> Result<Option<TagList>, Error> {
+    let mut tags = vec![];
+    let mut any = false;
+    let mut empty = true;
+    for (index, value) in values.enumerate() {
+        empty = false;
+
+        let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+        if value.trim() == "*" {
+            if any || !tags.is_empty() {
+                return Err(Error::invalid_value().with_index(index));
+            }
+            any = true;
+            continue;
+        }
+        if any {
+            return Err(Error::invalid_value().with_index(index));
+        }
+
+        let mut c = Cursor::new(value);
+        loop {
+            c.skip_empty_elements();
+            if c.is_empty() {
+                break;
+            }
+
+            tags.push(EntityTag::parse(&mut c).map_err(|e| e.with_index(index))?);
+
+            c.ows();
+            if !c.is_empty() && c.peek() != Some(b',') {
+                return Err(c.error(ErrorKind::InvalidEntityTag).with_index(index));
+            }
+        }
+    }
+
+    if empty {
+        Ok(None)
+    } else if any {
+        Ok(Some(TagList::Any))
+    } else if tags.is_empty() {
+        Err(Error::too_few_values())
+    } else {
+        Ok(Some(TagList::Tags(tags)))
+    }
+}
+
+pub(crate) fn encode_tag_list(tags: Option<&[EntityTag]>, values: &mut ToValues) {
+    match tags {
+        Some(tags) => util::encode_comma_delimited(tags, values),
+        None => values.append(HeaderValue::from_static("*")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let tag = "\"xyzzy\"".parse::<EntityTag>().unwrap();
+        assert_eq!(tag, EntityTag::strong("xyzzy").unwrap());
+        assert!(!tag.is_weak());
+
+        let tag = "W/\"xyzzy\"".parse::<EntityTag>().unwrap();
+        assert_eq!(tag, EntityTag::weak("xyzzy").unwrap());
+        assert_eq!(tag.to_string(), "W/\"xyzzy\"");
+
+        assert_eq!("\"\"".parse::<EntityTag>().unwrap().tag(), "");
+        assert_eq!("\"a,b\"".parse::<EntityTag>().unwrap().tag(), "a,b");
+
+        assert!("xyzzy".parse::<EntityTag>().is_err());
+        assert!("w/\"xyzzy\"".parse::<EntityTag>().is_err());
+        assert!("\"xy\"zzy\"".parse::<EntityTag>().is_err());
+        assert!("\"xyzzy".parse::<EntityTag>().is_err());
+        assert!(EntityTag::strong("a\"b").is_err());
+        assert!(EntityTag::strong("a b").is_err());
+    }
+
+    // https://tools.ietf.org/html/rfc7232#section-2.3.2
+    #[test]
+    fn comparison() {
+        let cases = [
+            ("W/\"1\"", "W/\"1\"", false, true),
+            ("W/\"1\"", "W/\"2\"", false, false),
+            ("W/\"1\"", "\"1\"", false, true),
+            ("\"1\"", "\"1\"", true, true),
+        ];
+
+        for &(a, b, strong, weak) in &cases {
+            let a = a.parse::<EntityTag>().unwrap();
+            let b = b.parse::<EntityTag>().unwrap();
+            assert_eq!(a.strong_eq(&b), strong);
+            assert_eq!(b.strong_eq(&a), strong);
+            assert_eq!(a.weak_eq(&b), weak);
+            assert_eq!(b.weak_eq(&a), weak);
+        }
+    }
+}
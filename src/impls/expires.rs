@@ -0,0 +1,127 @@
+use http::header::{HeaderName, HeaderValue, ValueIter, EXPIRES};
+
+use super::HttpDate;
+use crate::{util, Error, Header, ToValues};
+
+/// `Expires` header, defined in [RFC7234](http://tools.ietf.org/html/rfc7234#section-5.3)
+///
+/// The `Expires` header field gives the date/time after which the
+/// response is considered stale.
+///
+/// The presence of an `Expires` field does not imply that the original
+/// resource will change or cease to exist at, before, or after that
+/// time.
+///
+/// A cache recipient MUST interpret invalid date formats, especially the
+/// value "0", as representing a time in the past (i.e., "already
+/// expired").
+///
+/// # ABNF
+///
+/// ```text
+/// Expires = HTTP-date
+/// ```
+///
+/// # Example values
+/// * `Thu, 01 Dec 1994 16:00:00 GMT`
+/// * `0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expires(Option<HttpDate>);
+
+impl Expires {
+    /// Creates an `Expires` header with the specified date.
+    #[inline]
+    pub fn new(date: HttpDate) -> Expires {
+        Expires(Some(date))
+    }
+
+    /// Creates an `Expires` header indicating that the response is already expired.
+    ///
+    /// It is serialized as `0`.
+    #[inline]
+    pub fn expired() -> Expires {
+        Expires(None)
+    }
+
+    /// Returns the expiration date, or `None` if the header contained an invalid date.
+    #[inline]
+    pub fn date(&self) -> Option<&HttpDate> {
+        self.0.as_ref()
+    }
+
+    /// Determines if the response is expired at the specified time.
+    ///
+    /// Invalid dates always represent a time in the past.
+    #[inline]
+    pub fn is_expired_at(&self, now: &HttpDate) -> bool {
+        match self.0 {
+            Some(ref date) => date <= now,
+            None => true,
+        }
+    }
+}
+
+impl From<HttpDate> for Expires {
+    #[inline]
+    fn from(date: HttpDate) -> Expires {
+        Expires::new(date)
+    }
+}
+
+impl Header for Expires {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &EXPIRES
+    }
+
+    #[inline]
+    fn from_values<'a>(values: &mut ValueIter<'a, HeaderValue>) -> Result<Option<Expires>, Error> {
+        let value = match values.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let date = util::to_str(value)
+            .ok()
+            .and_then(|s| s.parse::<HttpDate>().ok());
+        Ok(Some(Expires(date)))
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        match self.0 {
+            Some(ref date) => util::encode_single_value(date, values),
+            None => values.append(HeaderValue::from_static("0")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &Expires::new("Thu, 01 Dec 1994 16:00:00 GMT".parse().unwrap()),
+            &["Thu, 01 Dec 1994 16:00:00 GMT"],
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        util::test_round_trip(&Expires::expired(), &["0"]);
+        util::test_decode(&["-1"], &Expires::expired());
+        util::test_decode(&["Thu, 01 Dec 1994"], &Expires::expired());
+    }
+
+    #[test]
+    fn is_expired_at() {
+        let now = "Thu, 01 Dec 1994 16:00:00 GMT".parse().unwrap();
+        assert!(Expires::expired().is_expired_at(&now));
+        assert!(Expires::new(now.clone()).is_expired_at(&now));
+        assert!(
+            !Expires::new("Thu, 01 Dec 1994 16:00:01 GMT".parse().unwrap()).is_expired_at(&now)
+        );
+    }
+}
@@ -0,0 +1,642 @@
+use http::header::{self, HeaderName, HeaderValue, FORWARDED};
+use http::uri::Scheme;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use super::Host;
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind, Header, ToValues};
+
+/// `Forwarded` header, defined in [RFC7239](https://tools.ietf.org/html/rfc7239#section-4)
+///
+/// The "Forwarded" HTTP header field is an OPTIONAL header field that,
+/// when used, contains a list of parameter-identifier pairs that disclose
+/// information that is altered or lost when a proxy is involved in the
+/// path of the request.
+///
+/// Each proxy appends an element to the list, so the last element was added by the proxy
+/// closest to the recipient.
+///
+/// # ABNF
+///
+/// ```text
+/// Forwarded         = 1#forwarded-element
+///
+/// forwarded-element =
+///     [ forwarded-pair ] *( ";" [ forwarded-pair ] )
+///
+/// forwarded-pair    = token "=" value
+/// value             = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `for="_gazonk"`
+/// * `For="[2001:db8:cafe::17]:4711"`
+/// * `for=192.0.2.60;proto=http;by=203.0.113.43`
+/// * `for=192.0.2.43, for=198.51.100.17`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Forwarded(Vec<ForwardedElement>);
+
+impl Forwarded {
+    /// Creates a new `Forwarded` header.
+    ///
+    /// An error is returned if no elements are provided.
+    #[inline]
+    pub fn new(elements: Vec<ForwardedElement>) -> Result<Forwarded, Error> {
+        if elements.is_empty() {
+            Err(Error::too_few_values())
+        } else {
+            Ok(Forwarded(elements))
+        }
+    }
+
+    /// Appends an element to the header, as a proxy does when forwarding a request.
+    #[inline]
+    pub fn push(&mut self, element: ForwardedElement) {
+        self.0.push(element);
+    }
+}
+
+impl Deref for Forwarded {
+    type Target = Vec<ForwardedElement>;
+
+    #[inline]
+    fn deref(&self) -> &Vec<ForwardedElement> {
+        &self.0
+    }
+}
+
+impl From<ForwardedElement> for Forwarded {
+    #[inline]
+    fn from(element: ForwardedElement) -> Forwarded {
+        Forwarded(vec![element])
+    }
+}
+
+impl Header for Forwarded {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &FORWARDED
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<Forwarded>, Error> {
+        let mut elements = vec![];
+        let mut empty = true;
+        for (index, value) in values.enumerate() {
+            empty = false;
+
+            let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+            parse_elements(value, &mut elements).map_err(|e| e.with_index(index))?;
+        }
+
+        if empty {
+            Ok(None)
+        } else {
+            Forwarded::new(elements).map(Some)
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_comma_delimited(&self.0, values);
+    }
+}
+
+/// The information disclosed by a single proxy in a `Forwarded` header.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ForwardedElement {
+    forwarded_for: Option<ForwardedNode>,
+    by: Option<ForwardedNode>,
+    host: Option<Host>,
+    proto: Option<Scheme>,
+    extensions: Vec<(String, String)>,
+}
+
+impl ForwardedElement {
+    /// Creates an empty element.
+    #[inline]
+    pub fn new() -> ForwardedElement {
+        ForwardedElement::default()
+    }
+
+    /// Sets the `for` parameter, identifying the node making the request to the proxy.
+    #[inline]
+    pub fn with_for(mut self, node: ForwardedNode) -> ForwardedElement {
+        self.forwarded_for = Some(node);
+        self
+    }
+
+    /// Sets the `by` parameter, identifying the interface where the request came in to the proxy.
+    #[inline]
+    pub fn with_by(mut self, node: ForwardedNode) -> ForwardedElement {
+        self.by = Some(node);
+        self
+    }
+
+    /// Sets the `host` parameter, the `Host` header of the request as received by the proxy.
+    #[inline]
+    pub fn with_host(mut self, host: Host) -> ForwardedElement {
+        self.host = Some(host);
+        self
+    }
+
+    /// Sets the `proto` parameter, the protocol used to make the request to the proxy.
+    #[inline]
+    pub fn with_proto(mut self, proto: Scheme) -> ForwardedElement {
+        self.proto = Some(proto);
+        self
+    }
+
+    /// Returns the `for` parameter.
+    #[inline]
+    pub fn forwarded_for(&self) -> Option<&ForwardedNode> {
+        self.forwarded_for.as_ref()
+    }
+
+    /// Returns the `by` parameter.
+    #[inline]
+    pub fn by(&self) -> Option<&ForwardedNode> {
+        self.by.as_ref()
+    }
+
+    /// Returns the `host` parameter.
+    #[inline]
+    pub fn host(&self) -> Option<&Host> {
+        self.host.as_ref()
+    }
+
+    /// Returns the `proto` parameter.
+    #[inline]
+    pub fn proto(&self) -> Option<&Scheme> {
+        self.proto.as_ref()
+    }
+
+    /// Returns the value of an extension parameter.
+    ///
+    /// Parameter names are case-insensitive.
+    pub fn extension(&self, name: &str) -> Option<&str> {
+        self.extensions
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| &**v)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.forwarded_for.is_none()
+            && self.by.is_none()
+            && self.host.is_none()
+            && self.proto.is_none()
+            && self.extensions.is_empty()
+    }
+}
+
+impl fmt::Display for ForwardedElement {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        let mut pair = |name: &str, value: &dyn fmt::Display| {
+            if !first {
+                fmt.write_str(";")?;
+            }
+            first = false;
+            write!(fmt, "{}=", name)?;
+            util::write_token_or_quoted_string(fmt, &value.to_string())
+        };
+
+        if let Some(ref node) = self.forwarded_for {
+            pair("for", node)?;
+        }
+        if let Some(ref node) = self.by {
+            pair("by", node)?;
+        }
+        if let Some(ref host) = self.host {
+            pair("host", host)?;
+        }
+        if let Some(ref proto) = self.proto {
+            pair("proto", proto)?;
+        }
+        for (name, value) in &self.extensions {
+            pair(name, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_elements(s: &str, elements: &mut Vec<ForwardedElement>) -> Result<(), Error> {
+    let mut cursor = Cursor::new(s);
+    loop {
+        cursor.skip_empty_elements();
+        if cursor.is_empty() {
+            return Ok(());
+        }
+
+        let element = parse_element(&mut cursor)?;
+        if !element.is_empty() {
+            elements.push(element);
+        }
+
+        cursor.ows();
+        if !cursor.is_empty() {
+            cursor.expect(b',')?;
+        }
+    }
+}
+
+fn parse_element(cursor: &mut Cursor) -> Result<ForwardedElement, Error> {
+    let mut element = ForwardedElement::new();
+    loop {
+        cursor.ows();
+        if cursor.eat(b';') {
+            continue;
+        }
+        if cursor.is_empty() || cursor.peek() == Some(b',') {
+            return Ok(element);
+        }
+
+        let start = cursor.pos();
+        let name = cursor.token()?.to_ascii_lowercase();
+        cursor.expect(b'=')?;
+        let value_start = cursor.pos();
+        let value = if cursor.peek() == Some(b'"') {
+            cursor.quoted_string()?
+        } else {
+            cursor.token()?.to_string()
+        };
+        let value_error = |e: Error| e.offset_by(value_start);
+
+        let duplicate = match &*name {
+            "for" => element
+                .forwarded_for
+                .replace(value.parse().map_err(value_error)?)
+                .is_some(),
+            "by" => element
+                .by
+                .replace(value.parse().map_err(value_error)?)
+                .is_some(),
+            "host" => element
+                .host
+                .replace(value.parse().map_err(value_error)?)
+                .is_some(),
+            "proto" => element
+                .proto
+                .replace(util::parse_scheme(&value).map_err(value_error)?)
+                .is_some(),
+            _ => {
+                let duplicate = element.extension(&name).is_some();
+                element.extensions.push((name, value));
+                duplicate
+            }
+        };
+        if duplicate {
+            return Err(Error::new(ErrorKind::DuplicateParameter).with_offset(start));
+        }
+    }
+}
+
+/// The name of a node in a `Forwarded` header.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NodeName {
+    /// The IP address of the node.
+    Ip(IpAddr),
+    /// An obfuscated identifier, including its leading `_`.
+    Obfuscated(String),
+    /// The `unknown` identifier, used when the node is not known but the proxy still wants to
+    /// signal that the request was forwarded.
+    Unknown,
+}
+
+/// The port of a node in a `Forwarded` header.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NodePort {
+    /// A port number.
+    Port(u16),
+    /// An obfuscated identifier, including its leading `_`.
+    Obfuscated(String),
+}
+
+/// A node identifier, used in the `for` and `by` parameters of the `Forwarded` header.
+///
+/// # ABNF
+///
+/// ```text
+/// node     = nodename [ ":" node-port ]
+/// nodename = IPv4address / "[" IPv6address "]" /
+///            "unknown" / obfnode
+/// obfnode  = "_" 1*( ALPHA / DIGIT / "." / "_" / "-")
+///
+/// node-port     = port / obfport
+/// port          = 1*5DIGIT
+/// obfport       = "_" 1*(ALPHA / DIGIT / "." / "_" / "-")
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ForwardedNode {
+    name: NodeName,
+    port: Option<NodePort>,
+}
+
+impl ForwardedNode {
+    /// Creates a node identified by an IP address.
+    #[inline]
+    pub fn ip(ip: IpAddr) -> ForwardedNode {
+        ForwardedNode {
+            name: NodeName::Ip(ip),
+            port: None,
+        }
+    }
+
+    /// Creates an `unknown` node.
+    #[inline]
+    pub fn unknown() -> ForwardedNode {
+        ForwardedNode {
+            name: NodeName::Unknown,
+            port: None,
+        }
+    }
+
+    /// Creates a node identified by an obfuscated identifier, like `_hidden`.
+    ///
+    /// An error is returned if the identifier does not start with `_` or contains invalid
+    /// characters.
+    pub fn obfuscated(name: &str) -> Result<ForwardedNode, Error> {
+        if is_obfuscated(name) {
+            Ok(ForwardedNode {
+                name: NodeName::Obfuscated(name.to_string()),
+                port: None,
+            })
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    /// Sets the port of the node.
+    #[inline]
+    pub fn with_port(mut self, port: u16) -> ForwardedNode {
+        self.port = Some(NodePort::Port(port));
+        self
+    }
+
+    /// Sets the port of the node to an obfuscated identifier, like `_port`.
+    ///
+    /// An error is returned if the identifier does not start with `_` or contains invalid
+    /// characters.
+    pub fn with_obfuscated_port(mut self, port: &str) -> Result<ForwardedNode, Error> {
+        if is_obfuscated(port) {
+            self.port = Some(NodePort::Obfuscated(port.to_string()));
+            Ok(self)
+        } else {
+            Err(Error::invalid_value())
+        }
+    }
+
+    /// Returns the name of the node.
+    #[inline]
+    pub fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    /// Returns the IP address of the node, if it is identified by one.
+    #[inline]
+    pub fn ip_addr(&self) -> Option<IpAddr> {
+        match self.name {
+            NodeName::Ip(ip) => Some(ip),
+            _ => None,
+        }
+    }
+
+    /// Returns the port of the node.
+    #[inline]
+    pub fn port(&self) -> Option<&NodePort> {
+        self.port.as_ref()
+    }
+}
+
+impl From<IpAddr> for ForwardedNode {
+    #[inline]
+    fn from(ip: IpAddr) -> ForwardedNode {
+        ForwardedNode::ip(ip)
+    }
+}
+
+impl From<SocketAddr> for ForwardedNode {
+    #[inline]
+    fn from(addr: SocketAddr) -> ForwardedNode {
+        ForwardedNode::ip(addr.ip()).with_port(addr.port())
+    }
+}
+
+impl fmt::Display for ForwardedNode {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            NodeName::Ip(IpAddr::V4(ip)) => write!(fmt, "{}", ip)?,
+            NodeName::Ip(IpAddr::V6(ip)) => write!(fmt, "[{}]", ip)?,
+            NodeName::Obfuscated(ref name) => fmt.write_str(name)?,
+            NodeName::Unknown => fmt.write_str("unknown")?,
+        }
+
+        match self.port {
+            Some(NodePort::Port(port)) => write!(fmt, ":{}", port),
+            Some(NodePort::Obfuscated(ref port)) => write!(fmt, ":{}", port),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ForwardedNode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ForwardedNode, Error> {
+        let (name, port_start) = if s.starts_with('[') {
+            let end = s.find(']').ok_or_else(Error::invalid_value)?;
+            let ip = s[1..end]
+                .parse::<Ipv6Addr>()
+                .map_err(|e| Error::invalid_value().with_offset(1).with_source(e))?;
+            (NodeName::Ip(IpAddr::V6(ip)), end + 1)
+        } else {
+            let end = s.find(':').unwrap_or(s.len());
+            let name = &s[..end];
+            let name = if name.eq_ignore_ascii_case("unknown") {
+                NodeName::Unknown
+            } else if is_obfuscated(name) {
+                NodeName::Obfuscated(name.to_string())
+            } else {
+                let ip = name
+                    .parse::<Ipv4Addr>()
+                    .map_err(|e| Error::invalid_value().with_source(e))?;
+                NodeName::Ip(IpAddr::V4(ip))
+            };
+            (name, end)
+        };
+
+        let port = match &s[port_start..] {
+            "" => None,
+            rest => {
+                let port = rest
+                    .strip_prefix(':')
+                    .ok_or_else(|| Error::invalid_value().with_offset(port_start))?;
+                if is_obfuscated(port) {
+                    Some(NodePort::Obfuscated(port.to_string()))
+                } else if port.len() <= 5 {
+                    let port = util::parse_digits(port)
+                        .ok()
+                        .filter(|&port| port <= u64::from(u16::MAX))
+                        .ok_or_else(|| Error::invalid_value().with_offset(port_start + 1))?;
+                    Some(NodePort::Port(port as u16))
+                } else {
+                    return Err(Error::invalid_value().with_offset(port_start + 1));
+                }
+            }
+        };
+
+        Ok(ForwardedNode { name, port })
+    }
+}
+
+fn is_obfuscated(s: &str) -> bool {
+    s.len() > 1
+        && s.starts_with('_')
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'_' || b == b'-')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HeaderMapExt;
+    use http::HeaderMap;
+
+    fn ip(s: &str) -> ForwardedNode {
+        ForwardedNode::ip(s.parse().unwrap())
+    }
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &Forwarded::from(
+                ForwardedElement::new().with_for(ForwardedNode::obfuscated("_gazonk").unwrap()),
+            ),
+            &["for=_gazonk"],
+        );
+        util::test_decode(
+            &["For=\"[2001:db8:cafe::17]:4711\""],
+            &Forwarded::from(
+                ForwardedElement::new().with_for(ip("2001:db8:cafe::17").with_port(4711)),
+            ),
+        );
+        util::test_round_trip(
+            &Forwarded::from(
+                ForwardedElement::new()
+                    .with_for(ip("192.0.2.60"))
+                    .with_by(ip("203.0.113.43"))
+                    .with_proto(Scheme::HTTP),
+            ),
+            &["for=192.0.2.60;by=203.0.113.43;proto=http"],
+        );
+        util::test_decode(
+            &["for=192.0.2.60;proto=http;by=203.0.113.43"],
+            &Forwarded::from(
+                ForwardedElement::new()
+                    .with_for(ip("192.0.2.60"))
+                    .with_by(ip("203.0.113.43"))
+                    .with_proto(Scheme::HTTP),
+            ),
+        );
+        util::test_round_trip(
+            &Forwarded::new(vec![
+                ForwardedElement::new().with_for(ip("192.0.2.43")),
+                ForwardedElement::new().with_for(ip("198.51.100.17")),
+            ])
+            .unwrap(),
+            &["for=192.0.2.43, for=198.51.100.17"],
+        );
+        util::test_round_trip(
+            &Forwarded::from(
+                ForwardedElement::new()
+                    .with_for(ForwardedNode::unknown())
+                    .with_host(Host::new("example.com", Some(8080)).unwrap()),
+            ),
+            &["for=unknown;host=\"example.com:8080\""],
+        );
+    }
+
+    #[test]
+    fn multiple_values() {
+        let header = Forwarded::new(vec![
+            ForwardedElement::new()
+                .with_for(ip("192.0.2.60"))
+                .with_proto(Scheme::HTTPS)
+                .with_host(Host::new("example.com", None).unwrap()),
+            ForwardedElement::new().with_for(ip("2001:db8::1").with_port(4711)),
+            ForwardedElement::new().with_for(
+                ForwardedNode::obfuscated("_hidden")
+                    .unwrap()
+                    .with_obfuscated_port("_port")
+                    .unwrap(),
+            ),
+        ])
+        .unwrap();
+        util::test_decode(
+            &[
+                "for=192.0.2.60 ; Proto=HTTPS; host=example.com,",
+                ", for=\"[2001:db8::1]:4711\"",
+                "for=\"_hidden:_port\"",
+            ],
+            &header,
+        );
+
+        let node = header[1].forwarded_for().unwrap();
+        assert_eq!(node.ip_addr(), Some("2001:db8::1".parse().unwrap()));
+        assert_eq!(node.port(), Some(&NodePort::Port(4711)));
+    }
+
+    #[test]
+    fn extensions() {
+        let mut map = HeaderMap::new();
+        map.insert(
+            FORWARDED,
+            HeaderValue::from_static("for=unknown;Secret=\"a b\";x=y"),
+        );
+        let header = map.typed_get::<Forwarded>().unwrap().unwrap();
+        assert_eq!(header[0].extension("secret"), Some("a b"));
+        assert_eq!(header[0].extension("X"), Some("y"));
+        util::test_encode(&header, &["for=unknown;secret=\"a b\";x=y"]);
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "for=2001:db8::1",
+            "for=\"2001:db8::1\"",
+            "for=\"[2001:db8::1\"",
+            "for=\"[2001:db8::1]4711\"",
+            "for=192.0.2.256",
+            "for=\"192.0.2.60:65536\"",
+            "for=\"192.0.2.60:\"",
+            "for=_",
+            "for=example.com",
+            "for=unknown;for=unknown",
+            "for=unknown;FOR=unknown",
+            "x=1;x=2",
+            "host=\"user@example.com\"",
+            "proto=\"ht tp\"",
+            "proto=1http",
+            "for",
+            "for=\"unterminated",
+            "for=unknown for=unknown",
+        ];
+        for value in &cases {
+            let mut map = HeaderMap::new();
+            map.insert(FORWARDED, HeaderValue::from_static(value));
+            assert!(map.typed_get::<Forwarded>().is_err(), "{}", value);
+        }
+
+        let mut map = HeaderMap::new();
+        map.insert(FORWARDED, HeaderValue::from_static(""));
+        let err = map.typed_get::<Forwarded>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TooFewValues);
+    }
+}
@@ -1,8 +1,9 @@
-use bytes::Bytes;
 use http::header::{self, HeaderName, HeaderValue, HOST};
 use http::uri::Authority;
+use std::fmt;
+use std::str::FromStr;
 
-use crate::{Error, Header, ToValues};
+use crate::{util, Error, ErrorKind, Header, ToValues};
 
 /// The `Host` header, defined in [RFC7230].
 ///
@@ -18,7 +19,7 @@ use crate::{Error, Header, ToValues};
 /// ```
 ///
 /// [RFC7230]: https://tools.ietf.org/html/rfc7230#section-5.4
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Host {
     host: String,
     port: Option<u16>,
@@ -30,10 +31,9 @@ impl Host {
     pub fn new(host: &'static str, port: Option<u16>) -> Result<Host, Error> {
         // go through authority to validate the hostname
         let authority = match port {
-            Some(port) => Bytes::from(format!("{}:{}", host, port)),
-            None => Bytes::from(host),
+            Some(port) => parse_authority(&format!("{}:{}", host, port))?,
+            None => parse_authority(host)?,
         };
-        let authority = Authority::from_maybe_shared(authority).map_err(|_| Error::invalid_value())?;
 
         Ok(Host::from_authority(&authority))
     }
@@ -62,6 +62,36 @@ impl Host {
     }
 }
 
+impl fmt::Display for Host {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.host)?;
+        if let Some(port) = self.port {
+            write!(fmt, ":{}", port)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Host {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Host, Error> {
+        parse_authority(s).map(|authority| Host::from_authority(&authority))
+    }
+}
+
+/// Parses a URI authority component, rejecting any userinfo.
+pub(crate) fn parse_authority(s: &str) -> Result<Authority, Error> {
+    let authority = s
+        .parse::<Authority>()
+        .map_err(|e| Error::new(ErrorKind::InvalidUri).with_source(e))?;
+    if let Some(offset) = authority.as_str().find('@') {
+        return Err(Error::new(ErrorKind::InvalidUri).with_offset(offset));
+    }
+
+    Ok(authority)
+}
+
 impl Header for Host {
     #[inline]
     fn name() -> &'static HeaderName {
@@ -72,29 +102,32 @@ impl Header for Host {
     fn from_values<'a>(
         values: &mut header::ValueIter<'a, HeaderValue>,
     ) -> Result<Option<Host>, Error> {
-        let value = match values.next() {
-            Some(value) => value,
-            None => return Ok(None),
-        };
-
-        let authority = Authority::from_maybe_shared(Bytes::copy_from_slice(value.as_bytes()))
-            .map_err(|_| Error::invalid_value())?;
-        // host header can't contain userinfo
-        if authority.as_str().contains('@') {
-            return Err(Error::invalid_value());
-        }
-
-        Ok(Some(Host::from_authority(&authority)))
+        util::parse_single_value(values)
     }
 
     #[inline]
     fn to_values(&self, values: &mut ToValues) {
-        let value = match self.port {
-            Some(port) => HeaderValue::from_str(&format!("{}:{}", self.host, port)),
-            None => HeaderValue::from_str(&self.host),
-        };
-        let value = value.expect("should have already validated contents");
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&Host::new("example.com", None).unwrap(), &["example.com"]);
+        util::test_round_trip(
+            &Host::new("example.com", Some(8080)).unwrap(),
+            &["example.com:8080"],
+        );
+        util::test_round_trip(&Host::new("[::1]", Some(80)).unwrap(), &["[::1]:80"]);
+    }
 
-        values.append(value);
+    #[test]
+    fn invalid() {
+        assert!("user@example.com".parse::<Host>().is_err());
+        assert!("example.com/foo".parse::<Host>().is_err());
     }
 }
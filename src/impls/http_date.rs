@@ -1,21 +1,30 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SubsecRound, Utc};
 use std::fmt;
 use std::str::FromStr;
 use std::time::SystemTime;
 
-use crate::Error;
+use crate::{Error, ErrorKind};
 
+#[allow(clippy::redundant_static_lifetimes)]
 const IMF_FIXDATE_PATTERN: &'static str = "%a, %d %b %Y %T GMT";
+#[allow(clippy::redundant_static_lifetimes)]
 const RFC850_DATE_PATTERN: &'static str = "%A, %d-%b-%y %T GMT";
+#[allow(clippy::redundant_static_lifetimes)]
 const ASCTIME_DATE_PATTERN: &'static str = "%a %b %e %T %Y";
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A timestamp, formatted as defined in [RFC7231].
+///
+/// HTTP dates have a resolution of one second. Any fractional part of a `SystemTime` is
+/// discarded on conversion, so comparisons between dates are at second granularity.
+///
+/// [RFC7231]: https://tools.ietf.org/html/rfc7231#section-7.1.1.1
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct HttpDate(DateTime<Utc>);
 
 impl From<SystemTime> for HttpDate {
     #[inline]
     fn from(t: SystemTime) -> HttpDate {
-        HttpDate(DateTime::from(t))
+        HttpDate(DateTime::<Utc>::from(t).trunc_subsecs(0))
     }
 }
 
@@ -33,9 +42,9 @@ impl FromStr for HttpDate {
         let naive = NaiveDateTime::parse_from_str(s, IMF_FIXDATE_PATTERN)
             .or_else(|_| NaiveDateTime::parse_from_str(s, RFC850_DATE_PATTERN))
             .or_else(|_| NaiveDateTime::parse_from_str(s, ASCTIME_DATE_PATTERN))
-            .map_err(|_| Error::invalid_value())?;
+            .map_err(|e| Error::new(ErrorKind::InvalidDate).with_source(e))?;
 
-        Ok(HttpDate(DateTime::from_utc(naive, Utc)))
+        Ok(HttpDate(DateTime::from_naive_utc_and_offset(naive, Utc)))
     }
 }
 
@@ -44,3 +53,145 @@ impl fmt::Display for HttpDate {
         fmt::Display::fmt(&self.0.format(IMF_FIXDATE_PATTERN), fmt)
     }
 }
+
+impl HttpDate {
+    /// Parses a date using the lenient algorithm for cookie dates defined in [RFC6265].
+    ///
+    /// [RFC6265]: https://tools.ietf.org/html/rfc6265#section-5.1.1
+    pub(crate) fn parse_cookie_date(s: &str) -> Option<HttpDate> {
+        let mut time = None;
+        let mut day = None;
+        let mut month = None;
+        let mut year = None;
+
+        for token in s.split(is_cookie_date_delimiter).filter(|t| !t.is_empty()) {
+            if time.is_none() {
+                if let Some(t) = parse_cookie_time(token) {
+                    time = Some(t);
+                    continue;
+                }
+            }
+            if day.is_none() {
+                if let Some(d) = leading_digits(token, 1, 2) {
+                    day = Some(d);
+                    continue;
+                }
+            }
+            if month.is_none() {
+                if let Some(m) = parse_cookie_month(token) {
+                    month = Some(m);
+                    continue;
+                }
+            }
+            if year.is_none() {
+                if let Some(y) = leading_digits(token, 2, 4) {
+                    year = Some(y);
+                    continue;
+                }
+            }
+        }
+
+        let (hour, minute, second) = time?;
+        let year = match year? {
+            y @ 70..=99 => y + 1900,
+            y @ 0..=69 => y + 2000,
+            y => y,
+        };
+        if year < 1601 || hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+
+        let naive = NaiveDate::from_ymd_opt(year as i32, month?, day?)?
+            .and_hms_opt(hour, minute, second)?;
+        Some(HttpDate(DateTime::from_naive_utc_and_offset(naive, Utc)))
+    }
+}
+
+fn is_cookie_date_delimiter(c: char) -> bool {
+    matches!(c, '\t' | ' '..='/' | ';'..='@' | '['..='`' | '{'..='~')
+}
+
+// Parses 1*2DIGIT etc. at the start of a token, which must be followed by a non-digit or the end.
+fn leading_digits(token: &str, min: usize, max: usize) -> Option<u32> {
+    let len = token.bytes().take_while(|b| b.is_ascii_digit()).count();
+    if len < min || len > max {
+        return None;
+    }
+    token[..len].parse().ok()
+}
+
+fn parse_cookie_time(token: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = token.splitn(3, ':');
+    let hour = parts.next()?;
+    let minute = parts.next()?;
+    let second = parts.next()?;
+
+    if !(1..=2).contains(&hour.len()) || !(1..=2).contains(&minute.len()) {
+        return None;
+    }
+    let hour = leading_digits(hour, 1, 2)?;
+    let minute = leading_digits(minute, 1, 2)?;
+    let second = leading_digits(second, 1, 2)?;
+    Some((hour, minute, second))
+}
+
+fn parse_cookie_month(token: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+
+    let prefix = token.get(..3)?;
+    MONTHS
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(prefix))
+        .map(|i| i as u32 + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn second_granularity() {
+        let a = HttpDate::from(UNIX_EPOCH + Duration::from_millis(1500));
+        let b = HttpDate::from(UNIX_EPOCH + Duration::from_millis(1999));
+        let c = HttpDate::from(UNIX_EPOCH + Duration::from_millis(2000));
+        assert_eq!(a, b);
+        assert!(b < c);
+        assert_eq!(a, "Thu, 01 Jan 1970 00:00:01 GMT".parse().unwrap());
+        assert_eq!(SystemTime::from(a), UNIX_EPOCH + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn cookie_date() {
+        let expected = "Wed, 21 Oct 2015 07:28:00 GMT".parse::<HttpDate>().unwrap();
+        let cases = [
+            "Wed, 21 Oct 2015 07:28:00 GMT",
+            "Wed, 21-Oct-2015 07:28:00 GMT",
+            "Wednesday, 21-Oct-15 07:28:00 GMT",
+            "Wed Oct 21 07:28:00 2015",
+            "21 October 2015 7:28:0",
+        ];
+        for s in &cases {
+            assert_eq!(
+                HttpDate::parse_cookie_date(s),
+                Some(expected.clone()),
+                "{}",
+                s
+            );
+        }
+
+        let invalid = [
+            "Wed, 21 Oct 2015",
+            "Wed, 32 Oct 2015 07:28:00 GMT",
+            "Wed, 21 Foo 2015 07:28:00 GMT",
+            "Wed, 21 Oct 1600 07:28:00 GMT",
+            "Wed, 21 Oct 2015 24:28:00 GMT",
+            "Wed, 31 Feb 2015 07:28:00 GMT",
+        ];
+        for s in &invalid {
+            assert_eq!(HttpDate::parse_cookie_date(s), None, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use http::header::{self, HeaderName, HeaderValue, IF_MATCH};
+
+use super::entity_tag::{self, TagList};
+use super::EntityTag;
+use crate::{Error, Header, ToValues};
+
+/// `If-Match` header, defined in
+/// [RFC7232](https://tools.ietf.org/html/rfc7232#section-3.1)
+///
+/// The `If-Match` header field makes the request method conditional on
+/// the recipient origin server either having at least one current
+/// representation of the target resource, when the field-value is "*",
+/// or having a current representation of the target resource that has an
+/// entity-tag matching a member of the list of entity-tags provided in
+/// the field-value.
+///
+/// An origin server MUST use the strong comparison function when
+/// comparing entity-tags for `If-Match`, since the client
+/// intends this precondition to prevent the method from being applied if
+/// there have been any changes to the representation data.
+///
+/// # ABNF
+///
+/// ```text
+/// If-Match = "*" / 1#entity-tag
+/// ```
+///
+/// # Example values
+///
+/// * `"xyzzy"`
+/// * `"xyzzy", "r2d2xxxx", "c3piozzzz"`
+/// * `*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfMatch {
+    /// Matches any current representation.
+    Any,
+    /// Matches representations with one of the listed entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfMatch {
+    /// Determines if an entity tag matches the header using the strong comparison function.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match *self {
+            IfMatch::Any => true,
+            IfMatch::Tags(ref tags) => tags.iter().any(|tag| tag.strong_eq(etag)),
+        }
+    }
+}
+
+impl Header for IfMatch {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &IF_MATCH
+    }
+
+    #[inline]
+    fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<IfMatch>, Error> {
+        let header = entity_tag::parse_tag_list(values)?.map(|tags| match tags {
+            TagList::Any => IfMatch::Any,
+            TagList::Tags(tags) => IfMatch::Tags(tags),
+        });
+        Ok(header)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        let tags = match *self {
+            IfMatch::Any => None,
+            IfMatch::Tags(ref tags) => Some(&**tags),
+        };
+        entity_tag::encode_tag_list(tags, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &IfMatch::Tags(vec![EntityTag::strong("xyzzy").unwrap()]),
+            &["\"xyzzy\""],
+        );
+        util::test_round_trip(
+            &IfMatch::Tags(vec![
+                EntityTag::strong("xyzzy").unwrap(),
+                EntityTag::strong("r2d2xxxx").unwrap(),
+                EntityTag::strong("c3piozzzz").unwrap(),
+            ]),
+            &["\"xyzzy\", \"r2d2xxxx\", \"c3piozzzz\""],
+        );
+        util::test_round_trip(&IfMatch::Any, &["*"]);
+    }
+
+    #[test]
+    fn decode() {
+        util::test_decode(
+            &["\"a,b\" ,, W/\"c\"", "\"d\""],
+            &IfMatch::Tags(vec![
+                EntityTag::strong("a,b").unwrap(),
+                EntityTag::weak("c").unwrap(),
+                EntityTag::strong("d").unwrap(),
+            ]),
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let header = IfMatch::Tags(vec![
+            EntityTag::strong("a").unwrap(),
+            EntityTag::weak("b").unwrap(),
+        ]);
+        assert!(header.matches(&EntityTag::strong("a").unwrap()));
+        assert!(!header.matches(&EntityTag::weak("a").unwrap()));
+        assert!(!header.matches(&EntityTag::strong("b").unwrap()));
+        assert!(IfMatch::Any.matches(&EntityTag::weak("c").unwrap()));
+    }
+}
@@ -0,0 +1,127 @@
+use http::header::{self, HeaderName, HeaderValue, IF_NONE_MATCH};
+
+use super::entity_tag::{self, TagList};
+use super::EntityTag;
+use crate::{Error, Header, ToValues};
+
+/// `If-None-Match` header, defined in
+/// [RFC7232](https://tools.ietf.org/html/rfc7232#section-3.2)
+///
+/// The `If-None-Match` header field makes the request method conditional
+/// on a recipient cache or origin server either not having any current
+/// representation of the target resource, when the field-value is "*",
+/// or having a selected representation with an entity-tag that does not
+/// match any of those listed in the field-value.
+///
+/// A recipient MUST use the weak comparison function when comparing
+/// entity-tags for `If-None-Match`, since weak entity-tags
+/// can be used for cache validation even if there have been changes to
+/// the representation data.
+///
+/// # ABNF
+///
+/// ```text
+/// If-None-Match = "*" / 1#entity-tag
+/// ```
+///
+/// # Example values
+///
+/// * `"xyzzy"`
+/// * `W/"xyzzy"`
+/// * `"xyzzy", "r2d2xxxx", "c3piozzzz"`
+/// * `W/"xyzzy", W/"r2d2xxxx", W/"c3piozzzz"`
+/// * `*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatch {
+    /// Matches any current representation.
+    Any,
+    /// Matches representations with one of the listed entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfNoneMatch {
+    /// Determines if an entity tag matches the header using the weak comparison function.
+    ///
+    /// Note that the request's precondition passes only if this returns `false`.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match *self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(ref tags) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        }
+    }
+}
+
+impl Header for IfNoneMatch {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &IF_NONE_MATCH
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<IfNoneMatch>, Error> {
+        let header = entity_tag::parse_tag_list(values)?.map(|tags| match tags {
+            TagList::Any => IfNoneMatch::Any,
+            TagList::Tags(tags) => IfNoneMatch::Tags(tags),
+        });
+        Ok(header)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        let tags = match *self {
+            IfNoneMatch::Any => None,
+            IfNoneMatch::Tags(ref tags) => Some(&**tags),
+        };
+        entity_tag::encode_tag_list(tags, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{util, ErrorKind, HeaderMapExt};
+    use http::HeaderMap;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &IfNoneMatch::Tags(vec![
+                EntityTag::weak("xyzzy").unwrap(),
+                EntityTag::weak("r2d2xxxx").unwrap(),
+                EntityTag::weak("c3piozzzz").unwrap(),
+            ]),
+            &["W/\"xyzzy\", W/\"r2d2xxxx\", W/\"c3piozzzz\""],
+        );
+        util::test_round_trip(&IfNoneMatch::Any, &["*"]);
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            (&["*", "\"a\""][..], ErrorKind::InvalidValue),
+            (&["\"a\", *"][..], ErrorKind::InvalidEntityTag),
+            (&["\"a\" \"b\""][..], ErrorKind::InvalidEntityTag),
+            (&["a"][..], ErrorKind::InvalidEntityTag),
+            (&[","][..], ErrorKind::TooFewValues),
+        ];
+
+        for &(values, kind) in &cases {
+            let mut map = HeaderMap::new();
+            for value in values {
+                map.append(IF_NONE_MATCH, value.parse().unwrap());
+            }
+            let err = map.typed_get::<IfNoneMatch>().unwrap_err();
+            assert_eq!(err.kind(), kind, "{:?}", values);
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let header = IfNoneMatch::Tags(vec![EntityTag::weak("a").unwrap()]);
+        assert!(header.matches(&EntityTag::strong("a").unwrap()));
+        assert!(header.matches(&EntityTag::weak("a").unwrap()));
+        assert!(!header.matches(&EntityTag::weak("b").unwrap()));
+    }
+}
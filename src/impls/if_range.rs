@@ -0,0 +1,91 @@
+use http::header::{HeaderName, HeaderValue, ValueIter, IF_RANGE};
+
+use super::{EntityTag, HttpDate};
+use crate::{util, Error, Header, ToValues};
+
+/// `If-Range` header, defined in [RFC7233](http://tools.ietf.org/html/rfc7233#section-3.2)
+///
+/// If a client has a partial copy of a representation and wishes to have
+/// an up-to-date copy of the entire representation, it could use the
+/// Range header field with a conditional GET (using either or both of
+/// If-Unmodified-Since and If-Match.)  However, if the precondition
+/// fails because the representation has been modified, the client would
+/// then have to make a second request to obtain the entire current
+/// representation.
+///
+/// The `If-Range` header field allows a client to "short-circuit" the
+/// second request.  Informally, its meaning is as follows: if the
+/// representation is unchanged, send me the part(s) that I am requesting
+/// in Range; otherwise, send me the entire representation.
+///
+/// # ABNF
+///
+/// ```text
+/// If-Range = entity-tag / HTTP-date
+/// ```
+///
+/// # Example values
+///
+/// * `Sat, 29 Oct 1994 19:43:31 GMT`
+/// * `"xyzzy"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfRange {
+    /// The entity tag of the client's partial copy.
+    EntityTag(EntityTag),
+    /// The modification date of the client's partial copy.
+    HttpDate(HttpDate),
+}
+
+impl Header for IfRange {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &IF_RANGE
+    }
+
+    #[inline]
+    fn from_values<'a>(values: &mut ValueIter<'a, HeaderValue>) -> Result<Option<IfRange>, Error> {
+        let value = match values.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let value = util::to_str(value).map_err(|e| e.with_index(0))?;
+
+        if value.starts_with('"') || value.starts_with("W/") {
+            value
+                .parse()
+                .map(|tag| Some(IfRange::EntityTag(tag)))
+                .map_err(|e: Error| e.with_index(0))
+        } else {
+            value
+                .parse()
+                .map(|date| Some(IfRange::HttpDate(date)))
+                .map_err(|e: Error| e.with_index(0))
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        match *self {
+            IfRange::EntityTag(ref tag) => util::encode_single_value(tag, values),
+            IfRange::HttpDate(ref date) => util::encode_single_value(date, values),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &IfRange::HttpDate("Sat, 29 Oct 1994 19:43:31 GMT".parse().unwrap()),
+            &["Sat, 29 Oct 1994 19:43:31 GMT"],
+        );
+        util::test_round_trip(
+            &IfRange::EntityTag(EntityTag::strong("xyzzy").unwrap()),
+            &["\"xyzzy\""],
+        );
+    }
+}
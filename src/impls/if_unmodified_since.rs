@@ -0,0 +1,38 @@
+use http::header::IF_UNMODIFIED_SINCE;
+
+use super::HttpDate;
+
+header! {
+    /// `If-Unmodified-Since` header, defined in
+    /// [RFC7232](http://tools.ietf.org/html/rfc7232#section-3.4)
+    ///
+    /// The `If-Unmodified-Since` header field makes the request method
+    /// conditional on the selected representation's last modification date
+    /// being earlier than or equal to the date provided in the field-value.
+    /// This field accomplishes the same purpose as If-Match for cases where
+    /// the user agent does not have an entity-tag for the representation.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// If-Unmodified-Since = HTTP-date
+    /// ```
+    ///
+    /// # Example values
+    /// * `Sat, 29 Oct 1994 19:43:31 GMT`
+    (IfUnmodifiedSince, IF_UNMODIFIED_SINCE) => [HttpDate]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &IfUnmodifiedSince("Sat, 29 Oct 1994 19:43:31 GMT".parse().unwrap()),
+            &["Sat, 29 Oct 1994 19:43:31 GMT"],
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::LanguageTag;
+use crate::{Error, ErrorKind};
+
+/// A basic language range, as defined in [RFC4647].
+///
+/// Language ranges are used in the `Accept-Language` header to identify sets of language tags.
+///
+/// [RFC4647]: https://tools.ietf.org/html/rfc4647#section-2.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageRange(String);
+
+impl LanguageRange {
+    /// Constructs a new language range.
+    ///
+    /// An error is returned if the string is not a well-formed basic language range.
+    pub fn new(s: &str) -> Result<LanguageRange, Error> {
+        if s == "*" {
+            return Ok(LanguageRange::star());
+        }
+
+        let mut offset = 0;
+        for (i, subtag) in s.split('-').enumerate() {
+            let valid = !subtag.is_empty()
+                && subtag.len() <= 8
+                && if i == 0 {
+                    subtag.bytes().all(|b| b.is_ascii_alphabetic())
+                } else {
+                    subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+                };
+            if !valid {
+                return Err(Error::new(ErrorKind::InvalidLanguageTag).with_offset(offset));
+            }
+            offset += subtag.len() + 1;
+        }
+
+        Ok(LanguageRange(s.to_string()))
+    }
+
+    /// Returns the wildcard language range, which matches all language tags.
+    #[inline]
+    pub fn star() -> LanguageRange {
+        LanguageRange("*".to_string())
+    }
+
+    /// Determines if this is the wildcard language range.
+    #[inline]
+    pub fn is_star(&self) -> bool {
+        self.0 == "*"
+    }
+
+    /// Returns the string representation of this range.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Determines if the range matches a language tag according to the basic filtering scheme of
+    /// [RFC4647].
+    ///
+    /// A range matches a tag if it is equal to the tag or to a prefix of the tag which is
+    /// followed by a `-`, ignoring case. The wildcard range matches all tags.
+    ///
+    /// [RFC4647]: https://tools.ietf.org/html/rfc4647#section-3.3.1
+    pub fn matches(&self, tag: &LanguageTag) -> bool {
+        if self.is_star() {
+            return true;
+        }
+
+        let tag = tag.as_str();
+        match tag.get(..self.0.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(&self.0) => {
+                tag.len() == self.0.len() || tag.as_bytes()[self.0.len()] == b'-'
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<LanguageTag> for LanguageRange {
+    #[inline]
+    fn from(tag: LanguageTag) -> LanguageRange {
+        LanguageRange(tag.0)
+    }
+}
+
+impl fmt::Display for LanguageRange {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+
+impl FromStr for LanguageRange {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<LanguageRange, Error> {
+        LanguageRange::new(s)
+    }
+}
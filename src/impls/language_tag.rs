@@ -0,0 +1,289 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, ErrorKind};
+
+// Tags registered before RFC4646 which do not match the langtag production.
+const GRANDFATHERED: &[&str] = &[
+    "en-gb-oed",
+    "i-ami",
+    "i-bnn",
+    "i-default",
+    "i-enochian",
+    "i-hak",
+    "i-klingon",
+    "i-lux",
+    "i-mingo",
+    "i-navajo",
+    "i-pwn",
+    "i-tao",
+    "i-tay",
+    "i-tsu",
+    "sgn-be-fr",
+    "sgn-be-nl",
+    "sgn-ch-de",
+    "art-lojban",
+    "cel-gaulish",
+    "no-bok",
+    "no-nyn",
+    "zh-guoyu",
+    "zh-hakka",
+    "zh-min",
+    "zh-min-nan",
+    "zh-xiang",
+];
+
+/// A language tag, as defined in [BCP47].
+///
+/// Language tags are case-insensitive. They are normalized to the canonical case described in
+/// [RFC5646] when constructed: region subtags are uppercased, script subtags are titlecased, and
+/// all other subtags are lowercased.
+///
+/// [BCP47]: https://tools.ietf.org/html/bcp47
+/// [RFC5646]: https://tools.ietf.org/html/rfc5646#section-2.1.1
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageTag(pub(crate) String);
+
+impl LanguageTag {
+    /// Constructs a new language tag.
+    ///
+    /// An error is returned if the string is not a well-formed language tag.
+    pub fn new(s: &str) -> Result<LanguageTag, Error> {
+        let lower = s.to_ascii_lowercase();
+        if !GRANDFATHERED.contains(&&*lower) {
+            validate(&lower)?;
+        }
+
+        Ok(LanguageTag(canonicalize(&lower)))
+    }
+
+    /// Returns the string representation of this tag.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the primary language subtag.
+    ///
+    /// This is `x` for private use tags and `i` for some grandfathered tags.
+    #[inline]
+    pub fn primary_language(&self) -> &str {
+        self.0.split('-').next().unwrap()
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<LanguageTag, Error> {
+        LanguageTag::new(s)
+    }
+}
+
+fn is_alpha(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_digit(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+// Validates a lowercased tag against the langtag and privateuse productions.
+fn validate(s: &str) -> Result<(), Error> {
+    let mut subtags = vec![];
+    let mut offset = 0;
+    for subtag in s.split('-') {
+        if subtag.is_empty()
+            || subtag.len() > 8
+            || !subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(Error::new(ErrorKind::InvalidLanguageTag).with_offset(offset));
+        }
+        subtags.push((offset, subtag));
+        offset += subtag.len() + 1;
+    }
+
+    let mut it = subtags.iter().cloned().peekable();
+    let error = |offset| Error::new(ErrorKind::InvalidLanguageTag).with_offset(offset);
+
+    // language
+    let (_, language) = it.next().unwrap();
+    if language == "x" {
+        return if it.next().is_some() {
+            Ok(())
+        } else {
+            Err(error(s.len()))
+        };
+    }
+    if language.len() < 2 || !is_alpha(language) {
+        return Err(error(0));
+    }
+
+    // extlang
+    if language.len() <= 3 {
+        for _ in 0..3 {
+            match it.peek() {
+                Some(&(_, s)) if s.len() == 3 && is_alpha(s) => {
+                    it.next();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    // script
+    if let Some(&(_, s)) = it.peek() {
+        if s.len() == 4 && is_alpha(s) {
+            it.next();
+        }
+    }
+
+    // region
+    if let Some(&(_, s)) = it.peek() {
+        if (s.len() == 2 && is_alpha(s)) || (s.len() == 3 && is_digit(s)) {
+            it.next();
+        }
+    }
+
+    // variants
+    let mut variants: Vec<&str> = vec![];
+    while let Some(&(offset, s)) = it.peek() {
+        let variant = s.len() >= 5 || (s.len() == 4 && s.as_bytes()[0].is_ascii_digit());
+        if !variant {
+            break;
+        }
+        if variants.contains(&s) {
+            return Err(error(offset));
+        }
+        variants.push(s);
+        it.next();
+    }
+
+    // extensions
+    let mut singletons = vec![];
+    while let Some(&(offset, s)) = it.peek() {
+        if s.len() != 1 || s == "x" {
+            break;
+        }
+        if singletons.contains(&s) {
+            return Err(error(offset));
+        }
+        singletons.push(s);
+        it.next();
+
+        let mut empty = true;
+        while let Some(&(_, s)) = it.peek() {
+            if s.len() < 2 {
+                break;
+            }
+            empty = false;
+            it.next();
+        }
+        if empty {
+            return Err(error(offset));
+        }
+    }
+
+    // privateuse
+    if let Some((offset, s)) = it.next() {
+        if s != "x" || it.next().is_none() {
+            return Err(error(offset));
+        }
+        // any remaining subtags are private use, which have already been validated
+        return Ok(());
+    }
+
+    Ok(())
+}
+
+fn canonicalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut after_singleton = false;
+    for (i, subtag) in s.split('-').enumerate() {
+        if i > 0 {
+            out.push('-');
+        }
+
+        if i == 0 || after_singleton {
+            out.push_str(subtag);
+        } else if subtag.len() == 2 {
+            out.push_str(&subtag.to_ascii_uppercase());
+        } else if subtag.len() == 4 && is_alpha(subtag) {
+            out.push_str(&subtag[..1].to_ascii_uppercase());
+            out.push_str(&subtag[1..]);
+        } else {
+            out.push_str(subtag);
+        }
+
+        if subtag.len() == 1 {
+            after_singleton = true;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let cases = [
+            ("de", "de"),
+            ("EN-us", "en-US"),
+            ("zh-hant-tw", "zh-Hant-TW"),
+            ("zh-yue-HK", "zh-yue-HK"),
+            ("sr-Latn-RS", "sr-Latn-RS"),
+            ("es-419", "es-419"),
+            ("sl-rozaj-biske", "sl-rozaj-biske"),
+            ("de-CH-1901", "de-CH-1901"),
+            ("en-US-u-islamcal", "en-US-u-islamcal"),
+            ("en-a-myext-b-another", "en-a-myext-b-another"),
+            ("de-CH-x-phonebk", "de-CH-x-phonebk"),
+            ("az-Arab-x-AZE-derbend", "az-Arab-x-aze-derbend"),
+            ("x-whatever", "x-whatever"),
+            ("i-klingon", "i-klingon"),
+            ("en-gb-oed", "en-GB-oed"),
+        ];
+
+        for &(tag, canonical) in &cases {
+            assert_eq!(
+                LanguageTag::new(tag).unwrap().as_str(),
+                canonical,
+                "{}",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "",
+            "a",
+            "de-",
+            "-de",
+            "en--US",
+            "abcdefghi",
+            "de-419-DE",
+            "a-DE",
+            "ar-a-aaa-b-bbb-a-ccc",
+            "en-a",
+            "de-1996-1996",
+            "x",
+            "en-x",
+            "en_US",
+        ];
+
+        for tag in &cases {
+            assert!(LanguageTag::new(tag).is_err(), "{}", tag);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use http::header::LAST_MODIFIED;
+
+use super::HttpDate;
+
+header! {
+    /// `Last-Modified` header, defined in
+    /// [RFC7232](http://tools.ietf.org/html/rfc7232#section-2.2)
+    ///
+    /// The `Last-Modified` header field in a response provides a timestamp
+    /// indicating the date and time at which the origin server believes the
+    /// selected representation was last modified, as determined at the
+    /// conclusion of handling the request.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Last-Modified = HTTP-date
+    /// ```
+    ///
+    /// # Example values
+    /// * `Tue, 15 Nov 1994 12:45:26 GMT`
+    (LastModified, LAST_MODIFIED) => [HttpDate]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &LastModified("Tue, 15 Nov 1994 12:45:26 GMT".parse().unwrap()),
+            &["Tue, 15 Nov 1994 12:45:26 GMT"],
+        );
+    }
+}
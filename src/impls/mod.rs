@@ -1,19 +1,76 @@
 pub use self::accept::Accept;
+pub use self::accept_charset::AcceptCharset;
 pub use self::accept_encoding::AcceptEncoding;
+pub use self::accept_language::AcceptLanguage;
+pub use self::accept_ranges::AcceptRanges;
+pub use self::access_control_allow_credentials::AccessControlAllowCredentials;
+pub use self::access_control_allow_headers::AccessControlAllowHeaders;
+pub use self::access_control_allow_methods::AccessControlAllowMethods;
+pub use self::access_control_allow_origin::AccessControlAllowOrigin;
+pub use self::access_control_expose_headers::AccessControlExposeHeaders;
+pub use self::access_control_max_age::AccessControlMaxAge;
+pub use self::access_control_request_headers::AccessControlRequestHeaders;
+pub use self::access_control_request_method::AccessControlRequestMethod;
+pub use self::age::Age;
 pub use self::allow::Allow;
+pub use self::auth_param::AuthParam;
 pub use self::auth_scheme::AuthScheme;
 pub use self::authorization::Authorization;
+pub use self::cache_control::{CacheControl, CacheDirective, CacheExtension};
+pub use self::challenge::Challenge;
+pub use self::charset::Charset;
 pub use self::content_coding::ContentCoding;
 pub use self::content_encoding::ContentEncoding;
+pub use self::content_language::ContentLanguage;
 pub use self::content_length::ContentLength;
+pub use self::content_range::ContentRange;
+pub use self::content_security_policy::{
+    ContentSecurityPolicy, CspDirective, CspHashAlgorithm, CspPolicy, CspSource,
+};
+pub use self::content_security_policy_report_only::ContentSecurityPolicyReportOnly;
 pub use self::content_type::ContentType;
+pub use self::cookie::Cookie;
 pub use self::credentials::Credentials;
+pub use self::cross_origin_embedder_policy::CrossOriginEmbedderPolicy;
+pub use self::cross_origin_opener_policy::CrossOriginOpenerPolicy;
+pub use self::cross_origin_resource_policy::CrossOriginResourcePolicy;
+pub use self::csp_directive_name::CspDirectiveName;
+pub use self::csp_keyword::CspKeyword;
+pub use self::date::Date;
+pub use self::entity_tag::EntityTag;
+pub use self::etag::ETag;
+pub use self::expires::Expires;
+pub use self::forwarded::{Forwarded, ForwardedElement, ForwardedNode, NodeName, NodePort};
 pub use self::host::Host;
 pub use self::http_date::HttpDate;
+pub use self::if_match::IfMatch;
+pub use self::if_modified_since::IfModifiedSince;
+pub use self::if_none_match::IfNoneMatch;
+pub use self::if_range::IfRange;
+pub use self::if_unmodified_since::IfUnmodifiedSince;
+pub use self::language_range::LanguageRange;
+pub use self::language_tag::LanguageTag;
+pub use self::last_modified::LastModified;
+pub use self::origin::Origin;
+pub use self::permissions_policy::{Allowlist, AllowlistEntry, PermissionsPolicy};
+pub use self::proxy_authenticate::ProxyAuthenticate;
 pub use self::proxy_authorization::ProxyAuthorization;
 pub use self::quality::{Quality, QualityItem};
+pub use self::range::{ByteRangeSpec, Range, RangeResolution};
+pub use self::range_unit::RangeUnit;
+pub use self::referrer_policy::{ReferrerPolicy, ReferrerPolicyToken};
 pub use self::retry_after::RetryAfter;
-pub use self::token68::Token68;
+pub use self::set_cookie::{ResponseCookie, SameSite, SetCookie};
+pub use self::strict_transport_security::StrictTransportSecurity;
+pub use self::token68::{InvalidToken68, Token68};
+pub use self::vary::{SecondaryKey, Vary};
+pub use self::www_authenticate::WwwAuthenticate;
+pub use self::x_content_type_options::XContentTypeOptions;
+pub use self::x_forwarded_for::XForwardedFor;
+pub use self::x_forwarded_host::XForwardedHost;
+pub use self::x_forwarded_proto::XForwardedProto;
+pub use self::x_frame_options::XFrameOptions;
+pub use self::x_real_ip::XRealIp;
 
 macro_rules! header {
     // #rule
@@ -45,6 +102,13 @@ macro_rules! header {
     };
     // 1#rule
     ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)+) => {
+        header! {
+            $(#[$a])*
+            ($id, $n) => ($item)+ using $crate::util::parse_comma_delimited
+        }
+    };
+    // 1#rule, with a custom list parser
+    ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)+ using $parse:path) => {
         $(#[$a])*
         #[derive(Clone, Debug, PartialEq)]
         pub struct $id(::std::vec::Vec<$item>);
@@ -72,7 +136,7 @@ macro_rules! header {
                 values: &mut $crate::http::header::ValueIter<$crate::http::header::HeaderValue>,
             ) -> ::std::result::Result<::std::option::Option<$id>, $crate::Error>
             {
-                match $crate::util::parse_comma_delimited(values)? {
+                match $parse(values)? {
                     Some(values) => $id::new(values).map(Some),
                     None => Ok(None),
                 }
@@ -148,7 +212,7 @@ macro_rules! token {
         }
     ) => {
         #[derive(Debug, Clone, PartialEq, Eq)]
-        #[allow(non_camel_case_types)]
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         enum Inner {
             $(
                 $variant,
@@ -185,7 +249,7 @@ macro_rules! token {
                 if $crate::util::is_token(s) {
                     Ok($name(Inner::Other(s.to_ascii_lowercase())))
                 } else {
-                    Err($crate::Error::invalid_value())
+                    Err($crate::util::invalid_token(s))
                 }
             }
 
@@ -217,18 +281,73 @@ macro_rules! token {
 }
 
 mod accept;
+mod accept_charset;
 mod accept_encoding;
+mod accept_language;
+mod accept_ranges;
+mod access_control_allow_credentials;
+mod access_control_allow_headers;
+mod access_control_allow_methods;
+mod access_control_allow_origin;
+mod access_control_expose_headers;
+mod access_control_max_age;
+mod access_control_request_headers;
+mod access_control_request_method;
+mod age;
 mod allow;
+mod auth_param;
 mod auth_scheme;
 mod authorization;
+mod cache_control;
+mod challenge;
+mod charset;
 mod content_coding;
 mod content_encoding;
+mod content_language;
 mod content_length;
+mod content_range;
+mod content_security_policy;
+mod content_security_policy_report_only;
 mod content_type;
+mod cookie;
 mod credentials;
+mod cross_origin_embedder_policy;
+mod cross_origin_opener_policy;
+mod cross_origin_resource_policy;
+mod csp_directive_name;
+mod csp_keyword;
+mod date;
+mod entity_tag;
+mod etag;
+mod expires;
+mod forwarded;
 mod host;
 mod http_date;
+mod if_match;
+mod if_modified_since;
+mod if_none_match;
+mod if_range;
+mod if_unmodified_since;
+mod language_range;
+mod language_tag;
+mod last_modified;
+mod origin;
+mod permissions_policy;
+mod proxy_authenticate;
 mod proxy_authorization;
 mod quality;
+mod range;
+mod range_unit;
+mod referrer_policy;
 mod retry_after;
+mod set_cookie;
+mod strict_transport_security;
 mod token68;
+mod vary;
+mod www_authenticate;
+mod x_content_type_options;
+mod x_forwarded_for;
+mod x_forwarded_host;
+mod x_forwarded_proto;
+mod x_frame_options;
+mod x_real_ip;
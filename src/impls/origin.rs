@@ -0,0 +1,279 @@
+use http::header::{self, HeaderName, HeaderValue, ORIGIN};
+use http::uri::Authority;
+use http::Uri;
+use std::fmt;
+use std::str::FromStr;
+
+use super::host::parse_authority;
+use crate::{util, Error, ErrorKind, Header, ToValues};
+
+/// `Origin` header, defined in [RFC6454](https://tools.ietf.org/html/rfc6454#section-7)
+///
+/// The `Origin` header field indicates the origin(s) that "caused" the
+/// user agent to issue the request, as defined in Section 4.
+///
+/// An origin is either a scheme, host, and port tuple, or the opaque value `null`. The scheme and
+/// host are compared case-insensitively, and a port which is the default for the scheme is
+/// equivalent to no port at all, so `https://example.com:443` and `https://EXAMPLE.com` are the
+/// same origin.
+///
+/// # ABNF
+///
+/// ```text
+/// origin              = "Origin:" OWS origin-list-or-null OWS
+/// origin-list-or-null = %x6E %x75 %x6C %x6C / origin-list
+/// origin-list         = serialized-origin *( SP serialized-origin )
+/// serialized-origin   = scheme "://" host [ ":" port ]
+/// ```
+///
+/// # Example values
+///
+/// * `https://example.com`
+/// * `http://example.com:8080`
+/// * `null`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Origin(Option<Tuple>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Tuple {
+    scheme: String,
+    host: String,
+    // None if the port is the default for the scheme
+    port: Option<u16>,
+}
+
+impl Origin {
+    /// Creates an origin from a scheme, host, and optional port.
+    pub fn new(scheme: &str, host: &str, port: Option<u16>) -> Result<Origin, Error> {
+        if !is_scheme(scheme) {
+            return Err(Error::new(ErrorKind::InvalidUri));
+        }
+
+        // go through authority to validate the hostname
+        let authority = match port {
+            Some(port) => parse_authority(&format!("{}:{}", host, port))?,
+            None => parse_authority(host)?,
+        };
+
+        Ok(Origin::from_parts(scheme, &authority))
+    }
+
+    /// Returns the origin of a URI.
+    ///
+    /// Returns `None` if the URI does not have both a scheme and an authority.
+    pub fn from_uri(uri: &Uri) -> Option<Origin> {
+        match (uri.scheme_str(), uri.authority()) {
+            (Some(scheme), Some(authority)) => Some(Origin::from_parts(scheme, authority)),
+            _ => None,
+        }
+    }
+
+    fn from_parts(scheme: &str, authority: &Authority) -> Origin {
+        let scheme = scheme.to_ascii_lowercase();
+        let port = authority
+            .port_u16()
+            .filter(|&port| default_port(&scheme) != Some(port));
+
+        Origin(Some(Tuple {
+            scheme,
+            host: authority.host().to_ascii_lowercase(),
+            port,
+        }))
+    }
+
+    /// Returns the opaque `null` origin.
+    #[inline]
+    pub fn null() -> Origin {
+        Origin(None)
+    }
+
+    /// Determines if this is the opaque `null` origin.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns the scheme of the origin, in lowercase.
+    #[inline]
+    pub fn scheme(&self) -> Option<&str> {
+        self.0.as_ref().map(|t| &*t.scheme)
+    }
+
+    /// Returns the host of the origin, in lowercase.
+    #[inline]
+    pub fn host(&self) -> Option<&str> {
+        self.0.as_ref().map(|t| &*t.host)
+    }
+
+    /// Returns the port of the origin.
+    ///
+    /// If the origin did not specify a port, the default port of its scheme is returned. `None`
+    /// is returned for the `null` origin, and for origins without a port whose scheme has no
+    /// default.
+    #[inline]
+    pub fn port(&self) -> Option<u16> {
+        self.0
+            .as_ref()
+            .and_then(|t| t.port.or_else(|| default_port(&t.scheme)))
+    }
+
+    /// Determines if a URI has the same origin as this one.
+    ///
+    /// The `null` origin is not the same origin as any URI, and neither is a URI without a scheme
+    /// and authority.
+    pub fn is_same_origin(&self, uri: &Uri) -> bool {
+        !self.is_null() && Origin::from_uri(uri).as_ref() == Some(self)
+    }
+}
+
+fn is_scheme(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(ref t) => {
+                write!(fmt, "{}://{}", t.scheme, t.host)?;
+                if let Some(port) = t.port {
+                    write!(fmt, ":{}", port)?;
+                }
+                Ok(())
+            }
+            None => fmt.write_str("null"),
+        }
+    }
+}
+
+impl FromStr for Origin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Origin, Error> {
+        if s == "null" {
+            return Ok(Origin::null());
+        }
+
+        let uri = s
+            .parse::<Uri>()
+            .map_err(|e| Error::new(ErrorKind::InvalidUri).with_source(e))?;
+        // the parser fills in a path of `/` when it is absent
+        if uri.path() != "/" || uri.query().is_some() || s.ends_with('/') {
+            return Err(Error::new(ErrorKind::InvalidUri));
+        }
+        if let Some(offset) = s.find('@') {
+            return Err(Error::new(ErrorKind::InvalidUri).with_offset(offset));
+        }
+
+        Origin::from_uri(&uri).ok_or_else(|| Error::new(ErrorKind::InvalidUri))
+    }
+}
+
+impl Header for Origin {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &ORIGIN
+    }
+
+    #[inline]
+    fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<Origin>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &Origin::new("https", "example.com", None).unwrap(),
+            &["https://example.com"],
+        );
+        util::test_round_trip(
+            &Origin::new("http", "example.com", Some(8080)).unwrap(),
+            &["http://example.com:8080"],
+        );
+        util::test_round_trip(
+            &Origin::new("http", "[::1]", Some(8080)).unwrap(),
+            &["http://[::1]:8080"],
+        );
+        util::test_round_trip(&Origin::null(), &["null"]);
+    }
+
+    #[test]
+    fn normalization() {
+        let origin = "HTTPS://Example.COM:443".parse::<Origin>().unwrap();
+        assert_eq!(origin, Origin::new("https", "example.com", None).unwrap());
+        assert_eq!(origin.to_string(), "https://example.com");
+        assert_eq!(origin.scheme(), Some("https"));
+        assert_eq!(origin.host(), Some("example.com"));
+        assert_eq!(origin.port(), Some(443));
+
+        let origin = "http://example.com:443".parse::<Origin>().unwrap();
+        assert_eq!(origin.port(), Some(443));
+        assert_eq!(origin.to_string(), "http://example.com:443");
+
+        let origin = "foo://example.com".parse::<Origin>().unwrap();
+        assert_eq!(origin.port(), None);
+
+        assert_eq!(Origin::null().scheme(), None);
+        assert_eq!(Origin::null().port(), None);
+    }
+
+    #[test]
+    fn same_origin() {
+        let origin = "https://example.com".parse::<Origin>().unwrap();
+        let cases = [
+            ("https://example.com/foo?bar", true),
+            ("https://EXAMPLE.com:443/", true),
+            ("https://user@example.com/", true),
+            ("http://example.com/", false),
+            ("https://example.com:8443/", false),
+            ("https://www.example.com/", false),
+            ("/foo", false),
+        ];
+        for &(uri, expected) in &cases {
+            let uri = uri.parse::<Uri>().unwrap();
+            assert_eq!(origin.is_same_origin(&uri), expected, "{}", uri);
+        }
+
+        let uri = "https://example.com/".parse::<Uri>().unwrap();
+        assert!(!Origin::null().is_same_origin(&uri));
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "example.com",
+            "https://example.com/",
+            "https://example.com/foo",
+            "https://example.com?foo",
+            "https://user@example.com",
+            "NULL",
+        ];
+        for value in &cases {
+            assert!(value.parse::<Origin>().is_err(), "{}", value);
+        }
+
+        assert!(Origin::new("1http", "example.com", None).is_err());
+        assert!(Origin::new("https", "example com", None).is_err());
+        assert!(Origin::new("https", "user@example.com", None).is_err());
+    }
+}
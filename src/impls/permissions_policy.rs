@@ -0,0 +1,412 @@
+use http::header::{self, HeaderName, HeaderValue};
+use std::fmt;
+
+use super::Origin;
+use crate::structured::{self, BareItem, Dictionary, InnerList, Item, Member, StructuredField};
+use crate::{Error, ErrorKind, Header, ToValues};
+
+static PERMISSIONS_POLICY: HeaderName = HeaderName::from_static("permissions-policy");
+
+/// `Permissions-Policy` header, defined in
+/// [Permissions Policy](https://w3c.github.io/webappsec-permissions-policy/#permissions-policy-http-header-field)
+///
+/// The `Permissions-Policy` HTTP header field can be used in the response
+/// (server to client) to communicate the permissions policy that should be
+/// enforced by the client.
+///
+/// The value is a structured field dictionary mapping feature names to allowlists. Members whose
+/// value is not a valid allowlist are ignored, as are unrecognized allowlist entries. Parameters
+/// are ignored.
+///
+/// # ABNF
+///
+/// ```text
+/// Permissions-Policy = sf-dictionary
+/// ```
+///
+/// # Example values
+///
+/// * `geolocation=()`
+/// * `camera=self, fullscreen=*, payment=(self "https://pay.example.com")`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionsPolicy(Vec<(String, Allowlist)>);
+
+impl PermissionsPolicy {
+    /// Creates an empty policy.
+    #[inline]
+    pub fn new() -> PermissionsPolicy {
+        PermissionsPolicy::default()
+    }
+
+    /// Returns the number of features in the policy.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Determines if the policy is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the allowlist of a feature, if the policy contains it.
+    pub fn get(&self, feature: &str) -> Option<&Allowlist> {
+        self.0.iter().find(|(f, _)| f == feature).map(|(_, a)| a)
+    }
+
+    /// Returns an iterator over the features and allowlists of the policy, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Allowlist)> {
+        self.0.iter().map(|(f, a)| (&**f, a))
+    }
+
+    /// Sets the allowlist of a feature.
+    ///
+    /// If the policy already contains the feature, its allowlist is replaced in its current
+    /// position. An error is returned if the feature name is not a valid structured field key.
+    pub fn insert(&mut self, feature: &str, allowlist: Allowlist) -> Result<(), Error> {
+        if !is_key(feature) {
+            return Err(Error::new(ErrorKind::InvalidToken));
+        }
+
+        self.insert_unchecked(feature, allowlist);
+        Ok(())
+    }
+
+    fn insert_unchecked(&mut self, feature: &str, allowlist: Allowlist) {
+        match self.0.iter_mut().find(|(f, _)| f == feature) {
+            Some(existing) => existing.1 = allowlist,
+            None => self.0.push((feature.to_string(), allowlist)),
+        }
+    }
+
+    /// Removes a feature from the policy, returning its allowlist.
+    pub fn remove(&mut self, feature: &str) -> Option<Allowlist> {
+        let idx = self.0.iter().position(|(f, _)| f == feature)?;
+        Some(self.0.remove(idx).1)
+    }
+
+    /// Merges the features of another policy into this one.
+    ///
+    /// Features which are only present in `other` are appended to this policy. For features
+    /// present in both, the entries of `other`'s allowlist which this policy's allowlist lacks are
+    /// appended to it.
+    pub fn merge(&mut self, other: &PermissionsPolicy) {
+        for (feature, allowlist) in &other.0 {
+            match self.0.iter_mut().find(|(f, _)| f == feature) {
+                Some(existing) => {
+                    for entry in &allowlist.0 {
+                        existing.1.push(entry.clone());
+                    }
+                }
+                None => self.0.push((feature.clone(), allowlist.clone())),
+            }
+        }
+    }
+
+    fn from_dictionary(dictionary: &Dictionary) -> PermissionsPolicy {
+        let mut policy = PermissionsPolicy::new();
+        for (feature, member) in dictionary.iter() {
+            let allowlist = match *member {
+                Member::Item(ref item) => match item.bare_item {
+                    BareItem::Token(_) | BareItem::String(_) => {
+                        Allowlist::new(parse_entry(&item.bare_item).into_iter().collect())
+                    }
+                    // other types of items are not allowlists
+                    _ => continue,
+                },
+                Member::InnerList(ref list) => Allowlist::new(
+                    list.items
+                        .iter()
+                        .filter_map(|item| parse_entry(&item.bare_item))
+                        .collect(),
+                ),
+            };
+            policy.0.push((feature.to_string(), allowlist));
+        }
+        policy
+    }
+
+    fn to_dictionary(&self) -> Dictionary {
+        let mut dictionary = Dictionary::new();
+        for (feature, allowlist) in &self.0 {
+            let member = match &*allowlist.0 {
+                [entry @ AllowlistEntry::Any] | [entry @ AllowlistEntry::SelfOrigin] => {
+                    Member::from(entry_item(entry))
+                }
+                entries => Member::from(InnerList::new(
+                    entries.iter().map(|e| Item::new(entry_item(e))).collect(),
+                )),
+            };
+            dictionary.insert(feature, member);
+        }
+        dictionary
+    }
+}
+
+impl fmt::Display for PermissionsPolicy {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.to_dictionary().serialize().map_err(|_| fmt::Error)?;
+        fmt.write_str(&value)
+    }
+}
+
+impl Header for PermissionsPolicy {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &PERMISSIONS_POLICY
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<PermissionsPolicy>, Error> {
+        structured::parse_field::<Dictionary>(values)
+            .map(|r| r.map(|dictionary| PermissionsPolicy::from_dictionary(&dictionary)))
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        structured::encode_field(&self.to_dictionary(), values);
+    }
+}
+
+/// An entry of a permissions policy allowlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowlistEntry {
+    /// `*`, matching all origins.
+    Any,
+    /// `self`, matching the origin of the document.
+    SelfOrigin,
+    /// A specific origin.
+    Origin(Origin),
+}
+
+/// The origins a feature is enabled for in a permissions policy.
+///
+/// An empty allowlist disables the feature entirely.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Allowlist(Vec<AllowlistEntry>);
+
+impl Allowlist {
+    /// Creates an allowlist from a list of entries.
+    ///
+    /// Duplicate entries are removed.
+    pub fn new(entries: Vec<AllowlistEntry>) -> Allowlist {
+        let mut allowlist = Allowlist::none();
+        for entry in entries {
+            allowlist.push(entry);
+        }
+        allowlist
+    }
+
+    /// Returns an empty allowlist, disabling the feature.
+    #[inline]
+    pub fn none() -> Allowlist {
+        Allowlist(vec![])
+    }
+
+    /// Returns an allowlist matching all origins.
+    #[inline]
+    pub fn any() -> Allowlist {
+        Allowlist(vec![AllowlistEntry::Any])
+    }
+
+    /// Returns an allowlist matching only the origin of the document.
+    #[inline]
+    pub fn self_origin() -> Allowlist {
+        Allowlist(vec![AllowlistEntry::SelfOrigin])
+    }
+
+    /// Returns the entries of the allowlist.
+    #[inline]
+    pub fn entries(&self) -> &[AllowlistEntry] {
+        &self.0
+    }
+
+    /// Determines if the allowlist is empty, disabling the feature.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds an entry to the allowlist, if it is not already present.
+    pub fn push(&mut self, entry: AllowlistEntry) {
+        if !self.0.contains(&entry) {
+            self.0.push(entry);
+        }
+    }
+
+    /// Determines if the allowlist matches an origin.
+    ///
+    /// `self_origin` is the origin of the document the policy applies to. An opaque origin never
+    /// matches.
+    pub fn matches(&self, origin: &Origin, self_origin: &Origin) -> bool {
+        !origin.is_null()
+            && self.0.iter().any(|entry| match *entry {
+                AllowlistEntry::Any => true,
+                AllowlistEntry::SelfOrigin => origin == self_origin,
+                AllowlistEntry::Origin(ref allowed) => origin == allowed,
+            })
+    }
+}
+
+impl From<AllowlistEntry> for Allowlist {
+    #[inline]
+    fn from(entry: AllowlistEntry) -> Allowlist {
+        Allowlist(vec![entry])
+    }
+}
+
+fn parse_entry(item: &BareItem) -> Option<AllowlistEntry> {
+    match *item {
+        BareItem::Token(ref token) if token == "*" => Some(AllowlistEntry::Any),
+        BareItem::Token(ref token) if token == "self" => Some(AllowlistEntry::SelfOrigin),
+        BareItem::String(ref origin) => origin
+            .parse()
+            .ok()
+            .filter(|o: &Origin| !o.is_null())
+            .map(AllowlistEntry::Origin),
+        _ => None,
+    }
+}
+
+fn entry_item(entry: &AllowlistEntry) -> BareItem {
+    match *entry {
+        AllowlistEntry::Any => BareItem::Token("*".to_string()),
+        AllowlistEntry::SelfOrigin => BareItem::Token("self".to_string()),
+        AllowlistEntry::Origin(ref origin) => BareItem::String(origin.to_string()),
+    }
+}
+
+fn is_key_char(b: u8) -> bool {
+    b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b)
+}
+
+fn is_key(s: &str) -> bool {
+    s.bytes()
+        .next()
+        .is_some_and(|b| b.is_ascii_lowercase() || b == b'*')
+        && s.bytes().all(is_key_char)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{util, HeaderMapExt};
+    use http::HeaderMap;
+
+    fn origin(s: &str) -> Origin {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn rfc() {
+        let mut policy = PermissionsPolicy::new();
+        policy.insert("geolocation", Allowlist::none()).unwrap();
+        policy.insert("camera", Allowlist::self_origin()).unwrap();
+        policy.insert("fullscreen", Allowlist::any()).unwrap();
+        policy
+            .insert(
+                "payment",
+                Allowlist::new(vec![
+                    AllowlistEntry::SelfOrigin,
+                    AllowlistEntry::Origin(origin("https://pay.example.com")),
+                ]),
+            )
+            .unwrap();
+        util::test_round_trip(
+            &policy,
+            &["geolocation=(), camera=self, fullscreen=*, \
+               payment=(self \"https://pay.example.com\")"],
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let mut expected = PermissionsPolicy::new();
+        expected.insert("camera", Allowlist::self_origin()).unwrap();
+        expected
+            .insert(
+                "usb",
+                Allowlist::from(AllowlistEntry::Origin(origin("https://a.example"))),
+            )
+            .unwrap();
+        expected.insert("geolocation", Allowlist::none()).unwrap();
+        expected.insert("microphone", Allowlist::any()).unwrap();
+
+        util::test_decode(
+            &[
+                "  camera=(self), usb=(\"https://a.example\" src \"not an origin\");report-to=x",
+                "geolocation=(\"null\"),battery, sync-xhr=?1, microphone=self",
+                "microphone=*;a=1;b=\"c\";d=:aGk=:;e=?0",
+            ],
+            &expected,
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "Camera=self",
+            "camera=self,",
+            "camera=self camera=*",
+            "camera=(self",
+            "camera=(self\"https://a.example\")",
+            "camera=\"unterminated",
+            "camera=?2",
+        ];
+        for value in &cases {
+            let mut map = HeaderMap::new();
+            map.insert(&PERMISSIONS_POLICY, HeaderValue::from_static(value));
+            assert!(map.typed_get::<PermissionsPolicy>().is_err(), "{}", value);
+        }
+
+        let mut policy = PermissionsPolicy::new();
+        assert!(policy.insert("Camera", Allowlist::any()).is_err());
+    }
+
+    #[test]
+    fn merge() {
+        let mut policy = PermissionsPolicy::new();
+        policy.insert("camera", Allowlist::self_origin()).unwrap();
+        policy.insert("geolocation", Allowlist::none()).unwrap();
+
+        let mut other = PermissionsPolicy::new();
+        other
+            .insert(
+                "camera",
+                Allowlist::new(vec![
+                    AllowlistEntry::SelfOrigin,
+                    AllowlistEntry::Origin(origin("https://a.example")),
+                ]),
+            )
+            .unwrap();
+        other.insert("usb", Allowlist::none()).unwrap();
+
+        policy.merge(&other);
+        assert_eq!(
+            policy.to_string(),
+            "camera=(self \"https://a.example\"), geolocation=(), usb=()"
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let document = origin("https://example.com");
+        let allowlist = Allowlist::new(vec![
+            AllowlistEntry::SelfOrigin,
+            AllowlistEntry::Origin(origin("https://a.example")),
+        ]);
+        assert!(allowlist.matches(&document, &document));
+        assert!(allowlist.matches(&origin("https://a.example:443"), &document));
+        assert!(!allowlist.matches(&origin("https://b.example"), &document));
+        assert!(!Allowlist::none().matches(&document, &document));
+        assert!(Allowlist::any().matches(&origin("https://b.example"), &document));
+
+        let null = Origin::null();
+        assert!(!allowlist.matches(&null, &null));
+        assert!(!Allowlist::any().matches(&null, &document));
+        assert!(!Allowlist::from(AllowlistEntry::Origin(null.clone())).matches(&null, &document));
+    }
+}
@@ -0,0 +1,36 @@
+use http::header::PROXY_AUTHENTICATE;
+
+use super::{challenge, Challenge};
+
+header! {
+    /// `Proxy-Authenticate` header, defined in [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.3)
+    ///
+    /// The `Proxy-Authenticate` header field consists of at least one
+    /// challenge that indicates the authentication scheme(s) and parameters
+    /// applicable to the proxy for this effective request URI.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Proxy-Authenticate = 1#challenge
+    /// ```
+    ///
+    /// # Example values
+    /// * `Basic realm="simple"`
+    /// * `Newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm="simple"`
+    (ProxyAuthenticate, PROXY_AUTHENTICATE) => (Challenge)+ using challenge::parse_challenges
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn basic() {
+        util::test_round_trip(
+            &ProxyAuthenticate::from(Challenge::basic("simple").unwrap()),
+            &[r#"Basic realm="simple""#],
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use std::error;
 use std::fmt;
 use std::slice;
 use std::str::{self, FromStr};
 
+use crate::{util, Error, ErrorKind};
+
 /// A value paired with its "quality" as defined in [RFC7231].
 ///
 /// Quality items are used in content negotiation headers such as `Accept` and `Accept-Encoding`.
@@ -48,24 +51,43 @@ where
 impl<T> FromStr for QualityItem<T>
 where
     T: FromStr,
+    T::Err: Into<Box<dyn error::Error + Sync + Send>>,
 {
-    type Err = T::Err;
+    type Err = Error;
 
-    fn from_str(mut s: &str) -> Result<QualityItem<T>, T::Err> {
+    fn from_str(mut s: &str) -> Result<QualityItem<T>, Error> {
         let quality = match WeightParser::parse(s) {
             Some((remaining, quality)) => {
                 s = &s[..remaining];
                 quality
             }
-            None => Quality(1000),
+            None => {
+                if let Some(offset) = weight_offset(s) {
+                    return Err(Error::new(ErrorKind::InvalidQuality).with_offset(offset));
+                }
+                Quality(1000)
+            }
         };
 
-        let item = s.parse()?;
+        let item = s.parse().map_err(util::convert_error)?;
 
         Ok(QualityItem { item, quality })
     }
 }
 
+// Returns the offset of a trailing `q=` parameter, if one is present.
+fn weight_offset(s: &str) -> Option<usize> {
+    let idx = s.rfind(';')?;
+    let param = &s[idx + 1..];
+    let offset = idx + 1 + util::leading_ws(param);
+    let param = param.trim_start();
+    if param.starts_with("q=") || param.starts_with("Q=") {
+        Some(offset)
+    } else {
+        None
+    }
+}
+
 struct WeightParser<'a>(slice::Iter<'a, u8>);
 
 impl<'a> WeightParser<'a> {
@@ -125,6 +147,7 @@ impl<'a> WeightParser<'a> {
         }
     }
 
+    #[allow(clippy::while_let_loop)]
     fn ows(&mut self) {
         loop {
             match self.peek() {
@@ -181,10 +204,32 @@ impl Quality {
     }
 }
 
+/// Selects the available item with the highest nonzero quality.
+///
+/// Ties are broken in favor of the item which appears first in `available`.
+pub(crate) fn negotiate<T, F>(available: &[T], quality: F) -> Option<&T>
+where
+    F: Fn(&T) -> Quality,
+{
+    let mut best = None;
+    for item in available {
+        let quality = quality(item);
+        if quality == Quality(0) {
+            continue;
+        }
+
+        match best {
+            Some((_, best_quality)) if best_quality >= quality => {}
+            _ => best = Some((item, quality)),
+        }
+    }
+
+    best.map(|(item, _)| item)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Error;
 
     #[derive(Debug, Clone, PartialEq)]
     struct Item;
@@ -249,6 +294,16 @@ mod test {
         assert!("item; q=0.0001".parse::<QualityItem<Item>>().is_err());
     }
 
+    #[test]
+    fn parse_err_kind() {
+        let err = "item; q=1.1".parse::<QualityItem<Item>>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidQuality);
+        assert_eq!(err.offset(), Some(6));
+
+        let err = "itme; q=1".parse::<QualityItem<Item>>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
     #[test]
     fn display() {
         assert_eq!(qitem(1000).to_string(), "item");
@@ -0,0 +1,412 @@
+use http::header::{self, HeaderName, HeaderValue, RANGE};
+use std::fmt;
+use std::ops;
+use std::str::FromStr;
+
+use super::RangeUnit;
+use crate::{util, Error, Header, ToValues};
+
+/// A single range in a byte range set.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ByteRangeSpec {
+    /// The bytes from the first position to the last position, inclusive.
+    FromTo(u64, u64),
+    /// The bytes from the first position to the end of the representation.
+    AllFrom(u64),
+    /// The specified number of bytes at the end of the representation.
+    Last(u64),
+}
+
+impl ByteRangeSpec {
+    /// Resolves the range against a representation of the specified length.
+    ///
+    /// `None` is returned if the range is not satisfiable.
+    pub fn to_satisfiable_range(&self, complete_length: u64) -> Option<ops::Range<u64>> {
+        match *self {
+            ByteRangeSpec::FromTo(first, last) if first < complete_length => {
+                Some(first..last.saturating_add(1).min(complete_length))
+            }
+            ByteRangeSpec::AllFrom(first) if first < complete_length => {
+                Some(first..complete_length)
+            }
+            ByteRangeSpec::Last(len) if len > 0 && complete_length > 0 => {
+                Some(complete_length.saturating_sub(len)..complete_length)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ByteRangeSpec {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ByteRangeSpec::FromTo(first, last) => write!(fmt, "{}-{}", first, last),
+            ByteRangeSpec::AllFrom(first) => write!(fmt, "{}-", first),
+            ByteRangeSpec::Last(len) => write!(fmt, "-{}", len),
+        }
+    }
+}
+
+impl FromStr for ByteRangeSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ByteRangeSpec, Error> {
+        let idx = match s.find('-') {
+            Some(idx) => idx,
+            None => return Err(Error::invalid_value()),
+        };
+        let (first, last) = (&s[..idx], &s[idx + 1..]);
+
+        if first.is_empty() {
+            return util::parse_digits(last)
+                .map(ByteRangeSpec::Last)
+                .map_err(|e| e.offset_by(idx + 1));
+        }
+
+        let first = util::parse_digits(first)?;
+        if last.is_empty() {
+            return Ok(ByteRangeSpec::AllFrom(first));
+        }
+
+        let last = util::parse_digits(last).map_err(|e| e.offset_by(idx + 1))?;
+        if last < first {
+            return Err(Error::invalid_value().with_offset(idx + 1));
+        }
+
+        Ok(ByteRangeSpec::FromTo(first, last))
+    }
+}
+
+/// The result of evaluating a `Range` header against a representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeResolution {
+    /// The request should be served with a 206 (Partial Content) response containing these
+    /// ranges.
+    ///
+    /// The ranges are sorted, and overlapping or adjacent ranges are coalesced.
+    Partial(Vec<ops::Range<u64>>),
+    /// The request should be rejected with a 416 (Range Not Satisfiable) response.
+    NotSatisfiable,
+    /// The `Range` header should be ignored and the full representation served with a 200 (OK)
+    /// response.
+    Full,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Inner {
+    Bytes(Vec<ByteRangeSpec>),
+    Other(RangeUnit, String),
+}
+
+/// `Range` header, defined in [RFC7233](https://tools.ietf.org/html/rfc7233#section-3.1)
+///
+/// The `Range` header field on a GET request modifies the method
+/// semantics to request transfer of only one or more subranges of the
+/// selected representation data, rather than the entire selected
+/// representation data.
+///
+/// # ABNF
+///
+/// ```text
+/// Range = byte-ranges-specifier / other-ranges-specifier
+/// other-ranges-specifier = other-range-unit "=" other-range-set
+/// other-range-set = 1*VCHAR
+///
+/// bytes-unit = "bytes"
+///
+/// byte-ranges-specifier = bytes-unit "=" byte-range-set
+/// byte-range-set = 1#(byte-range-spec / suffix-byte-range-spec)
+/// byte-range-spec = first-byte-pos "-" [last-byte-pos]
+/// first-byte-pos = 1*DIGIT
+/// last-byte-pos = 1*DIGIT
+/// ```
+///
+/// # Example values
+///
+/// * `bytes=1000-`
+/// * `bytes=-2000`
+/// * `bytes=0-1,30-40`
+/// * `bytes=0-10,20-90,-100`
+/// * `custom_unit=0-123`
+/// * `custom_unit=xxx-yyy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range(Inner);
+
+impl Range {
+    /// Creates a `Range` header from a set of byte ranges.
+    ///
+    /// An error is returned if the set is empty or a range's last position precedes its first.
+    pub fn bytes(ranges: Vec<ByteRangeSpec>) -> Result<Range, Error> {
+        if ranges.is_empty() {
+            return Err(Error::too_few_values());
+        }
+
+        for range in &ranges {
+            if let ByteRangeSpec::FromTo(first, last) = *range {
+                if last < first {
+                    return Err(Error::invalid_value());
+                }
+            }
+        }
+
+        Ok(Range(Inner::Bytes(ranges)))
+    }
+
+    /// Creates a `Range` header from a unit other than `bytes` and an opaque range set.
+    pub fn other(unit: RangeUnit, set: &str) -> Result<Range, Error> {
+        if unit == RangeUnit::BYTES {
+            return format!("{}={}", unit, set).parse();
+        }
+
+        if set.is_empty() {
+            return Err(Error::invalid_value());
+        }
+        if let Some(offset) = set.bytes().position(|b| !(b'!'..=b'~').contains(&b)) {
+            return Err(Error::invalid_value().with_offset(offset));
+        }
+
+        Ok(Range(Inner::Other(unit, set.to_string())))
+    }
+
+    /// Returns the byte ranges, if this header uses the `bytes` unit.
+    #[inline]
+    pub fn byte_ranges(&self) -> Option<&[ByteRangeSpec]> {
+        match self.0 {
+            Inner::Bytes(ref ranges) => Some(ranges),
+            Inner::Other(..) => None,
+        }
+    }
+
+    /// Returns the unit and range set, if this header uses a unit other than `bytes`.
+    #[inline]
+    pub fn other_ranges(&self) -> Option<(&RangeUnit, &str)> {
+        match self.0 {
+            Inner::Bytes(_) => None,
+            Inner::Other(ref unit, ref set) => Some((unit, set)),
+        }
+    }
+
+    /// Evaluates the header against a representation of the specified length.
+    ///
+    /// Ranges which are not satisfiable are discarded, and the remainder are sorted and
+    /// coalesced. If none are satisfiable, the request is not satisfiable.
+    ///
+    /// As permitted by [RFC7233], requests for more than `max_ranges` ranges are rejected as not
+    /// satisfiable to protect against denial of service attacks. Headers using units other than
+    /// `bytes` are ignored.
+    ///
+    /// [RFC7233]: https://tools.ietf.org/html/rfc7233#section-6.1
+    pub fn resolve(&self, complete_length: u64, max_ranges: usize) -> RangeResolution {
+        let specs = match self.0 {
+            Inner::Bytes(ref specs) => specs,
+            Inner::Other(..) => return RangeResolution::Full,
+        };
+
+        if specs.len() > max_ranges {
+            return RangeResolution::NotSatisfiable;
+        }
+
+        let mut ranges = specs
+            .iter()
+            .filter_map(|spec| spec.to_satisfiable_range(complete_length))
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            return RangeResolution::NotSatisfiable;
+        }
+
+        ranges.sort_by_key(|range| range.start);
+        let mut coalesced: Vec<ops::Range<u64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match coalesced.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => coalesced.push(range),
+            }
+        }
+
+        RangeResolution::Partial(coalesced)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Inner::Bytes(ref ranges) => {
+                fmt.write_str("bytes=")?;
+                for (i, range) in ranges.iter().enumerate() {
+                    if i > 0 {
+                        fmt.write_str(",")?;
+                    }
+                    fmt::Display::fmt(range, fmt)?;
+                }
+                Ok(())
+            }
+            Inner::Other(ref unit, ref set) => write!(fmt, "{}={}", unit, set),
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Range, Error> {
+        let idx = match s.find('=') {
+            Some(idx) => idx,
+            None => return Err(Error::invalid_value()),
+        };
+        let unit = s[..idx].parse::<RangeUnit>()?;
+        let set = &s[idx + 1..];
+
+        if unit != RangeUnit::BYTES {
+            return Range::other(unit, set).map_err(|e| e.offset_by(idx + 1));
+        }
+
+        let mut ranges = vec![];
+        let mut start = idx + 1;
+        for elem in set.split(',') {
+            let offset = start + util::leading_ws(elem);
+            start += elem.len() + 1;
+
+            let elem = elem.trim();
+            if elem.is_empty() {
+                continue;
+            }
+
+            let range = elem
+                .parse::<ByteRangeSpec>()
+                .map_err(|e| e.offset_by(offset))?;
+            ranges.push(range);
+        }
+
+        Range::bytes(ranges).map_err(|_| Error::invalid_value().with_offset(idx + 1))
+    }
+}
+
+impl Header for Range {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &RANGE
+    }
+
+    #[inline]
+    fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<Range>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorKind, HeaderMapExt};
+    use http::HeaderMap;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &Range::bytes(vec![ByteRangeSpec::AllFrom(1000)]).unwrap(),
+            &["bytes=1000-"],
+        );
+        util::test_round_trip(
+            &Range::bytes(vec![ByteRangeSpec::Last(2000)]).unwrap(),
+            &["bytes=-2000"],
+        );
+        util::test_round_trip(
+            &Range::bytes(vec![
+                ByteRangeSpec::FromTo(0, 10),
+                ByteRangeSpec::FromTo(20, 90),
+                ByteRangeSpec::Last(100),
+            ])
+            .unwrap(),
+            &["bytes=0-10,20-90,-100"],
+        );
+        util::test_round_trip(
+            &Range::other(RangeUnit::new("custom_unit").unwrap(), "xxx-yyy").unwrap(),
+            &["custom_unit=xxx-yyy"],
+        );
+    }
+
+    #[test]
+    fn decode() {
+        util::test_decode(
+            &["Bytes= 0-499 , , -500"],
+            &Range::bytes(vec![
+                ByteRangeSpec::FromTo(0, 499),
+                ByteRangeSpec::Last(500),
+            ])
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn other_bytes() {
+        assert_eq!(
+            Range::other(RangeUnit::BYTES, "0-499,-500").unwrap(),
+            Range::bytes(vec![
+                ByteRangeSpec::FromTo(0, 499),
+                ByteRangeSpec::Last(500),
+            ])
+            .unwrap(),
+        );
+        assert!(Range::other(RangeUnit::BYTES, "499-0").is_err());
+        assert!(Range::other(RangeUnit::BYTES, "").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            ("bytes=", ErrorKind::InvalidValue, 6),
+            ("bytes=1-0", ErrorKind::InvalidValue, 8),
+            ("bytes=0-1, +1-2", ErrorKind::InvalidInteger, 11),
+            ("bytes=0-1,--2", ErrorKind::InvalidInteger, 11),
+            ("bytes=a", ErrorKind::InvalidValue, 6),
+            ("bytes=99999999999999999999-", ErrorKind::InvalidInteger, 6),
+        ];
+
+        for &(value, kind, offset) in &cases {
+            let mut map = HeaderMap::new();
+            map.insert(RANGE, value.parse().unwrap());
+            let err = map.typed_get::<Range>().unwrap_err();
+            assert_eq!(err.kind(), kind, "{}", value);
+            assert_eq!(err.offset(), Some(offset), "{}", value);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn resolve() {
+        let range = "bytes=0-499,-500".parse::<Range>().unwrap();
+        assert_eq!(
+            range.resolve(10000, 10),
+            RangeResolution::Partial(vec![0..500, 9500..10000])
+        );
+        assert_eq!(
+            range.resolve(800, 10),
+            RangeResolution::Partial(vec![0..800])
+        );
+        assert_eq!(range.resolve(0, 10), RangeResolution::NotSatisfiable);
+        assert_eq!(range.resolve(10000, 1), RangeResolution::NotSatisfiable);
+
+        let range = "bytes=500-600,601-999,0-10,5-20".parse::<Range>().unwrap();
+        assert_eq!(
+            range.resolve(10000, 10),
+            RangeResolution::Partial(vec![0..21, 500..1000])
+        );
+
+        let range = "bytes=500-,9000-9999".parse::<Range>().unwrap();
+        assert_eq!(range.resolve(400, 10), RangeResolution::NotSatisfiable);
+        assert_eq!(
+            range.resolve(501, 10),
+            RangeResolution::Partial(vec![500..501])
+        );
+
+        let range = "bytes=-0".parse::<Range>().unwrap();
+        assert_eq!(range.resolve(400, 10), RangeResolution::NotSatisfiable);
+
+        let range = "items=0-5".parse::<Range>().unwrap();
+        assert_eq!(range.resolve(400, 10), RangeResolution::Full);
+    }
+}
@@ -0,0 +1,12 @@
+token! {
+    /// A range unit, used in the `Range`, `Content-Range`, and `Accept-Ranges` headers.
+    RangeUnit => {
+        /// The bytes unit, as specified in [RFC7233].
+        ///
+        /// [RFC7233]: https://tools.ietf.org/html/rfc7233#section-2.1
+        BYTES => "bytes" => [],
+        /// A special value used in the `Accept-Ranges` header to indicate that no range units
+        /// are supported.
+        NONE => "none" => [],
+    }
+}
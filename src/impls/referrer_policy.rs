@@ -0,0 +1,116 @@
+use http::header::REFERRER_POLICY;
+
+token! {
+    /// A referrer policy, used in the `Referrer-Policy` header.
+    ReferrerPolicyToken => {
+        /// No referrer information is sent.
+        NO_REFERRER => "no-referrer" => [],
+        /// The full URL is sent, except when navigating from HTTPS to HTTP.
+        NO_REFERRER_WHEN_DOWNGRADE => "no-referrer-when-downgrade" => [],
+        /// Only the full URL is sent for same-origin requests.
+        SAME_ORIGIN => "same-origin" => [],
+        /// Only the origin is sent.
+        ORIGIN => "origin" => [],
+        /// Only the origin is sent, except when navigating from HTTPS to HTTP.
+        STRICT_ORIGIN => "strict-origin" => [],
+        /// The full URL is sent for same-origin requests, and only the origin otherwise.
+        ORIGIN_WHEN_CROSS_ORIGIN => "origin-when-cross-origin" => [],
+        /// Like `origin-when-cross-origin`, but nothing is sent when navigating from HTTPS to
+        /// HTTP. This is the default policy.
+        STRICT_ORIGIN_WHEN_CROSS_ORIGIN => "strict-origin-when-cross-origin" => [],
+        /// The full URL is always sent.
+        UNSAFE_URL => "unsafe-url" => [],
+    }
+}
+
+impl ReferrerPolicyToken {
+    /// Determines if this is one of the policies defined by the specification.
+    #[inline]
+    pub fn is_known(&self) -> bool {
+        !matches!(self.0, Inner::Other(_))
+    }
+}
+
+header! {
+    /// `Referrer-Policy` header, defined in
+    /// [Referrer Policy](https://www.w3.org/TR/referrer-policy/#referrer-policy-header)
+    ///
+    /// The `Referrer-Policy` HTTP header specifies the referrer policy that
+    /// the user agent applies when determining what referrer information
+    /// should be included with requests made, and with browsing contexts
+    /// created from the context of the protected resource.
+    ///
+    /// Senders may list fallback policies before the one they prefer, for user agents which do
+    /// not recognize it. The effective policy is the last one recognized, as returned by
+    /// `policy`.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Referrer-Policy = 1#policy-token
+    /// policy-token    = "no-referrer" / "no-referrer-when-downgrade"
+    ///                   / "strict-origin" / "strict-origin-when-cross-origin"
+    ///                   / "same-origin" / "origin"
+    ///                   / "origin-when-cross-origin" / "unsafe-url"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `no-referrer`
+    /// * `no-referrer, strict-origin-when-cross-origin`
+    (ReferrerPolicy, REFERRER_POLICY) => (ReferrerPolicyToken)*
+}
+
+impl ReferrerPolicy {
+    /// Returns the effective policy: the last recognized policy token.
+    ///
+    /// `None` is returned if no token is recognized, in which case the user agent falls back to
+    /// its default policy.
+    pub fn policy(&self) -> Option<&ReferrerPolicyToken> {
+        self.0.iter().rev().find(|t| t.is_known())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &ReferrerPolicy(vec![ReferrerPolicyToken::NO_REFERRER]),
+            &["no-referrer"],
+        );
+        util::test_round_trip(
+            &ReferrerPolicy(vec![
+                ReferrerPolicyToken::NO_REFERRER,
+                ReferrerPolicyToken::STRICT_ORIGIN_WHEN_CROSS_ORIGIN,
+            ]),
+            &["no-referrer, strict-origin-when-cross-origin"],
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        let header = ReferrerPolicy(vec![
+            ReferrerPolicyToken::ORIGIN,
+            ReferrerPolicyToken::STRICT_ORIGIN,
+            ReferrerPolicyToken::new("future-policy").unwrap(),
+        ]);
+        util::test_round_trip(&header, &["origin, strict-origin, future-policy"]);
+        assert_eq!(header.policy(), Some(&ReferrerPolicyToken::STRICT_ORIGIN));
+
+        util::test_decode(
+            &["no-referrer", "Unsafe-URL"],
+            &ReferrerPolicy(vec![
+                ReferrerPolicyToken::NO_REFERRER,
+                ReferrerPolicyToken::UNSAFE_URL,
+            ]),
+        );
+
+        let header = ReferrerPolicy(vec![ReferrerPolicyToken::new("foo").unwrap()]);
+        assert_eq!(header.policy(), None);
+        assert_eq!(ReferrerPolicy(vec![]).policy(), None);
+    }
+}
@@ -1,6 +1,6 @@
 use http::header::{HeaderName, HeaderValue, ValueIter, RETRY_AFTER};
 
-use crate::{Error, Header, ToValues};
+use crate::{util, Error, Header, ToValues};
 use super::HttpDate;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,15 +23,16 @@ impl Header for RetryAfter {
             None => return Ok(None),
         };
 
-        let value = value.to_str().map_err(|_| Error::invalid_value())?;
+        let value = util::to_str(value).map_err(|e| e.with_index(0))?;
+
+        if let Ok(delay) = value.parse::<u64>() {
+            return Ok(Some(RetryAfter::DelaySeconds(delay)));
+        }
 
         value
-            .parse::<u64>()
-            .ok()
-            .map(RetryAfter::DelaySeconds)
-            .or_else(|| value.parse::<HttpDate>().ok().map(RetryAfter::HttpDate))
-            .map(Some)
-            .ok_or_else(|| Error::invalid_value())
+            .parse::<HttpDate>()
+            .map(|date| Some(RetryAfter::HttpDate(date)))
+            .map_err(|e| e.with_index(0))
     }
 
     #[inline]
@@ -0,0 +1,621 @@
+use http::header::{self, HeaderName, HeaderValue, SET_COOKIE};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use super::HttpDate;
+use crate::{util, Error, Header, ToValues};
+
+// The limits recommended by RFC6265bis section 5.6.
+const MAX_NAME_VALUE_LEN: usize = 4096;
+const MAX_ATTRIBUTE_VALUE_LEN: usize = 1024;
+
+/// The value of a cookie's `SameSite` attribute.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    /// The cookie is only sent with same-site requests.
+    Strict,
+    /// The cookie is sent with same-site requests and top-level cross-site navigations.
+    Lax,
+    /// The cookie is sent with all requests. It must also be `Secure`.
+    None,
+}
+
+impl SameSite {
+    fn parse(s: &str) -> Option<SameSite> {
+        if s.eq_ignore_ascii_case("strict") {
+            Some(SameSite::Strict)
+        } else if s.eq_ignore_ascii_case("lax") {
+            Some(SameSite::Lax)
+        } else if s.eq_ignore_ascii_case("none") {
+            Some(SameSite::None)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for SameSite {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        };
+        fmt.write_str(s)
+    }
+}
+
+/// A cookie sent by a server in a `Set-Cookie` header, as defined in [RFC6265bis].
+///
+/// The cookie's name and value are validated strictly against the grammar servers are required
+/// to produce. Attributes are parsed with the lenient algorithm used by user agents: unknown
+/// attributes are preserved as extensions, and known attributes with invalid values are
+/// ignored.
+///
+/// [RFC6265bis]: https://tools.ietf.org/html/draft-ietf-httpbis-rfc6265bis-12#section-4.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCookie {
+    name: String,
+    value: String,
+    expires: Option<HttpDate>,
+    max_age: Option<i64>,
+    domain: Option<String>,
+    path: Option<String>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+    partitioned: bool,
+    extensions: Vec<String>,
+}
+
+impl ResponseCookie {
+    /// Creates a new cookie with no attributes.
+    ///
+    /// The name must be a token, and the value must consist of cookie-octets, optionally
+    /// surrounded by double quotes, which are considered part of the value. An error is returned
+    /// if either is invalid, or if they are longer than 4096 bytes combined.
+    pub fn new(name: &str, value: &str) -> Result<ResponseCookie, Error> {
+        validate_name(name)?;
+        validate_value(value).map_err(|e| e.offset_by(name.len() + 1))?;
+        if name.len() + value.len() > MAX_NAME_VALUE_LEN {
+            return Err(Error::invalid_value());
+        }
+
+        Ok(ResponseCookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            expires: None,
+            max_age: None,
+            domain: None,
+            path: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+            partitioned: false,
+            extensions: vec![],
+        })
+    }
+
+    /// Returns the name of the cookie.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the cookie.
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the value of the `Expires` attribute.
+    #[inline]
+    pub fn expires(&self) -> Option<&HttpDate> {
+        self.expires.as_ref()
+    }
+
+    /// Sets the value of the `Expires` attribute.
+    #[inline]
+    pub fn set_expires(&mut self, expires: Option<HttpDate>) {
+        self.expires = expires;
+    }
+
+    /// Returns the value of the `Max-Age` attribute, in seconds.
+    ///
+    /// A value of zero or less indicates that the cookie has expired.
+    #[inline]
+    pub fn max_age(&self) -> Option<i64> {
+        self.max_age
+    }
+
+    /// Sets the value of the `Max-Age` attribute, in seconds.
+    #[inline]
+    pub fn set_max_age(&mut self, max_age: Option<i64>) {
+        self.max_age = max_age;
+    }
+
+    /// Returns the value of the `Domain` attribute.
+    ///
+    /// Any leading `.` is removed, and the domain is lowercased.
+    #[inline]
+    pub fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+
+    /// Sets the value of the `Domain` attribute.
+    ///
+    /// An error is returned if the domain is empty or contains control characters or `;`.
+    pub fn set_domain(&mut self, domain: Option<&str>) -> Result<(), Error> {
+        self.domain = match domain {
+            Some(domain) => {
+                let domain = domain.strip_prefix('.').unwrap_or(domain);
+                if domain.is_empty() {
+                    return Err(Error::invalid_value());
+                }
+                validate_attribute_value(domain)?;
+                Some(domain.to_ascii_lowercase())
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Returns the value of the `Path` attribute.
+    #[inline]
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Sets the value of the `Path` attribute.
+    ///
+    /// An error is returned if the path does not start with `/`, or contains control characters
+    /// or `;`.
+    pub fn set_path(&mut self, path: Option<&str>) -> Result<(), Error> {
+        self.path = match path {
+            Some(path) => {
+                if !path.starts_with('/') {
+                    return Err(Error::invalid_value());
+                }
+                validate_attribute_value(path)?;
+                Some(path.to_string())
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Determines if the `Secure` attribute is present.
+    #[inline]
+    pub fn secure(&self) -> bool {
+        self.secure
+    }
+
+    /// Sets the presence of the `Secure` attribute.
+    #[inline]
+    pub fn set_secure(&mut self, secure: bool) {
+        self.secure = secure;
+    }
+
+    /// Determines if the `HttpOnly` attribute is present.
+    #[inline]
+    pub fn http_only(&self) -> bool {
+        self.http_only
+    }
+
+    /// Sets the presence of the `HttpOnly` attribute.
+    #[inline]
+    pub fn set_http_only(&mut self, http_only: bool) {
+        self.http_only = http_only;
+    }
+
+    /// Returns the value of the `SameSite` attribute.
+    #[inline]
+    pub fn same_site(&self) -> Option<SameSite> {
+        self.same_site
+    }
+
+    /// Sets the value of the `SameSite` attribute.
+    #[inline]
+    pub fn set_same_site(&mut self, same_site: Option<SameSite>) {
+        self.same_site = same_site;
+    }
+
+    /// Determines if the `Partitioned` attribute is present.
+    #[inline]
+    pub fn partitioned(&self) -> bool {
+        self.partitioned
+    }
+
+    /// Sets the presence of the `Partitioned` attribute.
+    #[inline]
+    pub fn set_partitioned(&mut self, partitioned: bool) {
+        self.partitioned = partitioned;
+    }
+
+    /// Returns the unrecognized attributes of the cookie, as they appeared in the header.
+    #[inline]
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Adds an extension attribute.
+    ///
+    /// An error is returned if the attribute is empty or contains control characters or `;`.
+    pub fn add_extension(&mut self, extension: &str) -> Result<(), Error> {
+        if extension.trim().is_empty() {
+            return Err(Error::invalid_value());
+        }
+        validate_attribute_value(extension)?;
+        self.extensions.push(extension.to_string());
+        Ok(())
+    }
+
+    fn apply_attribute(&mut self, name: &str, value: &str) {
+        if value.len() > MAX_ATTRIBUTE_VALUE_LEN {
+            return;
+        }
+
+        if name.eq_ignore_ascii_case("expires") {
+            if let Some(date) = HttpDate::parse_cookie_date(value) {
+                self.expires = Some(date);
+            }
+        } else if name.eq_ignore_ascii_case("max-age") {
+            if let Some(max_age) = parse_max_age(value) {
+                self.max_age = Some(max_age);
+            }
+        } else if name.eq_ignore_ascii_case("domain") {
+            let domain = value.strip_prefix('.').unwrap_or(value);
+            if !domain.is_empty() {
+                self.domain = Some(domain.to_ascii_lowercase());
+            }
+        } else if name.eq_ignore_ascii_case("path") {
+            self.path = if value.starts_with('/') {
+                Some(value.to_string())
+            } else {
+                None
+            };
+        } else if name.eq_ignore_ascii_case("secure") {
+            self.secure = true;
+        } else if name.eq_ignore_ascii_case("httponly") {
+            self.http_only = true;
+        } else if name.eq_ignore_ascii_case("samesite") {
+            if let Some(same_site) = SameSite::parse(value) {
+                self.same_site = Some(same_site);
+            }
+        } else if name.eq_ignore_ascii_case("partitioned") {
+            self.partitioned = true;
+        }
+    }
+}
+
+pub(crate) fn validate_name(name: &str) -> Result<(), Error> {
+    if util::is_token(name) {
+        Ok(())
+    } else {
+        Err(util::invalid_token(name))
+    }
+}
+
+pub(crate) fn validate_value(value: &str) -> Result<(), Error> {
+    let (unquoted, base) = if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        (&value[1..value.len() - 1], 1)
+    } else {
+        (value, 0)
+    };
+
+    match unquoted.bytes().position(|b| !is_cookie_octet(b)) {
+        Some(offset) => Err(Error::invalid_value().with_offset(base + offset)),
+        None => Ok(()),
+    }
+}
+
+// cookie-octet = %x21 / %x23-2B / %x2D-3A / %x3C-5B / %x5D-7E
+fn is_cookie_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x2b | 0x2d..=0x3a | 0x3c..=0x5b | 0x5d..=0x7e)
+}
+
+fn validate_attribute_value(s: &str) -> Result<(), Error> {
+    match s
+        .bytes()
+        .position(|b| b == b';' || b.is_ascii_control() || !b.is_ascii())
+    {
+        Some(offset) => Err(Error::invalid_value().with_offset(offset)),
+        None => Ok(()),
+    }
+}
+
+// Values which overflow are clamped, as they will either never or always be expired.
+fn parse_max_age(s: &str) -> Option<i64> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let value = digits.parse::<i64>().unwrap_or(i64::MAX);
+    Some(if negative { -value } else { value })
+}
+
+impl fmt::Display for ResponseCookie {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}={}", self.name, self.value)?;
+        if let Some(ref expires) = self.expires {
+            write!(fmt, "; Expires={}", expires)?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(fmt, "; Max-Age={}", max_age)?;
+        }
+        if let Some(ref domain) = self.domain {
+            write!(fmt, "; Domain={}", domain)?;
+        }
+        if let Some(ref path) = self.path {
+            write!(fmt, "; Path={}", path)?;
+        }
+        if self.secure {
+            fmt.write_str("; Secure")?;
+        }
+        if self.http_only {
+            fmt.write_str("; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(fmt, "; SameSite={}", same_site)?;
+        }
+        if self.partitioned {
+            fmt.write_str("; Partitioned")?;
+        }
+        for extension in &self.extensions {
+            write!(fmt, "; {}", extension)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ResponseCookie {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ResponseCookie, Error> {
+        let mut parts = s.split(';');
+        let pair = parts.next().unwrap();
+
+        let eq = match pair.find('=') {
+            Some(eq) => eq,
+            None => return Err(Error::invalid_value()),
+        };
+        let name = pair[..eq].trim_matches(is_wsp);
+        validate_name(name).map_err(|e| e.offset_by(leading_wsp(pair)))?;
+
+        let raw_value = &pair[eq + 1..];
+        let value = raw_value.trim_matches(is_wsp);
+        validate_value(value).map_err(|e| e.offset_by(eq + 1 + leading_wsp(raw_value)))?;
+
+        let mut cookie = ResponseCookie::new(name, value)?;
+
+        for attribute in parts {
+            let (name, value) = match attribute.find('=') {
+                Some(eq) => (&attribute[..eq], &attribute[eq + 1..]),
+                None => (attribute, ""),
+            };
+            let name = name.trim_matches(is_wsp);
+            let value = value.trim_matches(is_wsp);
+
+            if name.is_empty() {
+                continue;
+            }
+
+            let known = [
+                "expires",
+                "max-age",
+                "domain",
+                "path",
+                "secure",
+                "httponly",
+                "samesite",
+                "partitioned",
+            ];
+            if known.iter().any(|k| k.eq_ignore_ascii_case(name)) {
+                cookie.apply_attribute(name, value);
+            } else {
+                cookie
+                    .extensions
+                    .push(attribute.trim_matches(is_wsp).to_string());
+            }
+        }
+
+        Ok(cookie)
+    }
+}
+
+fn is_wsp(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+fn leading_wsp(s: &str) -> usize {
+    s.len() - s.trim_start_matches(is_wsp).len()
+}
+
+/// `Set-Cookie` header, defined in [RFC6265bis](https://tools.ietf.org/html/draft-ietf-httpbis-rfc6265bis-12#section-4.1)
+///
+/// The `Set-Cookie` HTTP response header is used to send cookies from
+/// the server to the user agent.
+///
+/// Unlike other headers, each cookie is sent in a separate `Set-Cookie` header field rather than
+/// being combined into a comma-separated list, since cookie attributes such as `Expires` can
+/// contain commas.
+///
+/// # ABNF
+///
+/// ```text
+/// set-cookie        = set-cookie-string
+/// set-cookie-string = BWS cookie-pair *( BWS ";" OWS cookie-av )
+/// cookie-pair       = cookie-name BWS "=" BWS cookie-value
+/// cookie-name       = token
+/// cookie-value      = *cookie-octet / ( DQUOTE *cookie-octet DQUOTE )
+/// cookie-av         = expires-av / max-age-av / domain-av /
+///                     path-av / secure-av / httponly-av /
+///                     samesite-av / extension-av
+/// ```
+///
+/// # Example values
+///
+/// * `SID=31d4d96e407aad42`
+/// * `SID=31d4d96e407aad42; Path=/; Secure; HttpOnly`
+/// * `lang=en-US; Expires=Wed, 09 Jun 2021 10:18:14 GMT`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookie(Vec<ResponseCookie>);
+
+impl SetCookie {
+    /// Creates a new `Set-Cookie` header.
+    ///
+    /// An error is returned if no cookies are provided.
+    #[inline]
+    pub fn new(cookies: Vec<ResponseCookie>) -> Result<SetCookie, Error> {
+        if cookies.is_empty() {
+            Err(Error::too_few_values())
+        } else {
+            Ok(SetCookie(cookies))
+        }
+    }
+}
+
+impl Deref for SetCookie {
+    type Target = Vec<ResponseCookie>;
+
+    #[inline]
+    fn deref(&self) -> &Vec<ResponseCookie> {
+        &self.0
+    }
+}
+
+impl From<ResponseCookie> for SetCookie {
+    #[inline]
+    fn from(cookie: ResponseCookie) -> SetCookie {
+        SetCookie(vec![cookie])
+    }
+}
+
+impl Header for SetCookie {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &SET_COOKIE
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<SetCookie>, Error> {
+        let mut cookies = vec![];
+        for (index, value) in values.enumerate() {
+            let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+            let cookie = value.parse().map_err(|e: Error| e.with_index(index))?;
+            cookies.push(cookie);
+        }
+
+        if cookies.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(SetCookie(cookies)))
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        for cookie in &self.0 {
+            util::encode_single_value(cookie, values);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &SetCookie::from(ResponseCookie::new("SID", "31d4d96e407aad42").unwrap()),
+            &["SID=31d4d96e407aad42"],
+        );
+
+        let mut sid = ResponseCookie::new("SID", "31d4d96e407aad42").unwrap();
+        sid.set_path(Some("/")).unwrap();
+        sid.set_secure(true);
+        sid.set_http_only(true);
+        let mut lang = ResponseCookie::new("lang", "en-US").unwrap();
+        lang.set_expires(Some("Wed, 09 Jun 2021 10:18:14 GMT".parse().unwrap()));
+        util::test_round_trip(
+            &SetCookie::new(vec![sid, lang]).unwrap(),
+            &[
+                "SID=31d4d96e407aad42; Path=/; Secure; HttpOnly",
+                "lang=en-US; Expires=Wed, 09 Jun 2021 10:18:14 GMT",
+            ],
+        );
+    }
+
+    #[test]
+    fn all_attributes() {
+        let mut cookie = ResponseCookie::new("a", "\"b\"").unwrap();
+        cookie.set_expires(Some("Wed, 09 Jun 2021 10:18:14 GMT".parse().unwrap()));
+        cookie.set_max_age(Some(60));
+        cookie.set_domain(Some(".Example.COM")).unwrap();
+        cookie.set_path(Some("/docs")).unwrap();
+        cookie.set_secure(true);
+        cookie.set_http_only(true);
+        cookie.set_same_site(Some(SameSite::Lax));
+        cookie.set_partitioned(true);
+        cookie.add_extension("Priority=High").unwrap();
+        util::test_round_trip(
+            &SetCookie::from(cookie),
+            &[
+                "a=\"b\"; Expires=Wed, 09 Jun 2021 10:18:14 GMT; Max-Age=60; \
+               Domain=example.com; Path=/docs; Secure; HttpOnly; SameSite=Lax; Partitioned; \
+               Priority=High",
+            ],
+        );
+    }
+
+    #[test]
+    fn lenient_attributes() {
+        let cookie = " a = b ;expires=Wed, 09-Jun-21 10:18:14 GMT;  max-age=-1 ; \
+                      domain=.EXAMPLE.com; path=docs; SECURE=yes; samesite=bogus; Foo = Bar"
+            .parse::<ResponseCookie>()
+            .unwrap();
+        assert_eq!(cookie.name(), "a");
+        assert_eq!(cookie.value(), "b");
+        assert_eq!(
+            cookie.expires(),
+            Some(&"Wed, 09 Jun 2021 10:18:14 GMT".parse().unwrap())
+        );
+        assert_eq!(cookie.max_age(), Some(-1));
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.path(), None);
+        assert!(cookie.secure());
+        assert_eq!(cookie.same_site(), None);
+        assert_eq!(cookie.extensions(), &["Foo = Bar".to_string()]);
+
+        let cookie = "a=b; Max-Age=1e3; Expires=garbage"
+            .parse::<ResponseCookie>()
+            .unwrap();
+        assert_eq!(cookie.max_age(), None);
+        assert_eq!(cookie.expires(), None);
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = ["a", "=b", "a b=c", "a=b c", "a=\"b", "a=b,c", "a=b\\c"];
+        for value in &cases {
+            assert!(value.parse::<ResponseCookie>().is_err(), "{}", value);
+        }
+
+        let long = "a".repeat(MAX_NAME_VALUE_LEN);
+        assert!(ResponseCookie::new("a", &long).is_err());
+
+        let mut cookie = ResponseCookie::new("a", "b").unwrap();
+        assert!(cookie.set_path(Some("docs")).is_err());
+        assert!(cookie.set_path(Some("/a;b")).is_err());
+        assert!(cookie.set_domain(Some(".")).is_err());
+        assert!(cookie.add_extension("a\nb").is_err());
+    }
+}
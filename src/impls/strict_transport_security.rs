@@ -0,0 +1,250 @@
+use http::header::{self, HeaderName, HeaderValue, STRICT_TRANSPORT_SECURITY};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind, Header, ToValues};
+
+/// `Strict-Transport-Security` header, defined in [RFC6797](https://tools.ietf.org/html/rfc6797#section-6.1)
+///
+/// The Strict-Transport-Security HTTP response header field (STS header field) indicates to a
+/// UA that it MUST enforce the HSTS Policy in regards to the host emitting the response message
+/// containing this header field.
+///
+/// Directive names are case-insensitive, and unrecognized directives are ignored. A value which
+/// repeats a directive is invalid.
+///
+/// # ABNF
+///
+/// ```text
+/// Strict-Transport-Security = "Strict-Transport-Security" ":"
+///                             [ directive ]  *( ";" [ directive ] )
+///
+/// directive                 = directive-name [ "=" directive-value ]
+/// directive-name            = token
+/// directive-value           = token | quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `max-age=31536000`
+/// * `max-age=63072000; includeSubDomains; preload`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictTransportSecurity {
+    max_age: u64,
+    include_subdomains: bool,
+    preload: bool,
+}
+
+impl StrictTransportSecurity {
+    /// Creates a policy which remains in effect for `max_age` seconds.
+    ///
+    /// A `max_age` of zero instructs the user agent to forget the host's policy.
+    #[inline]
+    pub fn new(max_age: u64) -> StrictTransportSecurity {
+        StrictTransportSecurity {
+            max_age,
+            include_subdomains: false,
+            preload: false,
+        }
+    }
+
+    /// Extends the policy to all subdomains of the host.
+    #[inline]
+    pub fn with_include_subdomains(mut self) -> StrictTransportSecurity {
+        self.include_subdomains = true;
+        self
+    }
+
+    /// Adds the `preload` directive, consenting to inclusion in browsers' HSTS preload lists.
+    #[inline]
+    pub fn with_preload(mut self) -> StrictTransportSecurity {
+        self.preload = true;
+        self
+    }
+
+    /// Returns the number of seconds the policy remains in effect.
+    #[inline]
+    pub fn max_age(&self) -> u64 {
+        self.max_age
+    }
+
+    /// Determines if the policy applies to all subdomains of the host.
+    #[inline]
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Determines if the `preload` directive is present.
+    #[inline]
+    pub fn preload(&self) -> bool {
+        self.preload
+    }
+}
+
+impl fmt::Display for StrictTransportSecurity {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "max-age={}", self.max_age)?;
+        if self.include_subdomains {
+            fmt.write_str("; includeSubDomains")?;
+        }
+        if self.preload {
+            fmt.write_str("; preload")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for StrictTransportSecurity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<StrictTransportSecurity, Error> {
+        let mut cursor = Cursor::new(s);
+        let mut max_age = None;
+        let mut include_subdomains = false;
+        let mut preload = false;
+        let mut seen = vec![];
+
+        loop {
+            cursor.ows();
+            if cursor.eat(b';') {
+                continue;
+            }
+            if cursor.is_empty() {
+                break;
+            }
+
+            let start = cursor.pos();
+            let name = cursor.token()?.to_ascii_lowercase();
+            cursor.ows();
+            let value = if cursor.eat(b'=') {
+                cursor.ows();
+                let value_start = cursor.pos();
+                let value = if cursor.peek() == Some(b'"') {
+                    cursor.quoted_string()?
+                } else {
+                    cursor.token()?.to_string()
+                };
+                cursor.ows();
+                Some((value, value_start))
+            } else {
+                None
+            };
+            if !cursor.is_empty() {
+                cursor.expect(b';')?;
+            }
+
+            if seen.contains(&name) {
+                return Err(Error::new(ErrorKind::DuplicateParameter).with_offset(start));
+            }
+
+            match (&*name, value) {
+                ("max-age", Some((value, value_start))) => {
+                    let seconds =
+                        util::parse_digits(&value).map_err(|e| e.offset_by(value_start))?;
+                    max_age = Some(seconds);
+                }
+                ("includesubdomains", None) => include_subdomains = true,
+                ("preload", None) => preload = true,
+                ("max-age", None) | ("includesubdomains", Some(_)) | ("preload", Some(_)) => {
+                    return Err(Error::invalid_value().with_offset(start));
+                }
+                _ => {}
+            }
+            seen.push(name);
+        }
+
+        match max_age {
+            Some(max_age) => Ok(StrictTransportSecurity {
+                max_age,
+                include_subdomains,
+                preload,
+            }),
+            None => Err(Error::invalid_value()),
+        }
+    }
+}
+
+impl Header for StrictTransportSecurity {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &STRICT_TRANSPORT_SECURITY
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<StrictTransportSecurity>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &StrictTransportSecurity::new(31536000),
+            &["max-age=31536000"],
+        );
+        util::test_round_trip(
+            &StrictTransportSecurity::new(15768000).with_include_subdomains(),
+            &["max-age=15768000; includeSubDomains"],
+        );
+        util::test_round_trip(
+            &StrictTransportSecurity::new(63072000)
+                .with_include_subdomains()
+                .with_preload(),
+            &["max-age=63072000; includeSubDomains; preload"],
+        );
+        util::test_decode(
+            &["max-age=\"31536000\""],
+            &StrictTransportSecurity::new(31536000),
+        );
+        util::test_decode(&["max-age=0"], &StrictTransportSecurity::new(0));
+    }
+
+    #[test]
+    fn lenient() {
+        util::test_decode(
+            &[" INCLUDESUBDOMAINS ;; Max-Age = 60 ; foo=\"bar\"; baz ;"],
+            &StrictTransportSecurity::new(60).with_include_subdomains(),
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            "",
+            "includeSubDomains",
+            "max-age",
+            "max-age=",
+            "max-age=-1",
+            "max-age=1.5",
+            "max-age=60; max-age=60",
+            "max-age=60; includeSubDomains; includesubdomains",
+            "max-age=60; foo; FOO",
+            "max-age=60; preload=true",
+            "max-age=60 includeSubDomains",
+        ];
+        for value in &cases {
+            assert!(
+                value.parse::<StrictTransportSecurity>().is_err(),
+                "{}",
+                value
+            );
+        }
+
+        let err = "max-age=60; max-age=60"
+            .parse::<StrictTransportSecurity>()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DuplicateParameter);
+    }
+}
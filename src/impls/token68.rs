@@ -18,6 +18,7 @@ impl Token68 {
             return Err(InvalidToken68(()));
         }
 
+        #[allow(clippy::match_like_matches_macro)]
         let ok = trimmed.as_bytes().iter().all(|b| match b {
             b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'-' | b'.' | b'_' | b'~' | b'+' | b'/' => {
                 true
@@ -54,6 +55,7 @@ impl FromStr for Token68 {
     }
 }
 
+/// An error returned when parsing an invalid token68 value.
 #[derive(Debug)]
 pub struct InvalidToken68(());
 
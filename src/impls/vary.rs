@@ -0,0 +1,227 @@
+use http::header::{self, HeaderMap, HeaderName, HeaderValue, VARY};
+
+use crate::{util, Error, Header, ToValues};
+
+/// `Vary` header, defined in [RFC7231](https://tools.ietf.org/html/rfc7231#section-7.1.4)
+///
+/// The `Vary` header field in a response describes what parts of a
+/// request message, aside from the method, Host header field, and
+/// request target, might influence the origin server's process for
+/// selecting and representing this response.  The value consists of
+/// either a single asterisk ("*") or a list of header field names
+/// (case-insensitive).
+///
+/// A `*` appearing anywhere in the list is treated as if it were the only value.
+///
+/// # ABNF
+///
+/// ```text
+/// Vary = "*" / 1#field-name
+/// ```
+///
+/// # Example values
+///
+/// * `accept-encoding, accept-language`
+/// * `*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vary {
+    /// The response varies on aspects of the request other than its header fields.
+    Any,
+    /// The response varies on the listed request header fields.
+    Headers(Vec<HeaderName>),
+}
+
+impl Vary {
+    /// Derives the secondary cache key of a request.
+    ///
+    /// The key consists of the values of the request header fields nominated by this header.
+    /// Field names are deduplicated and sorted, and the values of each field are trimmed and
+    /// combined into a single comma-separated value. A field which is absent from the request is
+    /// distinguished from one which is present but empty.
+    ///
+    /// `None` is returned for `Vary: *`, as a response with that header can never be reused.
+    pub fn secondary_key(&self, request: &HeaderMap) -> Option<SecondaryKey> {
+        let names = match *self {
+            Vary::Any => return None,
+            Vary::Headers(ref names) => names,
+        };
+
+        let mut names = names.iter().collect::<Vec<_>>();
+        names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        names.dedup();
+
+        let fields = names
+            .into_iter()
+            .map(|name| (name.clone(), combined_value(request, name)))
+            .collect();
+        Some(SecondaryKey(fields))
+    }
+
+    /// Determines if a stored response with this header can be used to satisfy a new request.
+    ///
+    /// `original` contains the headers of the request which produced the stored response, and
+    /// `presented` contains the headers of the new request. Responses with `Vary: *` never match.
+    pub fn matches(&self, original: &HeaderMap, presented: &HeaderMap) -> bool {
+        match self.secondary_key(original) {
+            Some(key) => key.matches(presented),
+            None => false,
+        }
+    }
+}
+
+fn combined_value(request: &HeaderMap, name: &HeaderName) -> Option<Vec<u8>> {
+    let mut out: Option<Vec<u8>> = None;
+    for value in request.get_all(name) {
+        let value = trim(value.as_bytes());
+        match out {
+            Some(ref mut out) => {
+                out.extend_from_slice(b", ");
+                out.extend_from_slice(value);
+            }
+            None => out = Some(value.to_vec()),
+        }
+    }
+    out
+}
+
+fn trim(mut value: &[u8]) -> &[u8] {
+    while let Some((&b, rest)) = value.split_first() {
+        if b != b' ' && b != b'\t' {
+            break;
+        }
+        value = rest;
+    }
+    while let Some((&b, rest)) = value.split_last() {
+        if b != b' ' && b != b'\t' {
+            break;
+        }
+        value = rest;
+    }
+    value
+}
+
+/// A normalized secondary cache key, derived from a request by `Vary::secondary_key`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecondaryKey(Vec<(HeaderName, Option<Vec<u8>>)>);
+
+impl SecondaryKey {
+    /// Determines if a request has the same secondary cache key.
+    pub fn matches(&self, request: &HeaderMap) -> bool {
+        self.0
+            .iter()
+            .all(|(name, value)| combined_value(request, name) == *value)
+    }
+
+    /// Returns an iterator over the field names and normalized values making up the key.
+    ///
+    /// The value is `None` if the field was absent from the request.
+    pub fn fields(&self) -> impl Iterator<Item = (&HeaderName, Option<&[u8]>)> {
+        self.0.iter().map(|(name, value)| (name, value.as_deref()))
+    }
+}
+
+impl Header for Vary {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &VARY
+    }
+
+    #[inline]
+    fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<Vary>, Error> {
+        let names = match util::parse_comma_delimited::<HeaderName>(values)? {
+            Some(names) => names,
+            None => return Ok(None),
+        };
+
+        if names.is_empty() {
+            Err(Error::too_few_values())
+        } else if names.iter().any(|name| name == "*") {
+            Ok(Some(Vary::Any))
+        } else {
+            Ok(Some(Vary::Headers(names)))
+        }
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        match *self {
+            Vary::Any => values.append(HeaderValue::from_static("*")),
+            Vary::Headers(ref names) => util::encode_comma_delimited(names, values),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::{ACCEPT_ENCODING, ACCEPT_LANGUAGE, USER_AGENT};
+
+    fn request(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &(name, value) in pairs {
+            headers.append(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(
+            &Vary::Headers(vec![ACCEPT_ENCODING, ACCEPT_LANGUAGE]),
+            &["accept-encoding, accept-language"],
+        );
+        util::test_round_trip(&Vary::Any, &["*"]);
+    }
+
+    #[test]
+    fn decode() {
+        util::test_decode(
+            &["Accept-Encoding,, User-Agent", "accept-language"],
+            &Vary::Headers(vec![ACCEPT_ENCODING, USER_AGENT, ACCEPT_LANGUAGE]),
+        );
+        util::test_decode(&["accept-encoding, *"], &Vary::Any);
+    }
+
+    #[test]
+    fn secondary_key() {
+        let vary = Vary::Headers(vec![ACCEPT_LANGUAGE, ACCEPT_ENCODING, ACCEPT_LANGUAGE]);
+        let key = vary
+            .secondary_key(&request(&[
+                ("Accept-Encoding", " gzip "),
+                ("Accept-Encoding", "br\t"),
+                ("User-Agent", "foo"),
+            ]))
+            .unwrap();
+        assert_eq!(
+            key.fields().collect::<Vec<_>>(),
+            vec![
+                (&ACCEPT_ENCODING, Some(&b"gzip, br"[..])),
+                (&ACCEPT_LANGUAGE, None),
+            ]
+        );
+
+        assert_eq!(Vary::Any.secondary_key(&request(&[])), None);
+    }
+
+    #[test]
+    fn matches() {
+        let vary = Vary::Headers(vec![ACCEPT_ENCODING, ACCEPT_LANGUAGE]);
+        let original = request(&[("Accept-Encoding", "gzip, br"), ("User-Agent", "a")]);
+
+        assert!(vary.matches(&original, &request(&[("Accept-Encoding", "gzip, br ")])));
+        assert!(vary.matches(
+            &original,
+            &request(&[("Accept-Encoding", "gzip"), ("Accept-Encoding", "br")])
+        ));
+        assert!(!vary.matches(&original, &request(&[("Accept-Encoding", "br, gzip")])));
+        assert!(!vary.matches(
+            &original,
+            &request(&[("Accept-Encoding", "gzip, br"), ("Accept-Language", "")])
+        ));
+        assert!(!Vary::Any.matches(&original, &original));
+
+        let key = vary.secondary_key(&original).unwrap();
+        assert!(key.matches(&request(&[("Accept-Encoding", "gzip, br")])));
+        assert!(!key.matches(&request(&[("Accept-Encoding", "gzip")])));
+    }
+}
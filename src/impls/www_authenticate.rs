@@ -0,0 +1,56 @@
+use http::header::WWW_AUTHENTICATE;
+
+use super::{challenge, Challenge};
+
+header! {
+    /// `WWW-Authenticate` header, defined in [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.1)
+    ///
+    /// The `WWW-Authenticate` header field indicates the authentication
+    /// scheme(s) and parameters applicable to the target resource.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// WWW-Authenticate = 1#challenge
+    /// ```
+    ///
+    /// # Example values
+    /// * `Basic realm="simple"`
+    /// * `Newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm="simple"`
+    (WwwAuthenticate, WWW_AUTHENTICATE) => (Challenge)+ using challenge::parse_challenges
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{util, AuthParam, AuthScheme};
+
+    #[test]
+    fn basic() {
+        util::test_round_trip(
+            &WwwAuthenticate::from(Challenge::basic("simple").unwrap()),
+            &[r#"Basic realm="simple""#],
+        );
+    }
+
+    #[test]
+    fn rfc() {
+        let header = WwwAuthenticate::new(vec![
+            Challenge::from_auth_params(
+                AuthScheme::new("Newauth").unwrap(),
+                vec![
+                    AuthParam::quoted("realm", "apps").unwrap(),
+                    AuthParam::new("type", "1").unwrap(),
+                    AuthParam::new("title", "Login to \"apps\"").unwrap(),
+                ],
+            )
+            .unwrap(),
+            Challenge::basic("simple").unwrap(),
+        ])
+        .unwrap();
+        util::test_round_trip(
+            &header,
+            &[r#"newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm="simple""#],
+        );
+    }
+}
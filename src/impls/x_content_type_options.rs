@@ -0,0 +1,56 @@
+use http::header::{self, HeaderName, HeaderValue, X_CONTENT_TYPE_OPTIONS};
+
+use crate::{util, Error, Header, ToValues};
+
+token! {
+    /// `X-Content-Type-Options` header, defined in the
+    /// [Fetch Standard](https://fetch.spec.whatwg.org/#x-content-type-options-header)
+    ///
+    /// The `X-Content-Type-Options` response header can be used to require
+    /// checking of a response's `Content-Type` header against the destination
+    /// of a request.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Content-Type-Options = "nosniff" ; case-insensitive
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `nosniff`
+    XContentTypeOptions => {
+        /// Blocks responses whose `Content-Type` does not match the request's destination.
+        NOSNIFF => "nosniff" => [],
+    }
+}
+
+impl Header for XContentTypeOptions {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &X_CONTENT_TYPE_OPTIONS
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<XContentTypeOptions>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&XContentTypeOptions::NOSNIFF, &["nosniff"]);
+        util::test_decode(&["NoSniff"], &XContentTypeOptions::NOSNIFF);
+    }
+}
@@ -0,0 +1,72 @@
+use http::header::HeaderName;
+use std::net::IpAddr;
+
+static X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+header! {
+    /// `X-Forwarded-For` header, a de facto standard
+    ///
+    /// The `X-Forwarded-For` header identifies the addresses of the client and proxies through
+    /// which a request has passed. Each proxy appends the address it received the request from,
+    /// so the first address is the one claimed for the original client.
+    ///
+    /// The `Forwarded` header is the standardized replacement for this header.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Forwarded-For = 1#( IPv4address / IPv6address )
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `203.0.113.195`
+    /// * `203.0.113.195, 2001:db8:85a3:8d3:1319:8a2e:370:7348, 198.51.100.178`
+    (XForwardedFor, X_FORWARDED_FOR) => (IpAddr)+
+}
+
+impl XForwardedFor {
+    /// Appends an address to the header, as a proxy does when forwarding a request.
+    #[inline]
+    pub fn push(&mut self, addr: IpAddr) {
+        self.0.push(addr);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+    use crate::HeaderMapExt;
+    use http::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn basic() {
+        util::test_round_trip(
+            &XForwardedFor::new(vec![
+                "203.0.113.195".parse().unwrap(),
+                "2001:db8:85a3:8d3:1319:8a2e:370:7348".parse().unwrap(),
+                "198.51.100.178".parse().unwrap(),
+            ])
+            .unwrap(),
+            &["203.0.113.195, 2001:db8:85a3:8d3:1319:8a2e:370:7348, 198.51.100.178"],
+        );
+        util::test_decode(
+            &["203.0.113.195", "198.51.100.178"],
+            &XForwardedFor::new(vec![
+                "203.0.113.195".parse().unwrap(),
+                "198.51.100.178".parse().unwrap(),
+            ])
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for value in &["unknown", "203.0.113.195:80", "[2001:db8::1]", ""] {
+            let mut map = HeaderMap::new();
+            map.insert(&X_FORWARDED_FOR, HeaderValue::from_static(value));
+            assert!(map.typed_get::<XForwardedFor>().is_err(), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use http::header::HeaderName;
+
+use super::Host;
+
+static X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+
+header! {
+    /// `X-Forwarded-Host` header, a de facto standard
+    ///
+    /// The `X-Forwarded-Host` header identifies the host originally requested by the client in
+    /// the `Host` header of a request forwarded by a proxy. A proxy which forwards the request on
+    /// may append the host it was received with, in the same way as `X-Forwarded-For`.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Forwarded-Host = 1#( uri-host [ ":" port ] )
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `example.com`
+    /// * `example.com:8080, internal`
+    (XForwardedHost, X_FORWARDED_HOST) => (Host)+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn basic() {
+        util::test_round_trip(
+            &XForwardedHost::from(Host::new("example.com", Some(8080)).unwrap()),
+            &["example.com:8080"],
+        );
+        util::test_round_trip(
+            &XForwardedHost::new(vec![
+                Host::new("example.com", Some(8080)).unwrap(),
+                Host::new("internal", None).unwrap(),
+            ])
+            .unwrap(),
+            &["example.com:8080, internal"],
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use http::header::{self, HeaderName, HeaderValue};
+use http::uri::Scheme;
+
+use crate::{util, Error};
+
+static X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+header! {
+    /// `X-Forwarded-Proto` header, a de facto standard
+    ///
+    /// The `X-Forwarded-Proto` header identifies the protocol used by the client to connect to a
+    /// proxy. A proxy which forwards the request on may append the protocol it was received with,
+    /// in the same way as `X-Forwarded-For`. Schemes are normalized to lowercase when parsed.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Forwarded-Proto = 1#scheme
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `https`
+    /// * `https, http`
+    (XForwardedProto, X_FORWARDED_PROTO) => (Scheme)+ using parse_schemes
+}
+
+fn parse_schemes(
+    values: &mut header::ValueIter<HeaderValue>,
+) -> Result<Option<Vec<Scheme>>, Error> {
+    util::parse_comma_delimited_with(values, util::parse_scheme)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HeaderMapExt;
+    use http::HeaderMap;
+
+    #[test]
+    fn basic() {
+        util::test_round_trip(&XForwardedProto::from(Scheme::HTTPS), &["https"]);
+        util::test_decode(&[" HTTPS "], &XForwardedProto::from(Scheme::HTTPS));
+        util::test_round_trip(
+            &XForwardedProto::new(vec![Scheme::HTTPS, Scheme::HTTP]).unwrap(),
+            &["https, http"],
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for value in &["", "https, 1http", "1http"] {
+            let mut map = HeaderMap::new();
+            map.insert(&X_FORWARDED_PROTO, HeaderValue::from_static(value));
+            assert!(map.typed_get::<XForwardedProto>().is_err(), "{}", value);
+        }
+    }
+}
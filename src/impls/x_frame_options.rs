@@ -0,0 +1,62 @@
+use http::header::{self, HeaderName, HeaderValue, X_FRAME_OPTIONS};
+
+use crate::{util, Error, Header, ToValues};
+
+token! {
+    /// `X-Frame-Options` header, defined in [RFC7034](https://tools.ietf.org/html/rfc7034#section-2)
+    ///
+    /// The X-Frame-Options HTTP header field indicates a policy that
+    /// specifies whether the browser should render the transmitted resource
+    /// within a <frame> or an <iframe>.
+    ///
+    /// The `frame-ancestors` directive of `Content-Security-Policy` supersedes this header.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Frame-Options = "DENY" / "SAMEORIGIN"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `DENY`
+    /// * `SAMEORIGIN`
+    XFrameOptions => {
+        /// The page cannot be displayed in a frame.
+        DENY => "DENY" => [],
+        /// The page can only be displayed in a frame on the same origin as the page itself.
+        SAMEORIGIN => "SAMEORIGIN" => [],
+    }
+}
+
+impl Header for XFrameOptions {
+    #[inline]
+    fn name() -> &'static HeaderName {
+        &X_FRAME_OPTIONS
+    }
+
+    #[inline]
+    fn from_values(
+        values: &mut header::ValueIter<HeaderValue>,
+    ) -> Result<Option<XFrameOptions>, Error> {
+        util::parse_single_value(values)
+    }
+
+    #[inline]
+    fn to_values(&self, values: &mut ToValues) {
+        util::encode_single_value(self, values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc() {
+        util::test_round_trip(&XFrameOptions::DENY, &["DENY"]);
+        util::test_round_trip(&XFrameOptions::SAMEORIGIN, &["SAMEORIGIN"]);
+        util::test_decode(&["sameorigin"], &XFrameOptions::SAMEORIGIN);
+        util::test_round_trip(&XFrameOptions::new("Foo").unwrap(), &["foo"]);
+    }
+}
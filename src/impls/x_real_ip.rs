@@ -0,0 +1,38 @@
+use http::header::HeaderName;
+use std::net::IpAddr;
+
+static X_REAL_IP: HeaderName = HeaderName::from_static("x-real-ip");
+
+header! {
+    /// `X-Real-IP` header, a de facto standard
+    ///
+    /// The `X-Real-IP` header identifies the address of the client which made a request to a
+    /// proxy.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Real-IP = IPv4address / IPv6address
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `203.0.113.195`
+    /// * `2001:db8::1`
+    (XRealIp, X_REAL_IP) => [IpAddr]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util;
+
+    #[test]
+    fn basic() {
+        util::test_round_trip(
+            &XRealIp("203.0.113.195".parse().unwrap()),
+            &["203.0.113.195"],
+        );
+        util::test_round_trip(&XRealIp("2001:db8::1".parse().unwrap()), &["2001:db8::1"]);
+    }
+}
@@ -21,7 +21,13 @@ use std::mem;
 
 pub use impls::*;
 
+pub mod conditional;
+pub mod cookie_jar;
+pub mod cors;
+pub mod freshness;
 mod impls;
+pub mod proxy;
+pub mod structured;
 pub mod util;
 
 pub trait Header {
@@ -55,48 +61,196 @@ pub trait Header {
     fn to_values(&self, values: &mut ToValues);
 }
 
-#[derive(Debug)]
-enum ErrorKind {
+/// The category of a header error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The value was malformed in a way not covered by a more specific kind.
     InvalidValue,
+    /// The value contained bytes which are not visible ASCII.
+    NonUtf8,
+    /// A token was expected but the value contained invalid characters.
+    InvalidToken,
+    /// A quoted-string was unterminated or contained invalid characters.
+    InvalidQuotedString,
+    /// A quality value (`q=...`) was malformed.
+    InvalidQuality,
+    /// An integer was malformed or out of range.
+    InvalidInteger,
+    /// An HTTP date was malformed.
+    InvalidDate,
+    /// A media type was malformed.
+    InvalidMediaType,
+    /// A token68 value was malformed.
+    InvalidToken68,
+    /// A URI component such as an authority was malformed.
+    InvalidUri,
+    /// A language tag or language range was malformed.
+    InvalidLanguageTag,
+    /// An entity tag was malformed.
+    InvalidEntityTag,
+    /// A parameter or directive appeared more than once.
+    DuplicateParameter,
+    /// Fewer header values were present than required.
     TooFewValues,
+    /// More header values were present than permitted.
     TooManyValues,
 }
 
+impl ErrorKind {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            ErrorKind::InvalidValue => "invalid header value",
+            ErrorKind::NonUtf8 => "header value is not visible ASCII",
+            ErrorKind::InvalidToken => "invalid token",
+            ErrorKind::InvalidQuotedString => "invalid quoted-string",
+            ErrorKind::InvalidQuality => "invalid quality value",
+            ErrorKind::InvalidInteger => "invalid integer",
+            ErrorKind::InvalidDate => "invalid HTTP date",
+            ErrorKind::InvalidMediaType => "invalid media type",
+            ErrorKind::InvalidToken68 => "invalid token68",
+            ErrorKind::InvalidUri => "invalid URI component",
+            ErrorKind::InvalidLanguageTag => "invalid language tag",
+            ErrorKind::InvalidEntityTag => "invalid entity tag",
+            ErrorKind::DuplicateParameter => "duplicate parameter",
+            ErrorKind::TooFewValues => "too few header values",
+            ErrorKind::TooManyValues => "too many header values",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ErrorInner {
+    kind: ErrorKind,
+    name: Option<HeaderName>,
+    index: Option<usize>,
+    offset: Option<usize>,
+    source: Option<Box<dyn error::Error + Sync + Send>>,
+}
+
 /// An error serializing or deserializing a header.
+///
+/// In addition to its kind, an error may record the name of the header, the index of the raw
+/// value which failed to parse, and the byte offset of the failure within that value.
 #[derive(Debug)]
-pub struct Error(ErrorKind);
+pub struct Error(Box<ErrorInner>);
 
 impl Error {
+    /// Creates a new error of the specified kind.
+    #[inline]
+    pub fn new(kind: ErrorKind) -> Error {
+        Error(Box::new(ErrorInner {
+            kind,
+            name: None,
+            index: None,
+            offset: None,
+            source: None,
+        }))
+    }
+
     #[inline]
     pub fn invalid_value() -> Error {
-        Error(ErrorKind::InvalidValue)
+        Error::new(ErrorKind::InvalidValue)
     }
 
     #[inline]
     pub fn too_few_values() -> Error {
-        Error(ErrorKind::TooFewValues)
+        Error::new(ErrorKind::TooFewValues)
     }
 
     #[inline]
     pub fn too_many_values() -> Error {
-        Error(ErrorKind::TooManyValues)
+        Error::new(ErrorKind::TooManyValues)
+    }
+
+    /// Sets the name of the header associated with the error.
+    #[inline]
+    pub fn with_header_name(mut self, name: &HeaderName) -> Error {
+        self.0.name = Some(name.clone());
+        self
+    }
+
+    /// Sets the index of the raw header value associated with the error.
+    #[inline]
+    pub fn with_index(mut self, index: usize) -> Error {
+        self.0.index = Some(index);
+        self
+    }
+
+    /// Sets the byte offset within the raw header value at which the error occurred.
+    #[inline]
+    pub fn with_offset(mut self, offset: usize) -> Error {
+        self.0.offset = Some(offset);
+        self
+    }
+
+    /// Sets the underlying cause of the error.
+    #[inline]
+    pub fn with_source<E>(mut self, source: E) -> Error
+    where
+        E: Into<Box<dyn error::Error + Sync + Send>>,
+    {
+        self.0.source = Some(source.into());
+        self
+    }
+
+    /// Shifts the error's byte offset by `base`, for errors produced while parsing a substring.
+    #[inline]
+    pub(crate) fn offset_by(mut self, base: usize) -> Error {
+        self.0.offset = Some(self.0.offset.unwrap_or(0) + base);
+        self
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.0.kind
+    }
+
+    /// Returns the name of the header associated with the error, if known.
+    #[inline]
+    pub fn header_name(&self) -> Option<&HeaderName> {
+        self.0.name.as_ref()
+    }
+
+    /// Returns the index of the raw header value associated with the error, if known.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.0.index
+    }
+
+    /// Returns the byte offset within the raw header value at which the error occurred, if known.
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        self.0.offset
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let s = match self.0 {
-            ErrorKind::InvalidValue => "invalid header value",
-            ErrorKind::TooFewValues => "too few header values",
-            ErrorKind::TooManyValues => "too many header values",
-        };
-        fmt.write_str(s)
+        fmt.write_str(self.0.kind.as_str())?;
+        if let Some(ref name) = self.0.name {
+            write!(fmt, " in `{}` header", name)?;
+        }
+        match (self.0.index, self.0.offset) {
+            (Some(index), Some(offset)) => write!(fmt, " (value {}, byte {})", index, offset)?,
+            (Some(index), None) => write!(fmt, " (value {})", index)?,
+            (None, Some(offset)) => write!(fmt, " (byte {})", offset)?,
+            (None, None) => {}
+        }
+        if let Some(ref source) = self.0.source {
+            write!(fmt, ": {}", source)?;
+        }
+        Ok(())
     }
 }
 
 impl error::Error for Error {
-    fn description(&self) -> &str {
-        "header error"
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self.0.source {
+            Some(ref source) => Some(&**source),
+            None => None,
+        }
     }
 }
 
@@ -153,13 +307,19 @@ impl HeaderMapExt for HeaderMap {
     where
         H: Header,
     {
-        let mut values = self.get_all(H::name()).iter();
+        let all = self.get_all(H::name());
+        let mut values = all.iter();
         match H::from_values(&mut values) {
             Ok(header) => match values.next() {
-                Some(_) => Err(Error::too_many_values()),
+                Some(_) => {
+                    let index = all.iter().count() - values.count() - 1;
+                    Err(Error::too_many_values()
+                        .with_header_name(H::name())
+                        .with_index(index))
+                }
                 None => Ok(header),
             },
-            Err(e) => Err(e),
+            Err(e) => Err(e.with_header_name(H::name())),
         }
     }
 
@@ -180,7 +340,7 @@ impl HeaderMapExt for HeaderMap {
             header::Entry::Occupied(entry) => {
                 let r = H::from_values(&mut entry.iter());
                 entry.remove();
-                r
+                r.map_err(|e| e.with_header_name(H::name()))
             }
             header::Entry::Vacant(_) => Ok(None),
         }
@@ -0,0 +1,468 @@
+//! Identification of the client of a request forwarded by proxies.
+//!
+//! Reverse proxies record the client they received a request from in the [`Forwarded`] header,
+//! or in the de facto `X-Forwarded-For`, `X-Forwarded-Proto`, `X-Forwarded-Host`, and
+//! `X-Real-IP` headers. Since clients can set these headers too, they can only be believed when
+//! they were added by a proxy the server trusts. A `TrustedProxies` set walks the proxy chain
+//! from the server back towards the client, and stops at the first address it does not trust.
+//!
+//! [`Forwarded`]: https://tools.ietf.org/html/rfc7239
+use http::header::HeaderMap;
+use http::uri::Scheme;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+use crate::{
+    util, Error, Forwarded, HeaderMapExt, Host, XForwardedFor, XForwardedHost, XForwardedProto,
+    XRealIp,
+};
+
+/// A range of IP addresses in CIDR notation, like `10.0.0.0/8` or `fd00::/8`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Creates a new range from a network address and a prefix length.
+    ///
+    /// Bits of the address beyond the prefix are cleared, and a range of IPv4-mapped IPv6
+    /// addresses is converted to the IPv4 range it maps. An error is returned if the prefix length
+    /// is larger than the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<IpCidr, Error> {
+        let addr = match addr {
+            IpAddr::V4(addr) if prefix_len <= 32 => {
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & v4_mask(prefix_len)))
+            }
+            IpAddr::V6(addr) if prefix_len <= 128 => match addr.to_ipv4_mapped() {
+                Some(addr) if prefix_len >= 96 => {
+                    return IpCidr::new(IpAddr::V4(addr), prefix_len - 96);
+                }
+                _ => IpAddr::V6(Ipv6Addr::from(u128::from(addr) & v6_mask(prefix_len))),
+            },
+            _ => return Err(Error::invalid_value()),
+        };
+
+        Ok(IpCidr { addr, prefix_len })
+    }
+
+    /// Returns the network address of the range.
+    #[inline]
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length of the range.
+    #[inline]
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Determines if an address is in the range.
+    ///
+    /// IPv4-mapped IPv6 addresses are treated as the IPv4 address they map, so they are contained
+    /// in the same ranges.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.addr, canonical(addr)) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                u32::from(addr) & v4_mask(self.prefix_len) == u32::from(net)
+            }
+            (IpAddr::V4(_), IpAddr::V6(_)) => false,
+            (IpAddr::V6(net), addr) => {
+                let addr = match addr {
+                    IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+                    IpAddr::V6(addr) => addr,
+                };
+                u128::from(addr) & v6_mask(self.prefix_len) == u128::from(net)
+            }
+        }
+    }
+}
+
+impl From<IpAddr> for IpCidr {
+    /// Creates a range containing only the specified address.
+    #[inline]
+    fn from(addr: IpAddr) -> IpCidr {
+        let addr = canonical(addr);
+        let prefix_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        IpCidr { addr, prefix_len }
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = Error;
+
+    /// Parses a range in CIDR notation. A bare address is parsed as a range containing only that
+    /// address.
+    fn from_str(s: &str) -> Result<IpCidr, Error> {
+        let (addr, prefix_len) = match s.find('/') {
+            Some(idx) => (&s[..idx], Some((idx + 1, &s[idx + 1..]))),
+            None => (s, None),
+        };
+
+        let addr = addr
+            .parse::<IpAddr>()
+            .map_err(|e| Error::invalid_value().with_source(e))?;
+        match prefix_len {
+            Some((offset, prefix_len)) => {
+                let prefix_len = util::parse_digits(prefix_len)
+                    .ok()
+                    .filter(|&len| len <= 128)
+                    .ok_or_else(|| Error::invalid_value().with_offset(offset))?;
+                IpCidr::new(addr, prefix_len as u8).map_err(|e| e.with_offset(offset))
+            }
+            None => Ok(IpCidr::from(addr)),
+        }
+    }
+}
+
+fn v4_mask(prefix_len: u8) -> u32 {
+    u32::MAX
+        .checked_shl(32 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+fn v6_mask(prefix_len: u8) -> u128 {
+    u128::MAX
+        .checked_shl(128 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+fn canonical(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => addr,
+        },
+        addr => addr,
+    }
+}
+
+/// The client of a request, as determined by `TrustedProxies::resolve`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Client {
+    ip: IpAddr,
+    scheme: Option<Scheme>,
+    host: Option<Host>,
+}
+
+impl Client {
+    /// Returns the IP address of the client.
+    ///
+    /// If the chain of proxies could not be followed all the way to an untrusted address, this is
+    /// the address of the last trusted proxy which could be identified.
+    #[inline]
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
+
+    /// Returns the scheme the client used to make the request, if reported by a trusted proxy.
+    #[inline]
+    pub fn scheme(&self) -> Option<&Scheme> {
+        self.scheme.as_ref()
+    }
+
+    /// Returns the host the client requested, if reported by a trusted proxy.
+    #[inline]
+    pub fn host(&self) -> Option<&Host> {
+        self.host.as_ref()
+    }
+}
+
+/// The set of proxies whose forwarding headers are trusted.
+///
+/// The default set is empty, so forwarding headers are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    /// The address ranges of the trusted proxies.
+    pub networks: Vec<IpCidr>,
+}
+
+impl TrustedProxies {
+    /// Creates a new set of trusted proxies.
+    #[inline]
+    pub fn new(networks: Vec<IpCidr>) -> TrustedProxies {
+        TrustedProxies { networks }
+    }
+
+    /// Determines if an address belongs to a trusted proxy.
+    pub fn is_trusted(&self, addr: IpAddr) -> bool {
+        self.networks.iter().any(|net| net.contains(addr))
+    }
+
+    /// Determines the client of a request received from `peer`.
+    ///
+    /// If the peer is not a trusted proxy, it is the client and the request headers are ignored.
+    /// Otherwise, the `Forwarded` header is used if present, and the `X-Forwarded-For`,
+    /// `X-Forwarded-Proto`, and `X-Forwarded-Host` headers if not. Addresses are walked from the
+    /// most recently added, and the first address which is not a trusted proxy is the client. The
+    /// scheme and host are taken from the `X-Forwarded-Proto` and `X-Forwarded-Host` entries for
+    /// that hop, or the most recently added entries if the headers have fewer entries than
+    /// `X-Forwarded-For`. If neither `Forwarded` nor `X-Forwarded-For` is present, the
+    /// `X-Real-IP` header identifies the client.
+    ///
+    /// An error is returned if a forwarding header which would be consulted is malformed.
+    pub fn resolve(&self, peer: SocketAddr, headers: &HeaderMap) -> Result<Client, Error> {
+        let mut client = Client {
+            ip: canonical(peer.ip()),
+            scheme: None,
+            host: None,
+        };
+        if !self.is_trusted(client.ip) {
+            return Ok(client);
+        }
+
+        if let Some(forwarded) = headers.typed_get::<Forwarded>()? {
+            for element in forwarded.iter().rev() {
+                if let Some(proto) = element.proto() {
+                    client.scheme = Some(proto.clone());
+                }
+                if let Some(host) = element.host() {
+                    client.host = Some(host.clone());
+                }
+
+                match element.forwarded_for().and_then(|node| node.ip_addr()) {
+                    Some(ip) => client.ip = canonical(ip),
+                    None => break,
+                }
+                if !self.is_trusted(client.ip) {
+                    break;
+                }
+            }
+        } else if let Some(forwarded_for) = headers.typed_get::<XForwardedFor>()? {
+            let mut hop = 0;
+            for (i, &ip) in forwarded_for.iter().rev().enumerate() {
+                hop = i;
+                client.ip = canonical(ip);
+                if !self.is_trusted(client.ip) {
+                    break;
+                }
+            }
+            client.scheme = headers
+                .typed_get::<XForwardedProto>()?
+                .map(|proto| hop_entry(&proto, hop).clone());
+            client.host = headers
+                .typed_get::<XForwardedHost>()?
+                .map(|host| hop_entry(&host, hop).clone());
+        } else if let Some(real_ip) = headers.typed_get::<XRealIp>()? {
+            client.ip = canonical(real_ip.0);
+        }
+
+        Ok(client)
+    }
+}
+
+// Returns the entry added for the hop `hop` places back from the most recent, or the most recent
+// entry if there are too few, as some proxies overwrite the header rather than appending to it.
+fn hop_entry<T>(entries: &[T], hop: usize) -> &T {
+    entries
+        .iter()
+        .rev()
+        .nth(hop)
+        .unwrap_or(&entries[entries.len() - 1])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &(name, value) in pairs {
+            headers.append(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn proxies() -> TrustedProxies {
+        TrustedProxies::new(vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "fd00::/8".parse().unwrap(),
+        ])
+    }
+
+    fn peer(s: &str) -> SocketAddr {
+        SocketAddr::new(s.parse().unwrap(), 4711)
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn cidr() {
+        let net = "192.168.1.77/24".parse::<IpCidr>().unwrap();
+        assert_eq!(net.to_string(), "192.168.1.0/24");
+        assert!(net.contains(ip("192.168.1.255")));
+        assert!(net.contains(ip("::ffff:192.168.1.1")));
+        assert!(!net.contains(ip("192.168.2.1")));
+        assert!(!net.contains(ip("fd00::1")));
+
+        let net = "::ffff:192.168.1.0/120".parse::<IpCidr>().unwrap();
+        assert_eq!(net.to_string(), "192.168.1.0/24");
+        assert!(net.contains(ip("192.168.1.1")));
+        assert!(net.contains(ip("::ffff:192.168.1.1")));
+        assert_eq!(
+            IpCidr::from(ip("::ffff:192.168.1.1")),
+            "192.168.1.1".parse().unwrap()
+        );
+
+        let net = "::/0".parse::<IpCidr>().unwrap();
+        assert!(net.contains(ip("192.168.1.1")));
+        assert!(net.contains(ip("fd00::1")));
+
+        let net = "2001:db8::/32".parse::<IpCidr>().unwrap();
+        assert!(net.contains(ip("2001:db8:ffff::1")));
+        assert!(!net.contains(ip("2001:db9::1")));
+
+        assert!("0.0.0.0/0"
+            .parse::<IpCidr>()
+            .unwrap()
+            .contains(ip("203.0.113.1")));
+        let net = "203.0.113.1".parse::<IpCidr>().unwrap();
+        assert_eq!(net.prefix_len(), 32);
+        assert!(net.contains(ip("203.0.113.1")));
+        assert!(!net.contains(ip("203.0.113.2")));
+
+        for s in &[
+            "10.0.0.0/33",
+            "::/129",
+            "10.0.0.0/",
+            "10.0.0.0/+8",
+            "10.0.0/8",
+            "",
+        ] {
+            assert!(s.parse::<IpCidr>().is_err(), "{}", s);
+        }
+        let err = "fd00:0:0::/200".parse::<IpCidr>().unwrap_err();
+        assert_eq!(err.offset(), Some(11));
+    }
+
+    #[test]
+    fn untrusted_peer() {
+        let headers = headers(&[
+            ("forwarded", "for=198.51.100.1;proto=https"),
+            ("x-forwarded-for", "198.51.100.1"),
+            ("x-real-ip", "198.51.100.1"),
+        ]);
+        let client = proxies().resolve(peer("203.0.113.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("203.0.113.1"));
+        assert_eq!(client.scheme(), None);
+        assert_eq!(client.host(), None);
+
+        let client = TrustedProxies::default()
+            .resolve(peer("10.0.0.1"), &headers)
+            .unwrap();
+        assert_eq!(client.ip(), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn forwarded() {
+        let headers = headers(&[
+            (
+                "forwarded",
+                "for=192.0.2.1, for=198.51.100.1;proto=https;host=example.com",
+            ),
+            (
+                "forwarded",
+                "for=\"[fd00::2]:1234\";proto=http;host=internal",
+            ),
+            ("x-forwarded-for", "203.0.113.99"),
+        ]);
+        let client = proxies().resolve(peer("10.0.0.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("198.51.100.1"));
+        assert_eq!(client.scheme(), Some(&Scheme::HTTPS));
+        assert_eq!(
+            client.host(),
+            Some(&Host::new("example.com", None).unwrap())
+        );
+    }
+
+    #[test]
+    fn forwarded_unidentified() {
+        let headers = headers(&[("forwarded", "for=_hidden, for=10.0.0.2;proto=https")]);
+        let client = proxies().resolve(peer("10.0.0.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("10.0.0.2"));
+        assert_eq!(client.scheme(), Some(&Scheme::HTTPS));
+    }
+
+    #[test]
+    fn x_forwarded_for() {
+        let headers = headers(&[
+            ("x-forwarded-for", "192.0.2.1, 198.51.100.1"),
+            ("x-forwarded-for", "::ffff:10.0.0.3, fd00::2"),
+            ("x-forwarded-proto", "HTTPS"),
+            ("x-forwarded-host", "example.com:8443"),
+        ]);
+        let client = proxies()
+            .resolve(peer("::ffff:10.0.0.1"), &headers)
+            .unwrap();
+        assert_eq!(client.ip(), ip("198.51.100.1"));
+        assert_eq!(client.scheme(), Some(&Scheme::HTTPS));
+        assert_eq!(
+            client.host(),
+            Some(&Host::new("example.com", Some(8443)).unwrap())
+        );
+
+        let headers = self::headers(&[("x-forwarded-for", "10.0.0.3, 10.0.0.2")]);
+        let client = proxies().resolve(peer("10.0.0.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("10.0.0.3"));
+        assert_eq!(client.scheme(), None);
+    }
+
+    #[test]
+    fn x_forwarded_hops() {
+        let headers = headers(&[
+            ("x-forwarded-for", "192.0.2.1, 198.51.100.1, 10.0.0.2"),
+            ("x-forwarded-proto", "http, https, http"),
+            ("x-forwarded-host", "evil, example.com, internal"),
+        ]);
+        let client = proxies().resolve(peer("10.0.0.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("198.51.100.1"));
+        assert_eq!(client.scheme(), Some(&Scheme::HTTPS));
+        assert_eq!(
+            client.host(),
+            Some(&Host::new("example.com", None).unwrap())
+        );
+
+        let headers = self::headers(&[
+            ("x-forwarded-for", "198.51.100.1, 10.0.0.2"),
+            ("x-forwarded-proto", "https"),
+        ]);
+        let client = proxies().resolve(peer("10.0.0.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("198.51.100.1"));
+        assert_eq!(client.scheme(), Some(&Scheme::HTTPS));
+    }
+
+    #[test]
+    fn x_real_ip() {
+        let headers = headers(&[("x-real-ip", "198.51.100.1")]);
+        let client = proxies().resolve(peer("10.0.0.1"), &headers).unwrap();
+        assert_eq!(client.ip(), ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn malformed() {
+        for &(name, value) in &[
+            ("forwarded", "for=192.0.2.1;for=192.0.2.2"),
+            ("x-forwarded-for", "unknown, 198.51.100.1"),
+            ("x-real-ip", "nope"),
+        ] {
+            let headers = headers(&[(name, value)]);
+            assert!(proxies().resolve(peer("10.0.0.1"), &headers).is_err());
+            assert!(proxies().resolve(peer("203.0.113.1"), &headers).is_ok());
+        }
+    }
+}
@@ -0,0 +1,1216 @@
+//! Structured Field Values.
+//!
+//! [RFC8941] defines a common set of data structures for HTTP header values, along with
+//! algorithms to parse and serialize them. A structured field is either a `List`, a `Dictionary`,
+//! or a single `Item`. This module implements those types, including the `Date` and
+//! `DisplayString` bare items added by [RFC9651].
+//!
+//! Parsing is strict: any deviation from the specified syntax fails the entire field, as the RFC
+//! requires. Serialization produces the canonical form of a value, and fails if the value cannot
+//! be represented, such as an integer which is out of range.
+//!
+//! The `parse_field` and `encode_field` functions help implement `Header` for types defined in
+//! terms of structured fields.
+//!
+//! [RFC8941]: https://tools.ietf.org/html/rfc8941
+//! [RFC9651]: https://tools.ietf.org/html/rfc9651
+use http::header::{self, HeaderValue};
+use std::fmt::Write;
+use std::str::{self, FromStr};
+
+use crate::util::{self, Cursor};
+use crate::{Error, ErrorKind, ToValues};
+
+const MAX_INTEGER: i64 = 999_999_999_999_999;
+const MAX_DECIMAL: i64 = 999_999_999_999_999;
+
+/// A decimal number with at most three fractional digits.
+///
+/// The value is stored as an integral number of thousandths.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(i64);
+
+impl Decimal {
+    /// Creates a decimal from a number of thousandths.
+    #[inline]
+    pub fn from_thousandths(thousandths: i64) -> Decimal {
+        Decimal(thousandths)
+    }
+
+    /// Creates a decimal from a floating point number, rounding it to the nearest thousandth.
+    ///
+    /// Ties are rounded to even, as required when serializing. `None` is returned if the number
+    /// is not finite or is too large to represent.
+    pub fn from_f64(value: f64) -> Option<Decimal> {
+        let thousandths = value * 1000.;
+        if !thousandths.is_finite() || thousandths.abs() > MAX_DECIMAL as f64 {
+            return None;
+        }
+
+        let mut rounded = thousandths.round();
+        if (rounded - thousandths).abs() == 0.5 && rounded % 2. != 0. {
+            rounded -= thousandths.signum();
+        }
+        Some(Decimal(rounded as i64))
+    }
+
+    /// Returns the number of thousandths.
+    #[inline]
+    pub fn thousandths(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the value as a floating point number.
+    #[inline]
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / 1000.
+    }
+}
+
+/// A bare item value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BareItem {
+    /// An integer in the range -999,999,999,999,999 to 999,999,999,999,999.
+    Integer(i64),
+    /// A decimal with at most 12 integral digits.
+    Decimal(Decimal),
+    /// A string of printable ASCII characters.
+    String(String),
+    /// A token, like `foo` or `*/*`.
+    Token(String),
+    /// A sequence of arbitrary bytes.
+    ByteSequence(Vec<u8>),
+    /// A boolean.
+    Boolean(bool),
+    /// A date, as a number of seconds since the Unix epoch.
+    Date(i64),
+    /// A string of arbitrary Unicode characters.
+    DisplayString(String),
+}
+
+impl BareItem {
+    /// Returns the integer value, if this is an integer.
+    pub fn as_integer(&self) -> Option<i64> {
+        match *self {
+            BareItem::Integer(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the decimal value, if this is a decimal.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match *self {
+            BareItem::Decimal(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, if this is a string.
+    pub fn as_string(&self) -> Option<&str> {
+        match *self {
+            BareItem::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the token value, if this is a token.
+    pub fn as_token(&self) -> Option<&str> {
+        match *self {
+            BareItem::Token(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if this is a byte sequence.
+    pub fn as_byte_sequence(&self) -> Option<&[u8]> {
+        match *self {
+            BareItem::ByteSequence(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean value, if this is a boolean.
+    pub fn as_boolean(&self) -> Option<bool> {
+        match *self {
+            BareItem::Boolean(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the date value, if this is a date.
+    pub fn as_date(&self) -> Option<i64> {
+        match *self {
+            BareItem::Date(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, if this is a display string.
+    pub fn as_display_string(&self) -> Option<&str> {
+        match *self {
+            BareItem::DisplayString(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn parse(cursor: &mut Cursor) -> Result<BareItem, Error> {
+        match cursor.peek() {
+            Some(b'-') | Some(b'0'..=b'9') => parse_number(cursor),
+            Some(b'"') => parse_string(cursor).map(BareItem::String),
+            Some(b'*') | Some(b'A'..=b'Z') | Some(b'a'..=b'z') => {
+                Ok(BareItem::Token(parse_token(cursor).to_string()))
+            }
+            Some(b':') => parse_byte_sequence(cursor).map(BareItem::ByteSequence),
+            Some(b'?') => parse_boolean(cursor).map(BareItem::Boolean),
+            Some(b'@') => parse_date(cursor).map(BareItem::Date),
+            Some(b'%') => parse_display_string(cursor).map(BareItem::DisplayString),
+            _ => Err(cursor.error(ErrorKind::InvalidValue)),
+        }
+    }
+
+    fn serialize(&self, out: &mut String) -> Result<(), Error> {
+        match *self {
+            BareItem::Integer(value) => serialize_integer(value, out),
+            BareItem::Decimal(value) => serialize_decimal(value, out),
+            BareItem::String(ref value) => serialize_string(value, out),
+            BareItem::Token(ref value) => serialize_token(value, out),
+            BareItem::ByteSequence(ref value) => {
+                write!(out, ":{}:", base64::encode(value)).unwrap();
+                Ok(())
+            }
+            BareItem::Boolean(value) => {
+                out.push_str(if value { "?1" } else { "?0" });
+                Ok(())
+            }
+            BareItem::Date(value) => {
+                out.push('@');
+                serialize_integer(value, out)
+            }
+            BareItem::DisplayString(ref value) => {
+                serialize_display_string(value, out);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An ordered map of parameters attached to an item or inner list.
+///
+/// Keys must consist of lowercase letters, digits, `_`, `-`, `.`, and `*`, and start with a
+/// lowercase letter or `*`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Parameters(Vec<(String, BareItem)>);
+
+impl Parameters {
+    /// Creates an empty set of parameters.
+    #[inline]
+    pub fn new() -> Parameters {
+        Parameters::default()
+    }
+
+    /// Returns the number of parameters.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Determines if there are no parameters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value of a parameter.
+    pub fn get(&self, key: &str) -> Option<&BareItem> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Sets the value of a parameter, returning its previous value.
+    ///
+    /// An existing parameter keeps its position.
+    pub fn insert(&mut self, key: &str, value: BareItem) -> Option<BareItem> {
+        insert(&mut self.0, key, value)
+    }
+
+    /// Removes a parameter, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<BareItem> {
+        remove(&mut self.0, key)
+    }
+
+    /// Returns an iterator over the parameters, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BareItem)> {
+        self.0.iter().map(|(k, v)| (&**k, v))
+    }
+
+    fn parse(cursor: &mut Cursor) -> Result<Parameters, Error> {
+        let mut parameters = Parameters::new();
+        while cursor.eat(b';') {
+            skip_sp(cursor);
+            let key = parse_key(cursor)?;
+            let value = if cursor.eat(b'=') {
+                BareItem::parse(cursor)?
+            } else {
+                BareItem::Boolean(true)
+            };
+            parameters.insert(key, value);
+        }
+        Ok(parameters)
+    }
+
+    fn serialize(&self, out: &mut String) -> Result<(), Error> {
+        for (key, value) in &self.0 {
+            out.push(';');
+            serialize_key(key, out)?;
+            if *value != BareItem::Boolean(true) {
+                out.push('=');
+                value.serialize(out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A bare item with parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    /// The value of the item.
+    pub bare_item: BareItem,
+    /// The parameters of the item.
+    pub parameters: Parameters,
+}
+
+impl Item {
+    /// Creates an item without parameters.
+    #[inline]
+    pub fn new(bare_item: BareItem) -> Item {
+        Item {
+            bare_item,
+            parameters: Parameters::new(),
+        }
+    }
+
+    fn parse_inner(cursor: &mut Cursor) -> Result<Item, Error> {
+        let bare_item = BareItem::parse(cursor)?;
+        let parameters = Parameters::parse(cursor)?;
+        Ok(Item {
+            bare_item,
+            parameters,
+        })
+    }
+
+    fn serialize_inner(&self, out: &mut String) -> Result<(), Error> {
+        self.bare_item.serialize(out)?;
+        self.parameters.serialize(out)
+    }
+}
+
+impl From<BareItem> for Item {
+    #[inline]
+    fn from(bare_item: BareItem) -> Item {
+        Item::new(bare_item)
+    }
+}
+
+/// A parenthesized list of items, with parameters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InnerList {
+    /// The items of the list.
+    pub items: Vec<Item>,
+    /// The parameters of the list.
+    pub parameters: Parameters,
+}
+
+impl InnerList {
+    /// Creates an inner list without parameters.
+    #[inline]
+    pub fn new(items: Vec<Item>) -> InnerList {
+        InnerList {
+            items,
+            parameters: Parameters::new(),
+        }
+    }
+
+    fn parse(cursor: &mut Cursor) -> Result<InnerList, Error> {
+        cursor.expect(b'(')?;
+
+        let mut items = vec![];
+        loop {
+            skip_sp(cursor);
+            if cursor.eat(b')') {
+                break;
+            }
+
+            items.push(Item::parse_inner(cursor)?);
+            match cursor.peek() {
+                Some(b' ') | Some(b')') => {}
+                _ => return Err(cursor.error(ErrorKind::InvalidValue)),
+            }
+        }
+
+        let parameters = Parameters::parse(cursor)?;
+        Ok(InnerList { items, parameters })
+    }
+
+    fn serialize(&self, out: &mut String) -> Result<(), Error> {
+        out.push('(');
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            item.serialize_inner(out)?;
+        }
+        out.push(')');
+        self.parameters.serialize(out)
+    }
+}
+
+/// A member of a list or dictionary: either an item or an inner list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Member {
+    /// An item.
+    Item(Item),
+    /// An inner list.
+    InnerList(InnerList),
+}
+
+impl Member {
+    /// Returns the item, if this member is an item.
+    pub fn as_item(&self) -> Option<&Item> {
+        match *self {
+            Member::Item(ref item) => Some(item),
+            Member::InnerList(_) => None,
+        }
+    }
+
+    /// Returns the inner list, if this member is an inner list.
+    pub fn as_inner_list(&self) -> Option<&InnerList> {
+        match *self {
+            Member::Item(_) => None,
+            Member::InnerList(ref list) => Some(list),
+        }
+    }
+
+    /// Returns the parameters of the member.
+    pub fn parameters(&self) -> &Parameters {
+        match *self {
+            Member::Item(ref item) => &item.parameters,
+            Member::InnerList(ref list) => &list.parameters,
+        }
+    }
+
+    fn parse(cursor: &mut Cursor) -> Result<Member, Error> {
+        if cursor.peek() == Some(b'(') {
+            InnerList::parse(cursor).map(Member::InnerList)
+        } else {
+            Item::parse_inner(cursor).map(Member::Item)
+        }
+    }
+
+    fn serialize(&self, out: &mut String) -> Result<(), Error> {
+        match *self {
+            Member::Item(ref item) => item.serialize_inner(out),
+            Member::InnerList(ref list) => list.serialize(out),
+        }
+    }
+}
+
+impl From<Item> for Member {
+    #[inline]
+    fn from(item: Item) -> Member {
+        Member::Item(item)
+    }
+}
+
+impl From<BareItem> for Member {
+    #[inline]
+    fn from(bare_item: BareItem) -> Member {
+        Member::Item(Item::new(bare_item))
+    }
+}
+
+impl From<InnerList> for Member {
+    #[inline]
+    fn from(list: InnerList) -> Member {
+        Member::InnerList(list)
+    }
+}
+
+/// A list of members.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct List(pub Vec<Member>);
+
+impl List {
+    /// Creates an empty list.
+    #[inline]
+    pub fn new() -> List {
+        List::default()
+    }
+}
+
+impl std::ops::Deref for List {
+    type Target = Vec<Member>;
+
+    #[inline]
+    fn deref(&self) -> &Vec<Member> {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for List {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Vec<Member> {
+        &mut self.0
+    }
+}
+
+/// An ordered map from keys to members.
+///
+/// Keys must consist of lowercase letters, digits, `_`, `-`, `.`, and `*`, and start with a
+/// lowercase letter or `*`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dictionary(Vec<(String, Member)>);
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    #[inline]
+    pub fn new() -> Dictionary {
+        Dictionary::default()
+    }
+
+    /// Returns the number of members.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Determines if the dictionary is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the member with the specified key.
+    pub fn get(&self, key: &str) -> Option<&Member> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Sets the member with the specified key, returning the previous member.
+    ///
+    /// An existing member keeps its position.
+    pub fn insert(&mut self, key: &str, member: Member) -> Option<Member> {
+        insert(&mut self.0, key, member)
+    }
+
+    /// Removes the member with the specified key, returning it.
+    pub fn remove(&mut self, key: &str) -> Option<Member> {
+        remove(&mut self.0, key)
+    }
+
+    /// Returns an iterator over the members, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Member)> {
+        self.0.iter().map(|(k, v)| (&**k, v))
+    }
+}
+
+/// A top-level structured field type: `Item`, `List`, or `Dictionary`.
+pub trait StructuredField: Sized {
+    /// Parses a field value.
+    ///
+    /// Leading and trailing spaces are ignored.
+    fn parse(s: &str) -> Result<Self, Error>;
+
+    /// Serializes the value in its canonical form.
+    ///
+    /// An error is returned if the value contains an invalid component, such as an integer which
+    /// is out of range or a token with invalid characters.
+    fn serialize(&self) -> Result<String, Error>;
+}
+
+impl StructuredField for Item {
+    fn parse(s: &str) -> Result<Item, Error> {
+        parse_top_level(s, Item::parse_inner)
+    }
+
+    fn serialize(&self) -> Result<String, Error> {
+        let mut out = String::new();
+        self.serialize_inner(&mut out)?;
+        Ok(out)
+    }
+}
+
+impl StructuredField for List {
+    fn parse(s: &str) -> Result<List, Error> {
+        parse_top_level(s, |cursor| {
+            let mut list = List::new();
+            parse_members(cursor, |cursor| {
+                list.push(Member::parse(cursor)?);
+                Ok(())
+            })?;
+            Ok(list)
+        })
+    }
+
+    fn serialize(&self) -> Result<String, Error> {
+        let mut out = String::new();
+        for (i, member) in self.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            member.serialize(&mut out)?;
+        }
+        Ok(out)
+    }
+}
+
+impl StructuredField for Dictionary {
+    fn parse(s: &str) -> Result<Dictionary, Error> {
+        parse_top_level(s, |cursor| {
+            let mut dictionary = Dictionary::new();
+            parse_members(cursor, |cursor| {
+                let key = parse_key(cursor)?;
+                let member = if cursor.eat(b'=') {
+                    Member::parse(cursor)?
+                } else {
+                    Member::Item(Item {
+                        bare_item: BareItem::Boolean(true),
+                        parameters: Parameters::parse(cursor)?,
+                    })
+                };
+                dictionary.insert(key, member);
+                Ok(())
+            })?;
+            Ok(dictionary)
+        })
+    }
+
+    fn serialize(&self) -> Result<String, Error> {
+        let mut out = String::new();
+        for (i, (key, member)) in self.0.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            serialize_key(key, &mut out)?;
+            match *member {
+                Member::Item(ref item) if item.bare_item == BareItem::Boolean(true) => {
+                    item.parameters.serialize(&mut out)?;
+                }
+                _ => {
+                    out.push('=');
+                    member.serialize(&mut out)?;
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+macro_rules! from_str {
+    ($($t:ty),*) => {
+        $(
+            impl FromStr for $t {
+                type Err = Error;
+
+                #[inline]
+                fn from_str(s: &str) -> Result<$t, Error> {
+                    StructuredField::parse(s)
+                }
+            }
+        )*
+    };
+}
+
+from_str!(Item, List, Dictionary);
+
+/// Parses a structured header from its raw values.
+///
+/// The values are combined into a single field value before parsing, as required by the RFC. In
+/// particular, a header defined as an `Item` must have exactly one value.
+pub fn parse_field<T>(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<T>, Error>
+where
+    T: StructuredField,
+{
+    let mut combined = String::new();
+    let mut starts = vec![];
+    for (index, value) in values.enumerate() {
+        let value = util::to_str(value).map_err(|e| e.with_index(index))?;
+        if index > 0 {
+            combined.push_str(", ");
+        }
+        starts.push(combined.len());
+        combined.push_str(value);
+    }
+
+    if starts.is_empty() {
+        return Ok(None);
+    }
+
+    T::parse(&combined).map(Some).map_err(|e| {
+        // attribute the error to the value it occurred in
+        let offset = e.offset().unwrap_or(0);
+        let index = starts.iter().rposition(|&start| start <= offset).unwrap();
+        let e = e.with_index(index);
+        match e.offset() {
+            Some(offset) => {
+                let start = starts[index];
+                e.with_offset(offset.saturating_sub(start))
+            }
+            None => e,
+        }
+    })
+}
+
+/// Serializes a structured header into a single value.
+///
+/// # Panics
+///
+/// Panics if the value cannot be serialized.
+pub fn encode_field<T>(value: &T, values: &mut ToValues)
+where
+    T: StructuredField,
+{
+    let value = value
+        .serialize()
+        .expect("failed to serialize structured field");
+    let value = HeaderValue::from_str(&value).expect("failed to encode header");
+    values.append(value);
+}
+
+fn parse_top_level<T, F>(s: &str, f: F) -> Result<T, Error>
+where
+    F: FnOnce(&mut Cursor) -> Result<T, Error>,
+{
+    let mut cursor = Cursor::new(s);
+    skip_sp(&mut cursor);
+    let value = f(&mut cursor)?;
+    skip_sp(&mut cursor);
+    if cursor.is_empty() {
+        Ok(value)
+    } else {
+        Err(cursor.error(ErrorKind::InvalidValue))
+    }
+}
+
+fn parse_members<F>(cursor: &mut Cursor, mut f: F) -> Result<(), Error>
+where
+    F: FnMut(&mut Cursor) -> Result<(), Error>,
+{
+    if cursor.is_empty() {
+        return Ok(());
+    }
+
+    loop {
+        f(cursor)?;
+        cursor.ows();
+        if cursor.is_empty() {
+            return Ok(());
+        }
+        cursor.expect(b',')?;
+        cursor.ows();
+        // trailing commas are not permitted
+        if cursor.is_empty() {
+            return Err(cursor.error(ErrorKind::InvalidValue));
+        }
+    }
+}
+
+fn skip_sp(cursor: &mut Cursor) {
+    while cursor.eat(b' ') {}
+}
+
+fn is_key_char(b: u8) -> bool {
+    b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b)
+}
+
+fn parse_key<'a>(cursor: &mut Cursor<'a>) -> Result<&'a str, Error> {
+    match cursor.peek() {
+        Some(b) if b.is_ascii_lowercase() || b == b'*' => Ok(cursor.take_while(is_key_char)),
+        _ => Err(cursor.error(ErrorKind::InvalidToken)),
+    }
+}
+
+fn serialize_key(key: &str, out: &mut String) -> Result<(), Error> {
+    match key.as_bytes().first() {
+        Some(&b) if (b.is_ascii_lowercase() || b == b'*') && key.bytes().all(is_key_char) => {
+            out.push_str(key);
+            Ok(())
+        }
+        _ => Err(util::invalid_token(key)),
+    }
+}
+
+fn parse_number(cursor: &mut Cursor) -> Result<BareItem, Error> {
+    let start = cursor.pos();
+    let negative = cursor.eat(b'-');
+    match cursor.peek() {
+        Some(b) if b.is_ascii_digit() => {}
+        _ => return Err(cursor.error(ErrorKind::InvalidInteger)),
+    }
+
+    let integer = cursor.take_while(|b| b.is_ascii_digit());
+    if !cursor.eat(b'.') {
+        if integer.len() > 15 {
+            return Err(Error::new(ErrorKind::InvalidInteger).with_offset(start));
+        }
+        let value = integer.parse::<i64>().unwrap();
+        return Ok(BareItem::Integer(if negative { -value } else { value }));
+    }
+
+    let fraction = cursor.take_while(|b| b.is_ascii_digit());
+    if integer.len() > 12 || fraction.is_empty() || fraction.len() > 3 {
+        return Err(Error::new(ErrorKind::InvalidInteger).with_offset(start));
+    }
+    let mut thousandths = integer.parse::<i64>().unwrap() * 1000;
+    thousandths += fraction.parse::<i64>().unwrap() * 10i64.pow(3 - fraction.len() as u32);
+    Ok(BareItem::Decimal(Decimal(if negative {
+        -thousandths
+    } else {
+        thousandths
+    })))
+}
+
+fn serialize_integer(value: i64, out: &mut String) -> Result<(), Error> {
+    if !(-MAX_INTEGER..=MAX_INTEGER).contains(&value) {
+        return Err(Error::new(ErrorKind::InvalidInteger));
+    }
+    write!(out, "{}", value).unwrap();
+    Ok(())
+}
+
+fn serialize_decimal(value: Decimal, out: &mut String) -> Result<(), Error> {
+    if !(-MAX_DECIMAL..=MAX_DECIMAL).contains(&value.0) {
+        return Err(Error::new(ErrorKind::InvalidInteger));
+    }
+
+    if value.0 < 0 {
+        out.push('-');
+    }
+    let abs = value.0.abs();
+    write!(out, "{}.", abs / 1000).unwrap();
+    let fraction = format!("{:03}", abs % 1000);
+    let fraction = fraction.trim_end_matches('0');
+    out.push_str(if fraction.is_empty() { "0" } else { fraction });
+    Ok(())
+}
+
+fn parse_string(cursor: &mut Cursor) -> Result<String, Error> {
+    let start = cursor.pos();
+    cursor.expect(b'"')?;
+
+    let mut out = String::new();
+    loop {
+        match cursor.peek() {
+            Some(b'"') => {
+                cursor.eat(b'"');
+                return Ok(out);
+            }
+            Some(b'\\') => {
+                cursor.eat(b'\\');
+                match cursor.peek() {
+                    Some(b @ b'"') | Some(b @ b'\\') => {
+                        cursor.eat(b);
+                        out.push(b as char);
+                    }
+                    _ => return Err(cursor.error(ErrorKind::InvalidQuotedString)),
+                }
+            }
+            Some(b @ b' '..=b'~') => {
+                cursor.eat(b);
+                out.push(b as char);
+            }
+            Some(_) => return Err(cursor.error(ErrorKind::InvalidQuotedString)),
+            None => return Err(Error::new(ErrorKind::InvalidQuotedString).with_offset(start)),
+        }
+    }
+}
+
+fn serialize_string(value: &str, out: &mut String) -> Result<(), Error> {
+    if !value.bytes().all(|b| (b' '..=b'~').contains(&b)) {
+        return Err(Error::new(ErrorKind::InvalidQuotedString));
+    }
+
+    out.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    Ok(())
+}
+
+fn is_token_char(b: u8) -> bool {
+    util::is_tchar(b) || b == b':' || b == b'/'
+}
+
+fn parse_token<'a>(cursor: &mut Cursor<'a>) -> &'a str {
+    cursor.take_while(is_token_char)
+}
+
+fn serialize_token(value: &str, out: &mut String) -> Result<(), Error> {
+    match value.as_bytes().first() {
+        Some(&b) if (b.is_ascii_alphabetic() || b == b'*') && value.bytes().all(is_token_char) => {
+            out.push_str(value);
+            Ok(())
+        }
+        _ => Err(util::invalid_token(value)),
+    }
+}
+
+fn parse_byte_sequence(cursor: &mut Cursor) -> Result<Vec<u8>, Error> {
+    let start = cursor.pos();
+    cursor.expect(b':')?;
+    let encoded = cursor.take_while(|b| b.is_ascii_alphanumeric() || b"+/=".contains(&b));
+    cursor.expect(b':')?;
+    base64::decode(encoded).map_err(|e| {
+        Error::new(ErrorKind::InvalidValue)
+            .with_offset(start)
+            .with_source(e)
+    })
+}
+
+fn parse_boolean(cursor: &mut Cursor) -> Result<bool, Error> {
+    cursor.expect(b'?')?;
+    if cursor.eat(b'1') {
+        Ok(true)
+    } else if cursor.eat(b'0') {
+        Ok(false)
+    } else {
+        Err(cursor.error(ErrorKind::InvalidValue))
+    }
+}
+
+fn parse_date(cursor: &mut Cursor) -> Result<i64, Error> {
+    cursor.expect(b'@')?;
+    let start = cursor.pos();
+    match parse_number(cursor)? {
+        BareItem::Integer(value) => Ok(value),
+        _ => Err(Error::new(ErrorKind::InvalidDate).with_offset(start)),
+    }
+}
+
+fn parse_display_string(cursor: &mut Cursor) -> Result<String, Error> {
+    let start = cursor.pos();
+    cursor.expect(b'%')?;
+    cursor.expect(b'"')?;
+
+    let mut bytes = vec![];
+    loop {
+        match cursor.peek() {
+            Some(b'"') => {
+                cursor.eat(b'"');
+                break;
+            }
+            Some(b'%') => {
+                cursor.eat(b'%');
+                let hex = cursor.rest().as_bytes();
+                match (
+                    hex.first().and_then(|&b| lower_hex(b)),
+                    hex.get(1).and_then(|&b| lower_hex(b)),
+                ) {
+                    (Some(hi), Some(lo)) => {
+                        bytes.push(hi << 4 | lo);
+                        cursor.set_pos(cursor.pos() + 2);
+                    }
+                    _ => return Err(cursor.error(ErrorKind::InvalidValue)),
+                }
+            }
+            Some(b @ b' '..=b'~') => {
+                cursor.eat(b);
+                bytes.push(b);
+            }
+            Some(_) => return Err(cursor.error(ErrorKind::InvalidValue)),
+            None => return Err(Error::new(ErrorKind::InvalidValue).with_offset(start)),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|e| {
+        Error::new(ErrorKind::InvalidValue)
+            .with_offset(start)
+            .with_source(e)
+    })
+}
+
+fn lower_hex(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        _ => None,
+    }
+}
+
+fn serialize_display_string(value: &str, out: &mut String) {
+    out.push_str("%\"");
+    for b in value.bytes() {
+        if b == b'%' || b == b'"' || !(b' '..=b'~').contains(&b) {
+            write!(out, "%{:02x}", b).unwrap();
+        } else {
+            out.push(b as char);
+        }
+    }
+    out.push('"');
+}
+
+fn insert<T>(entries: &mut Vec<(String, T)>, key: &str, value: T) -> Option<T> {
+    match entries.iter_mut().find(|(k, _)| k == key) {
+        Some(entry) => Some(std::mem::replace(&mut entry.1, value)),
+        None => {
+            entries.push((key.to_string(), value));
+            None
+        }
+    }
+}
+
+fn remove<T>(entries: &mut Vec<(String, T)>, key: &str) -> Option<T> {
+    let idx = entries.iter().position(|(k, _)| k == key)?;
+    Some(entries.remove(idx).1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Header;
+    use http::header::HeaderName;
+    use http::HeaderMap;
+
+    fn round_trip<T>(input: &str, canonical: &str)
+    where
+        T: StructuredField + std::fmt::Debug,
+    {
+        let value = T::parse(input).unwrap_or_else(|e| panic!("{}: {}", input, e));
+        assert_eq!(value.serialize().unwrap(), canonical, "{}", input);
+    }
+
+    #[test]
+    fn items() {
+        let cases = [
+            ("42", "42"),
+            ("-0", "0"),
+            ("999999999999999", "999999999999999"),
+            ("4.5", "4.5"),
+            ("-1.250", "-1.25"),
+            ("1.0", "1.0"),
+            ("\"hello \\\"world\\\"\"", "\"hello \\\"world\\\"\""),
+            ("foo123/456", "foo123/456"),
+            ("*/*", "*/*"),
+            (
+                ":cHJldGVuZCB0aGlzIGlzIGJpbmFyeSBjb250ZW50Lg==:",
+                ":cHJldGVuZCB0aGlzIGlzIGJpbmFyeSBjb250ZW50Lg==:",
+            ),
+            ("?1", "?1"),
+            ("?0", "?0"),
+            ("@1659578233", "@1659578233"),
+            ("%\"f%c3%bc%c3%bc\"", "%\"f%c3%bc%c3%bc\""),
+            ("  abc;a=1;b=?1;c  ", "abc;a=1;b;c"),
+            ("1; a; b=?0", "1;a;b=?0"),
+        ];
+        for &(input, canonical) in &cases {
+            round_trip::<Item>(input, canonical);
+        }
+
+        let item = "%\"f%c3%bc%c3%bc\"".parse::<Item>().unwrap();
+        assert_eq!(item.bare_item.as_display_string(), Some("füü"));
+        let item = "-1.250".parse::<Item>().unwrap();
+        assert_eq!(
+            item.bare_item.as_decimal(),
+            Some(Decimal::from_thousandths(-1250))
+        );
+        let item = ":aGk=:".parse::<Item>().unwrap();
+        assert_eq!(item.bare_item.as_byte_sequence(), Some(&b"hi"[..]));
+    }
+
+    #[test]
+    fn invalid_items() {
+        let cases = [
+            "",
+            "1000000000000000",
+            "1.",
+            "1.2345",
+            "1234567890123.4",
+            "-",
+            "- 1",
+            "\"unterminated",
+            "\"bad \\escape\"",
+            "\"tab\there\"",
+            "\"é\"",
+            "_token",
+            ":aGk=",
+            ":a*b:",
+            "?2",
+            "@1.5",
+            "%\"%C3%BC\"",
+            "%\"%c3\"",
+            "%\"%zz\"",
+            "1, 2",
+            "1;A=2",
+            "1;a=",
+            "\tabc",
+        ];
+        for value in &cases {
+            assert!(value.parse::<Item>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn lists() {
+        let cases = [
+            ("", ""),
+            ("sugar, tea, rum", "sugar, tea, rum"),
+            ("sugar,tea,\trum", "sugar, tea, rum"),
+            (
+                "(\"foo\" \"bar\"), (\"baz\"), (\"bat\" \"one\"), ()",
+                "(\"foo\" \"bar\"), (\"baz\"), (\"bat\" \"one\"), ()",
+            ),
+            (
+                "(  \"foo\";a=1  \"bar\" );lvl=5, abc",
+                "(\"foo\";a=1 \"bar\");lvl=5, abc",
+            ),
+        ];
+        for &(input, canonical) in &cases {
+            round_trip::<List>(input, canonical);
+        }
+
+        let cases = ["a,", "a, ,b", "a b", "(a b", "(a,b)", "(a)b", "(a ;x)"];
+        for value in &cases {
+            assert!(value.parse::<List>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn dictionaries() {
+        let cases = [
+            (
+                "en=\"Applepie\", da=:w4ZibGV0w6ZydGU=:",
+                "en=\"Applepie\", da=:w4ZibGV0w6ZydGU=:",
+            ),
+            ("a=?0, b, c; foo=bar", "a=?0, b, c;foo=bar"),
+            (
+                "rating=1.5, feelings=(joy sadness)",
+                "rating=1.5, feelings=(joy sadness)",
+            ),
+            ("a=1, b=2, a=3", "a=3, b=2"),
+            ("a=(1 2);x, b=?1;y=?1", "a=(1 2);x, b;y"),
+        ];
+        for &(input, canonical) in &cases {
+            round_trip::<Dictionary>(input, canonical);
+        }
+
+        let dictionary = "a=1, b".parse::<Dictionary>().unwrap();
+        assert_eq!(
+            dictionary
+                .get("a")
+                .and_then(|m| m.as_item())
+                .map(|i| &i.bare_item),
+            Some(&BareItem::Integer(1))
+        );
+        assert_eq!(
+            dictionary
+                .get("b")
+                .and_then(|m| m.as_item())
+                .map(|i| &i.bare_item),
+            Some(&BareItem::Boolean(true))
+        );
+
+        let cases = ["A=1", "a=1,", "a=1 b=2", "1=a", "a=", "a=1;"];
+        for value in &cases {
+            assert!(value.parse::<Dictionary>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let mut parameters = Parameters::new();
+        parameters.insert("q", BareItem::Decimal(Decimal::from_f64(0.0005).unwrap()));
+        parameters.insert("x", BareItem::Boolean(false));
+        let item = Item {
+            bare_item: BareItem::String("a\"b".to_string()),
+            parameters,
+        };
+        assert_eq!(item.serialize().unwrap(), "\"a\\\"b\";q=0.0;x=?0");
+
+        assert_eq!(Decimal::from_f64(0.0015).unwrap().thousandths(), 2);
+        assert_eq!(Decimal::from_f64(-2.5005).unwrap().thousandths(), -2500);
+        assert_eq!(Decimal::from_f64(1e13), None);
+
+        let invalid = [
+            BareItem::Integer(1_000_000_000_000_000),
+            BareItem::Decimal(Decimal::from_thousandths(1_000_000_000_000_000)),
+            BareItem::String("é".to_string()),
+            BareItem::String("a\nb".to_string()),
+            BareItem::Token("1abc".to_string()),
+            BareItem::Token("a b".to_string()),
+            BareItem::Token(String::new()),
+            BareItem::Date(-1_000_000_000_000_000),
+        ];
+        for bare_item in &invalid {
+            assert!(
+                Item::new(bare_item.clone()).serialize().is_err(),
+                "{:?}",
+                bare_item
+            );
+        }
+
+        assert_eq!(
+            Item::new(BareItem::DisplayString("50% \"ü\"".to_string()))
+                .serialize()
+                .unwrap(),
+            "%\"50%25 %22%c3%bc%22\""
+        );
+
+        let mut dictionary = Dictionary::new();
+        dictionary.insert("Bad", BareItem::Integer(1).into());
+        assert!(dictionary.serialize().is_err());
+
+        let list = List(vec![
+            BareItem::Token("a".to_string()).into(),
+            InnerList::new(vec![
+                BareItem::Integer(1).into(),
+                BareItem::Integer(2).into(),
+            ])
+            .into(),
+        ]);
+        assert_eq!(list.serialize().unwrap(), "a, (1 2)");
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Foo(Dictionary);
+
+    static FOO: HeaderName = HeaderName::from_static("foo");
+
+    impl Header for Foo {
+        fn name() -> &'static HeaderName {
+            &FOO
+        }
+
+        fn from_values(values: &mut header::ValueIter<HeaderValue>) -> Result<Option<Foo>, Error> {
+            parse_field(values).map(|r| r.map(Foo))
+        }
+
+        fn to_values(&self, values: &mut ToValues) {
+            encode_field(&self.0, values);
+        }
+    }
+
+    #[test]
+    fn field() {
+        let dictionary = "a=1, b, c=(x y)".parse::<Dictionary>().unwrap();
+        util::test_decode(&["a=1, b", "c=(x y)"], &Foo(dictionary.clone()));
+        util::test_encode(&Foo(dictionary), &["a=1, b, c=(x y)"]);
+
+        let mut map = HeaderMap::new();
+        map.append("foo", "a=1, b".parse().unwrap());
+        map.append("foo", "c=(x y)".parse().unwrap());
+        map.append("foo", "d=?2".parse().unwrap());
+        let err = parse_field::<Dictionary>(&mut map.get_all("foo").iter()).unwrap_err();
+        assert_eq!(err.index(), Some(2));
+        assert_eq!(err.offset(), Some(3));
+
+        let mut map = HeaderMap::new();
+        map.append("foo", "1".parse().unwrap());
+        map.append("foo", "2".parse().unwrap());
+        assert!(parse_field::<Item>(&mut map.get_all("foo").iter()).is_err());
+        assert!(parse_field::<Item>(&mut map.get_all("bar").iter())
+            .unwrap()
+            .is_none());
+    }
+}
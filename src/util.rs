@@ -1,39 +1,308 @@
-use http::header::{self, HeaderMap, HeaderValue};
+use http::header::{self, HeaderMap, HeaderValue, InvalidHeaderName};
+use http::method::InvalidMethod;
+use http::uri::Scheme;
 use std::error;
 use std::fmt::{self, Write};
+use std::num::ParseIntError;
 use std::str::FromStr;
 
-use crate::{Error, Header, HeaderMapExt, ToValues};
+use crate::impls::InvalidToken68;
+use crate::{Error, ErrorKind, Header, HeaderMapExt, ToValues};
 
 #[inline]
 pub fn is_token(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
-    }
+    !s.is_empty() && s.as_bytes().iter().all(|b| is_tchar(*b))
+}
 
-    s.as_bytes().iter().all(|b| match *b {
+#[inline]
+pub(crate) fn is_tchar(b: u8) -> bool {
+    matches!(
+        b,
         b'a'..=b'z'
-        | b'A'..=b'Z'
-        | b'0'..=b'9'
-        | b'!'
-        | b'#'
-        | b'$'
-        | b'%'
-        | b'&'
-        | b'\''
-        | b'*'
-        | b'+'
-        | b'-'
-        | b'.'
-        | b'^'
-        | b'_'
-        | b'`'
-        | b'|'
-        | b'~' => true,
-        _ => false,
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'!'
+            | b'#'
+            | b'$'
+            | b'%'
+            | b'&'
+            | b'\''
+            | b'*'
+            | b'+'
+            | b'-'
+            | b'.'
+            | b'^'
+            | b'_'
+            | b'`'
+            | b'|'
+            | b'~'
+    )
+}
+
+/// Returns an `InvalidToken` error pointing at the first invalid byte of `s`.
+pub(crate) fn invalid_token(s: &str) -> Error {
+    let offset = s.bytes().position(|b| !is_tchar(b)).unwrap_or(0);
+    Error::new(ErrorKind::InvalidToken).with_offset(offset)
+}
+
+/// Determines if a string can be represented as a quoted-string.
+#[inline]
+pub fn is_quotable(s: &str) -> bool {
+    s.as_bytes()
+        .iter()
+        .all(|&b| b == b'\t' || (b' '..=b'~').contains(&b))
+}
+
+/// Writes a string as a quoted-string, escaping `"` and `\\`.
+///
+/// The string must satisfy `is_quotable`.
+pub fn write_quoted_string<W>(w: &mut W, s: &str) -> fmt::Result
+where
+    W: Write,
+{
+    w.write_char('"')?;
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            w.write_char('\\')?;
+        }
+        w.write_char(c)?;
+    }
+    w.write_char('"')
+}
+
+/// Writes a string as a token if possible, and as a quoted-string otherwise.
+pub fn write_token_or_quoted_string<W>(w: &mut W, s: &str) -> fmt::Result
+where
+    W: Write,
+{
+    if is_token(s) {
+        w.write_str(s)
+    } else {
+        write_quoted_string(w, s)
+    }
+}
+
+/// A simple cursor over a header value, used by hand-written parsers.
+///
+/// Errors produced by the cursor have their offsets set relative to the start of the string.
+pub(crate) struct Cursor<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(s: &'a str) -> Cursor<'a> {
+        Cursor { s, pos: 0 }
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn set_pos(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.s.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.pos).cloned()
+    }
+
+    pub fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, b: u8) -> Result<(), Error> {
+        if self.eat(b) {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::InvalidValue))
+        }
+    }
+
+    pub fn ows(&mut self) {
+        while let Some(b' ') | Some(b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips whitespace and the empty list elements permitted by the `#rule`.
+    pub fn skip_empty_elements(&mut self) {
+        loop {
+            self.ows();
+            if !self.eat(b',') {
+                break;
+            }
+        }
+    }
+
+    /// Consumes bytes while `f` returns true, returning them.
+    pub fn take_while<F>(&mut self, f: F) -> &'a str
+    where
+        F: Fn(u8) -> bool,
+    {
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            if !f(b) {
+                break;
+            }
+            self.pos += 1;
+        }
+        &self.s[start..self.pos]
+    }
+
+    pub fn token(&mut self) -> Result<&'a str, Error> {
+        let token = self.take_while(is_tchar);
+        if token.is_empty() {
+            Err(self.error(ErrorKind::InvalidToken))
+        } else {
+            Ok(token)
+        }
+    }
+
+    pub fn quoted_string(&mut self) -> Result<String, Error> {
+        let start = self.pos;
+        if !self.eat(b'"') {
+            return Err(self.error(ErrorKind::InvalidQuotedString));
+        }
+
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(b) if b == b'\t' || (b' '..=b'~').contains(&b) => {
+                            out.push(b as char);
+                            self.pos += 1;
+                        }
+                        _ => return Err(self.error(ErrorKind::InvalidQuotedString)),
+                    }
+                }
+                Some(b) if b == b'\t' || (b' '..=b'~').contains(&b) => {
+                    out.push(b as char);
+                    self.pos += 1;
+                }
+                _ => {
+                    return Err(Error::new(ErrorKind::InvalidQuotedString).with_offset(start));
+                }
+            }
+        }
+    }
+
+    pub fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(kind).with_offset(self.pos)
+    }
+}
+
+/// Converts an error produced while parsing a header component into an `Error`.
+///
+/// Errors from this crate are passed through unchanged, and errors from well-known component
+/// parsers are categorized. The original error is retained as the `source` of the result.
+pub fn convert_error<E>(e: E) -> Error
+where
+    E: Into<Box<dyn error::Error + Sync + Send>>,
+{
+    let e = e.into();
+    let e = match e.downcast::<Error>() {
+        Ok(e) => return *e,
+        Err(e) => e,
+    };
+
+    let kind = if e.is::<mime::FromStrError>() {
+        ErrorKind::InvalidMediaType
+    } else if e.is::<InvalidToken68>() {
+        ErrorKind::InvalidToken68
+    } else if e.is::<ParseIntError>() {
+        ErrorKind::InvalidInteger
+    } else if e.is::<InvalidMethod>() || e.is::<InvalidHeaderName>() {
+        ErrorKind::InvalidToken
+    } else {
+        ErrorKind::InvalidValue
+    };
+
+    Error::new(kind).with_source(e)
+}
+
+/// Returns the contents of a header value as a string.
+///
+/// A `NonUtf8` error pointing at the first invalid byte is returned if the value contains bytes
+/// which are not visible ASCII.
+pub fn to_str(value: &HeaderValue) -> Result<&str, Error> {
+    value.to_str().map_err(|e| {
+        let offset = value
+            .as_bytes()
+            .iter()
+            .position(|&b| !(b == b'\t' || (b' '..=b'~').contains(&b)))
+            .unwrap_or(0);
+        Error::new(ErrorKind::NonUtf8)
+            .with_offset(offset)
+            .with_source(e)
     })
 }
 
+/// Parses a non-negative decimal integer consisting only of digits.
+///
+/// Unlike `str::parse`, this rejects a leading `+`.
+pub(crate) fn parse_digits(s: &str) -> Result<u64, Error> {
+    if let Some(offset) = s.bytes().position(|b| !b.is_ascii_digit()) {
+        return Err(Error::new(ErrorKind::InvalidInteger).with_offset(offset));
+    }
+
+    s.parse()
+        .map_err(|e| Error::new(ErrorKind::InvalidInteger).with_source(e))
+}
+
+const MAX_DELTA_SECONDS: u64 = 2_147_483_648;
+
+/// Parses a delta-seconds value, as defined in [RFC7234].
+///
+/// Values too large to represent are clamped to 2^31, as required by the RFC.
+///
+/// [RFC7234]: https://tools.ietf.org/html/rfc7234#section-1.2.1
+pub(crate) fn parse_delta_seconds(s: &str) -> Result<u64, Error> {
+    match parse_digits(s) {
+        Ok(seconds) => Ok(u64::min(seconds, MAX_DELTA_SECONDS)),
+        Err(_) if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => Ok(MAX_DELTA_SECONDS),
+        Err(e) => Err(e),
+    }
+}
+
+/// Parses a URI scheme, as defined in [RFC3986], normalizing it to lowercase.
+///
+/// [RFC3986]: https://tools.ietf.org/html/rfc3986#section-3.1
+pub(crate) fn parse_scheme(s: &str) -> Result<Scheme, Error> {
+    if !s.bytes().next().is_some_and(|b| b.is_ascii_alphabetic()) {
+        return Err(Error::new(ErrorKind::InvalidUri));
+    }
+
+    s.to_ascii_lowercase()
+        .parse()
+        .map_err(|e| Error::new(ErrorKind::InvalidUri).with_source(e))
+}
+
+/// Returns the number of bytes of leading whitespace in `s`.
+#[inline]
+pub(crate) fn leading_ws(s: &str) -> usize {
+    s.len() - s.trim_start().len()
+}
+
 pub fn parse_single_value<T>(
     values: &mut header::ValueIter<HeaderValue>,
 ) -> Result<Option<T>, Error>
@@ -43,12 +312,11 @@ where
 {
     match values.next() {
         Some(value) => {
+            let value = to_str(value).map_err(|e| e.with_index(0))?;
             let value = value
-                .to_str()
-                .map_err(|_| Error::invalid_value())?
                 .trim()
                 .parse()
-                .map_err(|_| Error::invalid_value())?;
+                .map_err(|e| convert_error(e).offset_by(leading_ws(value)).with_index(0))?;
             Ok(Some(value))
         }
         None => Ok(None),
@@ -70,20 +338,35 @@ pub fn parse_comma_delimited<T>(
 where
     T: FromStr,
     T::Err: Into<Box<dyn error::Error + Sync + Send>>,
+{
+    parse_comma_delimited_with(values, |elem| elem.parse().map_err(convert_error))
+}
+
+/// Like `parse_comma_delimited`, but parses each element with `parse` rather than `FromStr`.
+pub(crate) fn parse_comma_delimited_with<T, F>(
+    values: &mut header::ValueIter<HeaderValue>,
+    mut parse: F,
+) -> Result<Option<Vec<T>>, Error>
+where
+    F: FnMut(&str) -> Result<T, Error>,
 {
     let mut out = vec![];
     let mut empty = true;
-    for value in values {
+    for (index, value) in values.enumerate() {
         empty = false;
 
-        let value = value.to_str().map_err(|_| Error::invalid_value())?;
+        let value = to_str(value).map_err(|e| e.with_index(index))?;
+        let mut start = 0;
         for elem in value.split(',') {
+            let offset = start + leading_ws(elem);
+            start += elem.len() + 1;
+
             let elem = elem.trim();
             if elem.is_empty() {
                 continue;
             }
 
-            let elem = elem.parse().map_err(|_| Error::invalid_value())?;
+            let elem = parse(elem).map_err(|e| e.offset_by(offset).with_index(index))?;
             out.push(elem);
         }
     }